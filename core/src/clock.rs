@@ -2,8 +2,12 @@
 The [`Clock`] type.
 
 A clock is a service that returns a [`Timestamp`] representing the current point in time. Clock readings are not guaranteed to be monotonic. They may move forwards or backwards arbitrarily, but for diagnostics to be useful, a clock should strive for accuracy.
+
+A clock may also support monotonic readings through [`Clock::now_monotonic`]. These can't be converted into timestamps, but can be used to measure the time elapsed between two points more accurately than [`Clock::now`].
 */
 
+use core::time::Duration;
+
 use crate::{empty::Empty, timestamp::Timestamp};
 
 /**
@@ -16,12 +20,27 @@ pub trait Clock {
     This method may return `None` if the clock couldn't be read for any reason. That may be because the clock doesn't actually supporting reading now, time moving backwards, or any other reason that could result in an inaccurate reading.
     */
     fn now(&self) -> Option<Timestamp>;
+
+    /**
+    Read the current time from a monotonic source.
+
+    The reading is the time elapsed since some arbitrary point that's fixed for the lifetime of the process. It can't be converted into a [`Timestamp`], and is only meaningful when compared with other readings from the same clock. Unlike [`Clock::now`], monotonic readings never move backwards, so the difference between two of them is an accurate measure of the time elapsed between them, even if the wall-clock is adjusted in the meantime.
+
+    This method is optional. The default implementation returns `None`, in which case callers should fall back to the difference between readings from [`Clock::now`].
+    */
+    fn now_monotonic(&self) -> Option<Duration> {
+        None
+    }
 }
 
 impl<'a, T: Clock + ?Sized> Clock for &'a T {
     fn now(&self) -> Option<Timestamp> {
         (**self).now()
     }
+
+    fn now_monotonic(&self) -> Option<Duration> {
+        (**self).now_monotonic()
+    }
 }
 
 impl<'a, T: Clock> Clock for Option<T> {
@@ -32,6 +51,14 @@ impl<'a, T: Clock> Clock for Option<T> {
             Empty.now()
         }
     }
+
+    fn now_monotonic(&self) -> Option<Duration> {
+        if let Some(time) = self {
+            time.now_monotonic()
+        } else {
+            Empty.now_monotonic()
+        }
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -39,6 +66,10 @@ impl<'a, T: Clock + ?Sized + 'a> Clock for alloc::boxed::Box<T> {
     fn now(&self) -> Option<Timestamp> {
         (**self).now()
     }
+
+    fn now_monotonic(&self) -> Option<Duration> {
+        (**self).now_monotonic()
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -46,6 +77,10 @@ impl<'a, T: Clock + ?Sized + 'a> Clock for alloc::sync::Arc<T> {
     fn now(&self) -> Option<Timestamp> {
         (**self).now()
     }
+
+    fn now_monotonic(&self) -> Option<Duration> {
+        (**self).now_monotonic()
+    }
 }
 
 impl Clock for Empty {
@@ -55,10 +90,13 @@ impl Clock for Empty {
 }
 
 mod internal {
+    use core::time::Duration;
+
     use super::Timestamp;
 
     pub trait DispatchClock {
         fn dispatch_now(&self) -> Option<Timestamp>;
+        fn dispatch_now_monotonic(&self) -> Option<Duration>;
     }
 
     pub trait SealedClock {
@@ -85,18 +123,30 @@ impl<T: Clock> internal::DispatchClock for T {
     fn dispatch_now(&self) -> Option<Timestamp> {
         self.now()
     }
+
+    fn dispatch_now_monotonic(&self) -> Option<Duration> {
+        self.now_monotonic()
+    }
 }
 
 impl<'a> Clock for dyn ErasedClock + 'a {
     fn now(&self) -> Option<Timestamp> {
         self.erase_clock().0.dispatch_now()
     }
+
+    fn now_monotonic(&self) -> Option<Duration> {
+        self.erase_clock().0.dispatch_now_monotonic()
+    }
 }
 
 impl<'a> Clock for dyn ErasedClock + Send + Sync + 'a {
     fn now(&self) -> Option<Timestamp> {
         self.erase_clock().0.dispatch_now()
     }
+
+    fn now_monotonic(&self) -> Option<Duration> {
+        self.erase_clock().0.dispatch_now_monotonic()
+    }
 }
 
 impl<'a> dyn ErasedClock + 'a {
@@ -106,6 +156,13 @@ impl<'a> dyn ErasedClock + 'a {
     pub fn now(&self) -> Option<Timestamp> {
         Clock::now(self)
     }
+
+    /**
+    Get the current time from a monotonic source.
+    */
+    pub fn now_monotonic(&self) -> Option<Duration> {
+        Clock::now_monotonic(self)
+    }
 }
 
 impl<'a> dyn ErasedClock + Send + Sync + 'a {
@@ -115,11 +172,18 @@ impl<'a> dyn ErasedClock + Send + Sync + 'a {
     pub fn now(&self) -> Option<Timestamp> {
         Clock::now(self)
     }
+
+    /**
+    Get the current time from a monotonic source.
+    */
+    pub fn now_monotonic(&self) -> Option<Duration> {
+        Clock::now_monotonic(self)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use core::{cell::Cell, time::Duration};
+    use core::cell::Cell;
 
     use super::*;
 
@@ -143,4 +207,25 @@ mod tests {
 
         assert_eq!(1, clock.now.get());
     }
+
+    #[test]
+    fn erased_clock_monotonic() {
+        struct SomeClock;
+
+        impl Clock for SomeClock {
+            fn now(&self) -> Option<Timestamp> {
+                None
+            }
+
+            fn now_monotonic(&self) -> Option<Duration> {
+                Some(Duration::from_secs(3))
+            }
+        }
+
+        assert_eq!(
+            Some(Duration::from_secs(3)),
+            (&SomeClock as &dyn ErasedClock).now_monotonic()
+        );
+        assert_eq!(None, (&Empty as &dyn ErasedClock).now_monotonic());
+    }
 }
@@ -7,6 +7,8 @@ Properties, also called attributes in some systems, are the structured data asso
 
 [`Props`] can be fed to a [`crate::template::Template`] to render it into a user-facing message.
 
+[`Props`] can be renamed, added, removed, or derived from each other using a [`Transform`]. This is useful for normalizing properties from different sources before they're emitted.
+
 Well-known properties described in [`crate::well_known`] are used to extend `emit`'s event model with different kinds of diagnostic data.
 */
 
@@ -1659,6 +1661,586 @@ mod std_support {
     }
 }
 
+#[cfg(feature = "alloc")]
+mod transform_support {
+    use alloc::{sync::Arc, vec::Vec};
+    use core::{fmt, ops::ControlFlow};
+
+    use crate::{
+        emitter::{Emitter, wrapping::Wrapping},
+        event::ToEvent,
+        str::Str,
+        value::{OwnedValue, ToValue, Value},
+    };
+
+    use super::{ErasedProps, Props};
+
+    /**
+    A declarative set of transformations to apply to [`Props`].
+
+    Transformations are applied in the order they're added, with each one seeing the result of those before it. A [`Transform`] can be applied to a set of properties directly through [`Transform::apply`], or to the properties of events through its [`Wrapping`] implementation.
+
+    Applying a transformation doesn't copy the original properties. If no transformations apply to a set of properties then events are forwarded without any changes.
+
+    The message of an event is still rendered from its original properties. Any holes in its template for properties that are renamed, removed, or changed are rendered into text before the event is forwarded.
+    */
+    #[derive(Clone, Default)]
+    pub struct Transform {
+        steps: Vec<Step>,
+    }
+
+    type MapValueFn = dyn Fn(Value) -> OwnedValue + Send + Sync;
+    type ComputedFn = dyn Fn(&dyn ErasedProps) -> Option<OwnedValue> + Send + Sync;
+
+    #[derive(Clone)]
+    enum Step {
+        Rename {
+            from: Str<'static>,
+            to: Str<'static>,
+        },
+        InsertIfAbsent {
+            key: Str<'static>,
+            value: OwnedValue,
+        },
+        Remove {
+            key: Str<'static>,
+        },
+        MapValue {
+            key: Str<'static>,
+            map: Arc<MapValueFn>,
+        },
+        Computed {
+            key: Str<'static>,
+            compute: Arc<ComputedFn>,
+        },
+    }
+
+    impl Transform {
+        /**
+        Create a new set of transformations that doesn't change anything.
+        */
+        pub fn new() -> Self {
+            Transform::default()
+        }
+
+        /**
+        Rename the property `from` to `to`.
+
+        If a property called `to` is already present then it's replaced. If there's no property called `from` then this transformation does nothing.
+        */
+        pub fn rename(
+            mut self,
+            from: impl Into<Str<'static>>,
+            to: impl Into<Str<'static>>,
+        ) -> Self {
+            self.steps.push(Step::Rename {
+                from: from.into(),
+                to: to.into(),
+            });
+            self
+        }
+
+        /**
+        Add a property with the given `key` and `value`, if one isn't already present.
+        */
+        pub fn insert_if_absent(
+            mut self,
+            key: impl Into<Str<'static>>,
+            value: impl ToValue,
+        ) -> Self {
+            self.steps.push(Step::InsertIfAbsent {
+                key: key.into(),
+                value: value.to_value().to_shared(),
+            });
+            self
+        }
+
+        /**
+        Remove the property with the given `key`.
+        */
+        pub fn remove(mut self, key: impl Into<Str<'static>>) -> Self {
+            self.steps.push(Step::Remove { key: key.into() });
+            self
+        }
+
+        /**
+        Replace the value of the property with the given `key` with the result of `map`.
+
+        If there's no property with the given `key` then `map` isn't called.
+        */
+        pub fn map_value<V: ToValue>(
+            mut self,
+            key: impl Into<Str<'static>>,
+            map: impl Fn(Value) -> V + Send + Sync + 'static,
+        ) -> Self {
+            self.steps.push(Step::MapValue {
+                key: key.into(),
+                map: Arc::new(move |value| map(value).to_value().to_shared()),
+            });
+            self
+        }
+
+        /**
+        Add a property with the given `key` and a value computed from the properties transformed so far.
+
+        If `compute` returns `Some` then its value replaces any existing property with the given `key`. If `compute` returns `None` then the properties are left unchanged.
+        */
+        pub fn computed<V: ToValue>(
+            mut self,
+            key: impl Into<Str<'static>>,
+            compute: impl Fn(&dyn ErasedProps) -> Option<V> + Send + Sync + 'static,
+        ) -> Self {
+            self.steps.push(Step::Computed {
+                key: key.into(),
+                compute: Arc::new(move |props| {
+                    compute(props).map(|value| value.to_value().to_shared())
+                }),
+            });
+            self
+        }
+
+        /**
+        Apply the transformations to a set of properties.
+        */
+        pub fn apply<P: Props>(&self, props: P) -> Transformed<P> {
+            let changes = self.changes(&props);
+
+            Transformed { props, changes }
+        }
+
+        fn changes(&self, props: &impl Props) -> Changes {
+            let mut changes = Changes::default();
+
+            for step in &self.steps {
+                match step {
+                    Step::Rename { from, to } => {
+                        if from != to && changes.contains(props, from) {
+                            changes.remove(props, to);
+                            changes.rename(props, from, to);
+                        }
+                    }
+                    Step::InsertIfAbsent { key, value } => {
+                        if !changes.contains(props, key) {
+                            changes.added.push((key.clone(), value.clone()));
+                        }
+                    }
+                    Step::Remove { key } => changes.remove(props, key),
+                    Step::MapValue { key, map } => {
+                        let value = View {
+                            props,
+                            changes: &changes,
+                        }
+                        .get(key)
+                        .map(|value| map(value));
+
+                        if let Some(value) = value {
+                            changes.set_value(props, key, value);
+                        }
+                    }
+                    Step::Computed { key, compute } => {
+                        let value = compute(&View {
+                            props,
+                            changes: &changes,
+                        });
+
+                        if let Some(value) = value {
+                            changes.remove(props, key);
+                            changes.added.push((key.clone(), value));
+                        }
+                    }
+                }
+            }
+
+            changes
+        }
+    }
+
+    impl fmt::Debug for Transform {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("Transform").finish_non_exhaustive()
+        }
+    }
+
+    impl Wrapping for Transform {
+        fn wrap<O: Emitter, E: ToEvent>(&self, output: O, evt: E) {
+            let evt = evt.to_event();
+
+            let changes = self.changes(evt.props());
+
+            if changes.is_empty() {
+                output.emit(evt);
+                return;
+            }
+
+            // Render any holes for changed properties using their original values,
+            // so the message of the event doesn't change
+            let tpl = evt
+                .tpl()
+                .render_holes(evt.props().filter(|k, _| changes.affects(&k)));
+
+            let evt = match tpl {
+                Some(tpl) => evt.with_tpl(tpl),
+                None => evt,
+            };
+
+            output.emit(evt.map_props(|props| Transformed { props, changes }));
+        }
+    }
+
+    /**
+    The result of calling [`Transform::apply`].
+    */
+    pub struct Transformed<P> {
+        props: P,
+        changes: Changes,
+    }
+
+    impl<P: Props> Props for Transformed<P> {
+        fn for_each<'kv, F: FnMut(Str<'kv>, Value<'kv>) -> ControlFlow<()>>(
+            &'kv self,
+            for_each: F,
+        ) -> ControlFlow<()> {
+            self.changes.for_each(&self.props, for_each)
+        }
+
+        fn is_unique(&self) -> bool {
+            self.props.is_unique() && self.changes.is_empty()
+        }
+    }
+
+    /*
+    The changes made by a `Transform` to a set of properties.
+
+    Changes to existing properties are tracked by their original key, so they
+    apply to all of its duplicates. New properties are added after existing ones.
+    */
+    #[derive(Default)]
+    struct Changes {
+        existing: Vec<Change>,
+        added: Vec<(Str<'static>, OwnedValue)>,
+    }
+
+    struct Change {
+        original: Str<'static>,
+        // `None` if the property has been removed
+        key: Option<Str<'static>>,
+        value: Option<OwnedValue>,
+    }
+
+    enum Target {
+        Existing(usize),
+        Added(usize),
+        Unchanged,
+    }
+
+    impl Changes {
+        fn is_empty(&self) -> bool {
+            self.existing.is_empty() && self.added.is_empty()
+        }
+
+        fn affects(&self, key: &Str) -> bool {
+            self.existing.iter().any(|change| change.original == *key)
+                || self.added.iter().any(|(k, _)| k == key)
+        }
+
+        fn find(&self, props: &impl Props, key: &Str) -> Option<Target> {
+            if let Some(i) = self.added.iter().position(|(k, _)| k == key) {
+                return Some(Target::Added(i));
+            }
+
+            if let Some(i) = self
+                .existing
+                .iter()
+                .position(|change| change.key.as_ref() == Some(key))
+            {
+                return Some(Target::Existing(i));
+            }
+
+            if !self.existing.iter().any(|change| change.original == *key)
+                && props.get(key).is_some()
+            {
+                return Some(Target::Unchanged);
+            }
+
+            None
+        }
+
+        fn contains(&self, props: &impl Props, key: &Str) -> bool {
+            self.find(props, key).is_some()
+        }
+
+        fn remove(&mut self, props: &impl Props, key: &Str<'static>) {
+            match self.find(props, key) {
+                Some(Target::Added(i)) => {
+                    self.added.remove(i);
+                }
+                Some(Target::Existing(i)) => self.existing[i].key = None,
+                Some(Target::Unchanged) => self.existing.push(Change {
+                    original: key.clone(),
+                    key: None,
+                    value: None,
+                }),
+                None => (),
+            }
+        }
+
+        fn rename(&mut self, props: &impl Props, from: &Str<'static>, to: &Str<'static>) {
+            match self.find(props, from) {
+                Some(Target::Added(i)) => self.added[i].0 = to.clone(),
+                Some(Target::Existing(i)) => self.existing[i].key = Some(to.clone()),
+                Some(Target::Unchanged) => self.existing.push(Change {
+                    original: from.clone(),
+                    key: Some(to.clone()),
+                    value: None,
+                }),
+                None => (),
+            }
+        }
+
+        fn for_each<'kv, P: Props + ?Sized, F: FnMut(Str<'kv>, Value<'kv>) -> ControlFlow<()>>(
+            &'kv self,
+            props: &'kv P,
+            mut for_each: F,
+        ) -> ControlFlow<()> {
+            props.for_each(|k, v| {
+                match self.existing.iter().find(|change| change.original == k) {
+                    Some(Change {
+                        key: Some(key),
+                        value,
+                        ..
+                    }) => for_each(
+                        key.by_ref(),
+                        value.as_ref().map(|value| value.by_ref()).unwrap_or(v),
+                    ),
+                    Some(Change { key: None, .. }) => ControlFlow::Continue(()),
+                    None => for_each(k, v),
+                }
+            })?;
+
+            for (k, v) in &self.added {
+                for_each(k.by_ref(), v.by_ref())?;
+            }
+
+            ControlFlow::Continue(())
+        }
+
+        fn set_value(&mut self, props: &impl Props, key: &Str<'static>, value: OwnedValue) {
+            match self.find(props, key) {
+                Some(Target::Added(i)) => self.added[i].1 = value,
+                Some(Target::Existing(i)) => self.existing[i].value = Some(value),
+                Some(Target::Unchanged) => self.existing.push(Change {
+                    original: key.clone(),
+                    key: Some(key.clone()),
+                    value: Some(value),
+                }),
+                None => (),
+            }
+        }
+    }
+
+    struct View<'a, P: ?Sized> {
+        props: &'a P,
+        changes: &'a Changes,
+    }
+
+    impl<'a, P: Props + ?Sized> Props for View<'a, P> {
+        fn for_each<'kv, F: FnMut(Str<'kv>, Value<'kv>) -> ControlFlow<()>>(
+            &'kv self,
+            for_each: F,
+        ) -> ControlFlow<()> {
+            self.changes.for_each(self.props, for_each)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        use alloc::{format, string::String, vec};
+
+        fn collect(props: impl Props) -> Vec<String> {
+            let mut collected = Vec::new();
+
+            let _ = props.for_each(|k, v| {
+                collected.push(format!("{k}={v}"));
+                ControlFlow::Continue(())
+            });
+
+            collected
+        }
+
+        #[test]
+        fn transform_empty() {
+            let props = [("a", 1), ("b", 2)];
+
+            let transformed = Transform::new().apply(&props);
+
+            assert!(transformed.changes.is_empty());
+            assert_eq!(vec!["a=1", "b=2"], collect(&transformed));
+        }
+
+        #[test]
+        fn transform_rename() {
+            let props = [("user", 1), ("user.id", 2), ("user", 3)];
+
+            let transformed = Transform::new().rename("user", "user.id").apply(&props);
+
+            assert_eq!(vec!["user.id=1", "user.id=3"], collect(&transformed));
+            assert_eq!(1, transformed.pull::<i32, _>("user.id").unwrap());
+
+            let transformed = Transform::new().rename("missing", "user.id").apply(&props);
+
+            assert!(transformed.changes.is_empty());
+        }
+
+        #[test]
+        fn transform_rename_chain() {
+            let props = [("a", 1)];
+
+            let transformed = Transform::new()
+                .rename("a", "b")
+                .rename("b", "c")
+                .apply(&props);
+
+            assert_eq!(vec!["c=1"], collect(&transformed));
+
+            let transformed = Transform::new()
+                .rename("a", "b")
+                .rename("a", "c")
+                .apply(&props);
+
+            assert_eq!(vec!["b=1"], collect(&transformed));
+        }
+
+        #[test]
+        fn transform_insert_if_absent() {
+            let props = [("a", 1)];
+
+            let transformed = Transform::new()
+                .insert_if_absent("a", 2)
+                .insert_if_absent("deployment.environment", "production")
+                .apply(&props);
+
+            assert_eq!(
+                vec!["a=1", "deployment.environment=production"],
+                collect(&transformed)
+            );
+        }
+
+        #[test]
+        fn transform_remove() {
+            let props = [("a", 1), ("b", 2), ("a", 3)];
+
+            let transformed = Transform::new()
+                .insert_if_absent("c", 4)
+                .remove("a")
+                .remove("c")
+                .apply(&props);
+
+            assert_eq!(vec!["b=2"], collect(&transformed));
+        }
+
+        #[test]
+        fn transform_map_value() {
+            let props = [("a", 1), ("b", 2)];
+
+            let transformed = Transform::new()
+                .rename("a", "c")
+                .map_value("c", |v| v.cast::<i32>().unwrap_or_default() * 10)
+                .map_value("missing", |_| -> i32 { unreachable!() })
+                .apply(&props);
+
+            assert_eq!(vec!["c=10", "b=2"], collect(&transformed));
+            assert_eq!(10, transformed.pull::<i32, _>("c").unwrap());
+        }
+
+        #[test]
+        fn transform_computed() {
+            let props = [("http.status_code", 404), ("http.status_class", 0)];
+
+            let transformed = Transform::new()
+                .computed("http.status_class", |props| {
+                    props
+                        .pull::<u16, _>("http.status_code")
+                        .map(|code| format!("{}xx", code / 100))
+                })
+                .computed("skipped", |_| None::<i32>)
+                .apply(&props);
+
+            assert_eq!(
+                vec!["http.status_code=404", "http.status_class=4xx"],
+                collect(&transformed)
+            );
+        }
+
+        #[test]
+        fn transform_wrap() {
+            use crate::{empty::Empty, event::Event, path::Path, template::Template};
+
+            let emitted = core::cell::RefCell::new(Vec::new());
+
+            let transform = Transform::new().rename("user", "user.id");
+
+            transform.wrap(
+                crate::emitter::from_fn(|evt| {
+                    *emitted.borrow_mut() = collect(evt.props());
+                }),
+                Event::new(
+                    Path::new_raw("test"),
+                    Template::literal("test"),
+                    Empty,
+                    [("user", 1)],
+                ),
+            );
+
+            assert_eq!(vec!["user.id=1"], *emitted.borrow());
+        }
+
+        #[test]
+        fn transform_wrap_msg() {
+            use crate::{empty::Empty, event::Event, path::Path, template::Template};
+
+            let tpl = Template::parse("Hello, {user} from {org} in {region}").unwrap();
+
+            for (transform, expected) in [
+                (
+                    Transform::new().rename("user", "user.id"),
+                    "Hello, Rust from emit in {region}",
+                ),
+                (
+                    Transform::new().remove("user").remove("org"),
+                    "Hello, Rust from emit in {region}",
+                ),
+                (
+                    Transform::new()
+                        .map_value("org", |_| "redacted")
+                        .insert_if_absent("region", "au"),
+                    "Hello, Rust from emit in au",
+                ),
+            ] {
+                let emitted = core::cell::RefCell::new(None);
+
+                transform.wrap(
+                    crate::emitter::from_fn(|evt| {
+                        *emitted.borrow_mut() = Some(evt.msg().to_string());
+                    }),
+                    Event::new(
+                        Path::new_raw("test"),
+                        tpl.clone(),
+                        Empty,
+                        [("user", "Rust"), ("org", "emit")],
+                    ),
+                );
+
+                assert_eq!(Some(expected), emitted.borrow().as_deref());
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub use self::transform_support::*;
+
 /**
 The result of calling [`Props::as_map`].
 
@@ -458,6 +458,10 @@ impl<T: Clock> Clock for AssertInternal<T> {
     fn now(&self) -> Option<Timestamp> {
         self.0.now()
     }
+
+    fn now_monotonic(&self) -> Option<core::time::Duration> {
+        self.0.now_monotonic()
+    }
 }
 
 impl<T: Rng> Rng for AssertInternal<T> {
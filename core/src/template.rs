@@ -25,6 +25,9 @@ use core::{
 #[cfg(feature = "alloc")]
 use alloc::boxed::Box;
 
+#[cfg(feature = "alloc")]
+pub use self::alloc_support::ParseTemplateError;
+
 use crate::{
     empty::Empty,
     props::Props,
@@ -893,7 +896,14 @@ This type supports formatting values using standard Rust flags like padding and
 */
 #[derive(Clone)]
 pub struct Formatter {
-    fmt: fn(Value, &mut fmt::Formatter) -> fmt::Result,
+    kind: FormatterKind,
+}
+
+#[derive(Clone)]
+enum FormatterKind {
+    Fn(fn(Value, &mut fmt::Formatter) -> fmt::Result),
+    #[cfg(feature = "alloc")]
+    Spec(alloc_support::FormatSpec),
 }
 
 impl fmt::Debug for Formatter {
@@ -909,14 +919,20 @@ impl Formatter {
     It's the responsibility of the function to actually write the value into the formatter.
     */
     pub const fn new(fmt: fn(Value, &mut fmt::Formatter) -> fmt::Result) -> Self {
-        Formatter { fmt }
+        Formatter {
+            kind: FormatterKind::Fn(fmt),
+        }
     }
 
     /**
     Invoke the formatter on a given value.
     */
     pub fn fmt(&self, value: Value, f: &mut fmt::Formatter) -> fmt::Result {
-        (self.fmt)(value, f)
+        match self.kind {
+            FormatterKind::Fn(fmt) => fmt(value, f),
+            #[cfg(feature = "alloc")]
+            FormatterKind::Spec(ref spec) => spec.fmt(value, f),
+        }
     }
 
     /**
@@ -925,18 +941,18 @@ impl Formatter {
     pub fn apply<'b>(&'b self, value: Value<'b>) -> impl fmt::Display + 'b {
         struct FormatValue<'a> {
             value: Value<'a>,
-            fmt: fn(Value, &mut fmt::Formatter) -> fmt::Result,
+            formatter: &'a Formatter,
         }
 
         impl<'a> fmt::Display for FormatValue<'a> {
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                (self.fmt)(self.value.by_ref(), f)
+                self.formatter.fmt(self.value.by_ref(), f)
             }
         }
 
         FormatValue {
             value,
-            fmt: self.fmt,
+            formatter: self,
         }
     }
 }
@@ -957,7 +973,11 @@ enum PartKind<'a> {
 mod alloc_support {
     use super::*;
 
-    use alloc::vec::Vec;
+    use core::{fmt::Write as _, mem, str::FromStr};
+
+    use alloc::{string::String, vec::Vec};
+
+    use unicode_ident::{is_xid_continue, is_xid_start};
 
     impl Template<'static> {
         /**
@@ -1017,6 +1037,428 @@ mod alloc_support {
         }
     }
 
+    impl<'a> Template<'a> {
+        /**
+        Get a new template from this one, rendering any holes with a matching property in `props` into text.
+
+        Holes without a matching property in `props` are kept as-is. If no holes match then this method returns `None`.
+
+        This is useful for keeping the message of an event the same when its properties are changed, by rendering the holes of changed properties using their original values.
+        */
+        pub fn render_holes(&self, props: impl Props) -> Option<Template<'static>> {
+            let matches =
+                |part: &Part| part.label().is_some_and(|label| props.get(label).is_some());
+
+            if !self.kind.parts().iter().any(matches) {
+                return None;
+            }
+
+            let mut parts = Vec::new();
+
+            for part in self.kind.parts() {
+                if matches(part) {
+                    let mut text = String::new();
+                    let _ = part.write(false, &mut text, &props);
+
+                    parts.push(text_part(text));
+                } else {
+                    parts.push(part.to_owned());
+                }
+            }
+
+            Some(Template::new_owned(parts))
+        }
+    }
+
+    impl Template<'static> {
+        /**
+        Parse a template from its text representation.
+
+        Templates use the same syntax as `emit`'s macros: text with holes for properties wrapped in `{}`, like `Hello, {user}`. Literal `{` and `}` characters are escaped by doubling them, like `{{` and `}}`. The label of a hole must be a valid Rust identifier.
+
+        A hole may also include a format specification after a `:`, using the same syntax as the standard library's [`core::fmt`], like `{x:.2}` or `{x:>8?}`. Widths and precisions must be literals, and only the [`fmt::Display`] (default) and [`fmt::Debug`] (`?`) traits are supported. The specification is applied to values interpolated into the hole through its [`Formatter`].
+
+        If the template is invalid then the returned [`ParseTemplateError`] includes the position in the input it was detected at.
+        */
+        pub fn parse(template: &str) -> Result<Self, ParseTemplateError> {
+            let mut parts = Vec::new();
+            let mut text = String::new();
+
+            let mut pos = 0;
+            while let Some(c) = template[pos..].chars().next() {
+                match c {
+                    '{' if template[pos + 1..].starts_with('{') => {
+                        text.push('{');
+                        pos += 2;
+                    }
+                    '}' if template[pos + 1..].starts_with('}') => {
+                        text.push('}');
+                        pos += 2;
+                    }
+                    '}' => {
+                        return Err(ParseTemplateError::new(
+                            pos,
+                            ParseTemplateErrorKind::UnexpectedClose,
+                        ));
+                    }
+                    '{' => {
+                        let start = pos + 1;
+
+                        let Some(len) = template[start..].find(['{', '}']) else {
+                            return Err(ParseTemplateError::new(
+                                pos,
+                                ParseTemplateErrorKind::UnterminatedHole,
+                            ));
+                        };
+
+                        let end = start + len;
+
+                        if template[end..].starts_with('{') {
+                            return Err(ParseTemplateError::new(
+                                end,
+                                ParseTemplateErrorKind::UnexpectedOpen,
+                            ));
+                        }
+
+                        if !text.is_empty() {
+                            parts.push(text_part(mem::take(&mut text)));
+                        }
+
+                        parts.push(parse_hole(&template[start..end], start)?);
+
+                        pos = end + 1;
+                    }
+                    c => {
+                        text.push(c);
+                        pos += c.len_utf8();
+                    }
+                }
+            }
+
+            if !text.is_empty() {
+                parts.push(text_part(text));
+            }
+
+            Ok(Template::new_owned(parts))
+        }
+    }
+
+    impl FromStr for Template<'static> {
+        type Err = ParseTemplateError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Template::parse(s)
+        }
+    }
+
+    fn text_part(text: String) -> Part<'static> {
+        let needs_escaping = text.contains(['{', '}']);
+
+        Part::text_owned(text).with_needs_escaping_raw(needs_escaping)
+    }
+
+    fn parse_hole(hole: &str, offset: usize) -> Result<Part<'static>, ParseTemplateError> {
+        let (label, spec) = match hole.split_once(':') {
+            Some((label, spec)) => (label, Some(spec)),
+            None => (hole, None),
+        };
+
+        // Whitespace around the label is allowed, like `{ user }`
+        let label_offset = offset + (label.len() - label.trim_start().len());
+        let label = label.trim();
+
+        if !is_valid_label(label) {
+            return Err(ParseTemplateError::new(
+                label_offset,
+                ParseTemplateErrorKind::InvalidLabel,
+            ));
+        }
+
+        let part = Part::hole_owned(label);
+
+        match spec {
+            Some(spec) if !spec.is_empty() => {
+                let spec = FormatSpec::parse(spec, offset + hole.len() - spec.len())?;
+
+                Ok(part.with_formatter(Formatter {
+                    kind: FormatterKind::Spec(spec),
+                }))
+            }
+            _ => Ok(part),
+        }
+    }
+
+    fn is_valid_label(label: &str) -> bool {
+        let mut chars = label.chars();
+
+        let Some(first) = chars.next() else {
+            return false;
+        };
+
+        if !(is_xid_start(first) || (first == '_' && label.len() > 1)) {
+            return false;
+        }
+
+        chars.all(is_xid_continue)
+    }
+
+    /**
+    A runtime format specification, like `>8.2?`.
+    */
+    #[derive(Debug, Clone, Copy)]
+    pub(super) struct FormatSpec {
+        fill: char,
+        align: Option<Align>,
+        plus: bool,
+        alternate: bool,
+        zero: bool,
+        width: usize,
+        precision: Option<usize>,
+        debug: bool,
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    enum Align {
+        Left,
+        Center,
+        Right,
+    }
+
+    impl FormatSpec {
+        fn parse(spec: &str, offset: usize) -> Result<Self, ParseTemplateError> {
+            // [[fill]align][sign]['#']['0'][width]['.' precision]['?']
+            let mut parsed = FormatSpec {
+                fill: ' ',
+                align: None,
+                plus: false,
+                alternate: false,
+                zero: false,
+                width: 0,
+                precision: None,
+                debug: false,
+            };
+
+            fn align(c: Option<char>) -> Option<Align> {
+                match c? {
+                    '<' => Some(Align::Left),
+                    '^' => Some(Align::Center),
+                    '>' => Some(Align::Right),
+                    _ => None,
+                }
+            }
+
+            fn digits(spec: &str, pos: &mut usize) -> Option<usize> {
+                let len = spec[*pos..]
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(spec.len() - *pos);
+
+                let digits = &spec[*pos..*pos + len];
+                *pos += len;
+
+                digits.parse().ok()
+            }
+
+            let mut pos = 0;
+
+            let mut chars = spec.chars();
+            let first = chars.next();
+            if let Some(align) = align(chars.next()) {
+                parsed.fill = first.expect("missing fill");
+                parsed.align = Some(align);
+                pos += parsed.fill.len_utf8() + 1;
+            } else if let Some(align) = align(first) {
+                parsed.align = Some(align);
+                pos += 1;
+            }
+
+            if spec[pos..].starts_with('+') {
+                parsed.plus = true;
+                pos += 1;
+            } else if spec[pos..].starts_with('-') {
+                // The `-` flag is accepted, but unused, by the standard library
+                pos += 1;
+            }
+
+            if spec[pos..].starts_with('#') {
+                parsed.alternate = true;
+                pos += 1;
+            }
+
+            if spec[pos..].starts_with('0') {
+                parsed.zero = true;
+                pos += 1;
+            }
+
+            if let Some(width) = digits(spec, &mut pos) {
+                parsed.width = width;
+            }
+
+            if spec[pos..].starts_with('.') {
+                pos += 1;
+
+                let Some(precision) = digits(spec, &mut pos) else {
+                    return Err(ParseTemplateError::new(
+                        offset + pos,
+                        ParseTemplateErrorKind::InvalidFormatSpec,
+                    ));
+                };
+
+                parsed.precision = Some(precision);
+            }
+
+            if spec[pos..].starts_with('?') {
+                parsed.debug = true;
+                pos += 1;
+            }
+
+            if pos != spec.len() {
+                return Err(ParseTemplateError::new(
+                    offset + pos,
+                    ParseTemplateErrorKind::InvalidFormatSpec,
+                ));
+            }
+
+            Ok(parsed)
+        }
+
+        pub(super) fn fmt(&self, value: Value, f: &mut fmt::Formatter) -> fmt::Result {
+            match self.align {
+                // The standard library only supports fills known at compile-time,
+                // so padding around explicitly aligned values is written here
+                Some(align) if !self.zero => {
+                    struct Count(usize);
+
+                    impl fmt::Write for Count {
+                        fn write_str(&mut self, s: &str) -> fmt::Result {
+                            self.0 += s.chars().count();
+
+                            Ok(())
+                        }
+                    }
+
+                    let mut count = Count(0);
+                    self.write_value(&value, &mut count, 0)?;
+
+                    let pad = self.width.saturating_sub(count.0);
+                    let (before, after) = match align {
+                        Align::Left => (0, pad),
+                        Align::Center => (pad / 2, pad - pad / 2),
+                        Align::Right => (pad, 0),
+                    };
+
+                    for _ in 0..before {
+                        f.write_char(self.fill)?;
+                    }
+
+                    self.write_value(&value, &mut *f, 0)?;
+
+                    for _ in 0..after {
+                        f.write_char(self.fill)?;
+                    }
+
+                    Ok(())
+                }
+                _ => self.write_value(&value, f, self.width),
+            }
+        }
+
+        fn write_value(&self, value: &Value, mut f: impl fmt::Write, width: usize) -> fmt::Result {
+            macro_rules! write_value {
+                ($flags:literal) => {
+                    match (self.precision, self.debug) {
+                        (Some(precision), false) => write!(
+                            f,
+                            concat!("{:", $flags, "width$.precision$}"),
+                            value,
+                            width = width,
+                            precision = precision
+                        ),
+                        (Some(precision), true) => write!(
+                            f,
+                            concat!("{:", $flags, "width$.precision$?}"),
+                            value,
+                            width = width,
+                            precision = precision
+                        ),
+                        (None, false) => {
+                            write!(f, concat!("{:", $flags, "width$}"), value, width = width)
+                        }
+                        (None, true) => {
+                            write!(f, concat!("{:", $flags, "width$?}"), value, width = width)
+                        }
+                    }
+                };
+            }
+
+            match (self.plus, self.alternate, self.zero) {
+                (false, false, false) => write_value!(""),
+                (true, false, false) => write_value!("+"),
+                (false, true, false) => write_value!("#"),
+                (true, true, false) => write_value!("+#"),
+                (false, false, true) => write_value!("0"),
+                (true, false, true) => write_value!("+0"),
+                (false, true, true) => write_value!("#0"),
+                (true, true, true) => write_value!("+#0"),
+            }
+        }
+    }
+
+    /**
+    An error attempting to parse a [`Template`] from text.
+    */
+    #[derive(Debug)]
+    pub struct ParseTemplateError {
+        pos: usize,
+        kind: ParseTemplateErrorKind,
+    }
+
+    #[derive(Debug)]
+    enum ParseTemplateErrorKind {
+        UnexpectedOpen,
+        UnexpectedClose,
+        UnterminatedHole,
+        InvalidLabel,
+        InvalidFormatSpec,
+    }
+
+    impl ParseTemplateError {
+        fn new(pos: usize, kind: ParseTemplateErrorKind) -> Self {
+            ParseTemplateError { pos, kind }
+        }
+
+        /**
+        The byte offset in the input where the error was detected.
+        */
+        pub fn pos(&self) -> usize {
+            self.pos
+        }
+    }
+
+    impl fmt::Display for ParseTemplateError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let msg = match self.kind {
+                ParseTemplateErrorKind::UnexpectedOpen => {
+                    "unexpected `{` in a hole; holes can't be nested"
+                }
+                ParseTemplateErrorKind::UnexpectedClose => {
+                    "unexpected `}` outside a hole; use `}}` to escape it"
+                }
+                ParseTemplateErrorKind::UnterminatedHole => "the hole is missing a closing `}`",
+                ParseTemplateErrorKind::InvalidLabel => {
+                    "the label of the hole is not a valid identifier"
+                }
+                ParseTemplateErrorKind::InvalidFormatSpec => {
+                    "the format specification of the hole is not supported"
+                }
+            };
+
+            write!(f, "{msg} at position {}", self.pos)
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl std::error::Error for ParseTemplateError {}
+
     impl Part<'static> {
         /**
         Create a token for an owned fragment of literal text.
@@ -1383,4 +1825,112 @@ mod tests {
             &[serde_test::Token::Str("Hello, {greet}!")],
         );
     }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn parse() {
+        for (template, display, render) in [
+            ("", "", ""),
+            ("text", "text", "text"),
+            ("Hello, {user}!", "Hello, {user}!", "Hello, Rust!"),
+            ("{ user }", "{user}", "Rust"),
+            ("{user}{missing}", "{user}{missing}", "Rust{missing}"),
+            ("{{user}} {user}", "{{user}} {user}", "{user} Rust"),
+            ("}}{{", "}}{{", "}{"),
+            ("{_user}", "{_user}", "Rust"),
+            ("{user:>6}", "{user}", "  Rust"),
+        ] {
+            let tpl = Template::parse(template).unwrap();
+
+            assert_eq!(display, tpl.to_string(), "{template}");
+            assert_eq!(
+                render,
+                tpl.render([("user", "Rust"), ("_user", "Rust")])
+                    .to_string(),
+                "{template}"
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn parse_eq() {
+        assert_eq!(
+            Template::new_ref(&[Part::text("Hello, "), Part::hole("user"), Part::text("!")]),
+            Template::parse("Hello, {user}!").unwrap(),
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn parse_format_spec() {
+        fn render(template: &str, value: impl ToValue) -> String {
+            Template::parse(template)
+                .unwrap()
+                .render(("x", value.to_value()))
+                .to_string()
+        }
+
+        assert_eq!(format!("{:.2}", 1.23456), render("{x:.2}", 1.23456));
+        assert_eq!(format!("{:>8.2}", 1.23456), render("{x:>8.2}", 1.23456));
+        assert_eq!(format!("{:<8}", 42), render("{x:<8}", 42));
+        assert_eq!(format!("{:*^9}", "ab"), render("{x:*^9}", "ab"));
+        assert_eq!(format!("{:é>4}", "ab"), render("{x:é>4}", "ab"));
+        assert_eq!(format!("{:+}", 1), render("{x:+}", 1));
+        assert_eq!(format!("{:08.3}", -1.5), render("{x:08.3}", -1.5));
+        assert_eq!(format!("{:5}", 42), render("{x:5}", 42));
+        assert_eq!(format!("{:5}", "ab"), render("{x:5}", "ab"));
+        assert_eq!(format!("{:?}", "ab"), render("{x:?}", "ab"));
+        assert_eq!(format!("{:>6?}", 42), render("{x:>6?}", 42));
+        assert_eq!(format!("{}", 42), render("{x:}", 42));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn render_holes() {
+        let tpl = Template::parse("Hello, {user}! {{{greeting:>4}}} {missing}").unwrap();
+
+        let rendered = tpl
+            .render_holes([("user", "{Rust}"), ("greeting", "hi")])
+            .unwrap();
+
+        assert_eq!("Hello, {{Rust}}! {{  hi}} {missing}", rendered.to_string());
+        assert_eq!(
+            "Hello, {Rust}! {  hi} other",
+            rendered.render(("missing", "other")).to_string()
+        );
+        assert_eq!(
+            tpl.render([("user", "{Rust}"), ("greeting", "hi"), ("missing", "other")])
+                .to_string(),
+            rendered.render(("missing", "other")).to_string()
+        );
+
+        assert!(tpl.render_holes(("other", "Rust")).is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn parse_invalid() {
+        for (template, pos) in [
+            ("{", 0),
+            ("text {user", 5),
+            ("}", 0),
+            ("text }", 5),
+            ("{a{b}}", 2),
+            ("{}", 1),
+            ("{ }", 2),
+            ("{_}", 1),
+            ("{1a}", 1),
+            ("{a.b}", 1),
+            ("{x:.}", 4),
+            ("{x:x}", 3),
+            ("{x:1$}", 4),
+            ("{x:.*}", 4),
+            ("{x:>8.2?z}", 8),
+        ] {
+            let err = Template::parse(template).unwrap_err();
+
+            assert_eq!(pos, err.pos(), "{template}: {err}");
+        }
+    }
 }
@@ -491,6 +491,37 @@ mod alloc_support {
 
             self.0.to_f64_seq::<Seq>().map(|seq| seq.0)
         }
+
+        /**
+        Get a sequence of unsigned integers from a captured sequence of values.
+
+        If the value is a sequence where every element can be converted into a `u64` in the same way as [`Value::cast`] then `Some` is returned.
+        If the value is not a sequence, or any of its elements can't be converted, then `None` is returned.
+
+        For more advanced or specific conversion cases, use `serde` or `sval`.
+        */
+        pub fn to_u64_sequence(&self) -> Option<Vec<u64>> {
+            struct Seq(Option<Vec<u64>>);
+
+            impl Default for Seq {
+                fn default() -> Self {
+                    Seq(Some(Vec::new()))
+                }
+            }
+
+            impl Extend<Option<u64>> for Seq {
+                fn extend<T: IntoIterator<Item = Option<u64>>>(&mut self, iter: T) {
+                    for v in iter {
+                        match (&mut self.0, v) {
+                            (Some(seq), Some(v)) => seq.push(v),
+                            _ => self.0 = None,
+                        }
+                    }
+                }
+            }
+
+            self.0.to_u64_seq::<Seq>().and_then(|seq| seq.0)
+        }
     }
 
     /**
@@ -606,6 +637,28 @@ mod alloc_support {
         }
     }
 
+    macro_rules! impl_vec {
+        ($($t:ty,)*) => {
+            $(
+                impl ToValue for Vec<$t> {
+                    fn to_value(&self) -> Value<'_> {
+                        Value(value_bag::ValueBag::from_seq_slice(self))
+                    }
+                }
+
+                impl<'v> From<&'v Vec<$t>> for Value<'v> {
+                    fn from(value: &'v Vec<$t>) -> Self {
+                        Value(value_bag::ValueBag::from_seq_slice(value))
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_vec!(
+        u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f64, bool,
+    );
+
     impl<'v> ToValue for Cow<'v, str> {
         fn to_value(&self) -> Value<'_> {
             Value(self.into())
@@ -662,6 +715,32 @@ mod alloc_support {
                 }
             }
         }
+
+        #[test]
+        fn to_u64_sequence() {
+            for (case, expected) in [
+                (Value::from(&[0u64, 1, 2]), Some(vec![0, 1, 2])),
+                (Value::from(0u64), None),
+                (Value::from(&[] as &[u64; 0]), Some(vec![])),
+                (Value::from(&[-1i64, 0]), None),
+                (Value::from(&[true, false]), None),
+            ] {
+                assert_eq!(expected, case.to_u64_sequence());
+            }
+        }
+
+        #[test]
+        fn vec_to_value() {
+            let bounds = vec![0.5, 1.0];
+            let counts = vec![1u64, 2, 3];
+
+            assert_eq!(Some(vec![0.5, 1.0]), bounds.to_value().to_f64_sequence());
+            assert_eq!(Some(vec![1, 2, 3]), Value::from(&counts).to_u64_sequence());
+            assert_eq!(
+                Some(vec![1, 2, 3]),
+                Value::from(&counts).to_owned().by_ref().to_u64_sequence()
+            );
+        }
     }
 }
 
@@ -9,6 +9,7 @@ Components can use the presence of well-known properties to change the way they
     - [`KEY_TS_START`]: The [`crate::event::Event::ts_start()`].
     - [`KEY_TPL`]: The [`crate::event::Event::tpl()`].
     - [`KEY_MSG`]: The [`crate::event::Event::msg()`].
+    - [`KEY_DROPPED_PROPS`]: The number of properties dropped from the event.
 
 - Logging:
     - [`KEY_LVL`]: A severity level to categorize the event by.
@@ -41,6 +42,8 @@ Extensions to the data model are signaled by the well-known [`KEY_EVT_KIND`] pro
     - [`KEY_METRIC_DESCRIPTION`]: A description of the underlying data source.
     - [`KEY_DIST_EXP_BUCKETS`]: The bucket midpoint/count pairs in a distribution.
     - [`KEY_DIST_EXP_SCALE`]: The scale of buckets in a distribution.
+    - [`KEY_DIST_BUCKET_BOUNDS`]: The upper bounds of explicit buckets in a distribution.
+    - [`KEY_DIST_BUCKET_COUNTS`]: The counts of explicit buckets in a distribution.
     - [`KEY_DIST_COUNT`]: The count of values in a distribution.
     - [`KEY_DIST_SUM`]: The sum of values in a distribution.
     - [`KEY_DIST_MIN`]: The minimum value in a distribution.
@@ -58,6 +61,8 @@ pub const KEY_TS_START: &'static str = "ts_start";
 pub const KEY_TPL: &'static str = "tpl";
 /** The [`crate::event::Event::msg()`]. */
 pub const KEY_MSG: &'static str = "msg";
+/** The number of properties dropped from the event, such as by limits on their number or size. */
+pub const KEY_DROPPED_PROPS: &'static str = "dropped_props";
 /** The kind of extension the event belongs to. */
 pub const KEY_EVT_KIND: &'static str = "evt_kind";
 
@@ -124,6 +129,10 @@ pub const KEY_METRIC_DESCRIPTION: &'static str = "metric_description";
 pub const KEY_DIST_EXP_BUCKETS: &'static str = "dist_exp_buckets";
 /** The scale of buckets in a distribution. */
 pub const KEY_DIST_EXP_SCALE: &'static str = "dist_exp_scale";
+/** The upper bounds of explicit buckets in a distribution. */
+pub const KEY_DIST_BUCKET_BOUNDS: &'static str = "dist_bucket_bounds";
+/** The counts of explicit buckets in a distribution. */
+pub const KEY_DIST_BUCKET_COUNTS: &'static str = "dist_bucket_counts";
 /** The count of values in a distribution. */
 pub const KEY_DIST_COUNT: &'static str = "dist_count";
 /** The sum of values in a distribution. */
@@ -104,6 +104,14 @@ metrics!(
             */
             file_delete_failed: Counter -> usize,
             /**
+            Attempting to lock a shared file set failed.
+            */
+            file_set_lock_failed: Counter -> usize,
+            /**
+            Attempting to update the symlink or pointer file to the active log file failed.
+            */
+            file_link_failed: Counter -> usize,
+            /**
             Attempting to format an event into a batch failed and was discarded.
 
             This happens before the event is written to any log files.
@@ -53,6 +53,14 @@ where:
 - `id`: A unique identifier for the file in the interval.
 - `ext`: A user-defined file extension.
 
+If the file set is shared, a stable `writer` identifier is also included before the extension:
+
+```text
+{prefix}.{date}.{counter}.{id}.{writer}.{ext}
+```
+
+See [Sharing file sets](#sharing-file-sets) for details.
+
 In the following log file:
 
 ```text
@@ -76,6 +84,56 @@ Diagnostic events are only ever written to a single file at a time. That file ch
 3. The size of the file exceeds [`FileSetBuilder::max_file_size_bytes`].
 4. Writing to the file fails.
 
+# Following the current file
+
+Since log file names include the time they were created, tools like `tail -F` can't follow the file currently being written to by name. Use [`FileSetBuilder::current_file_symlink`] to maintain a symlink to the active file at a stable path:
+
+```
+fn main() {
+    let rt = emit::setup()
+        .emit_to(
+            emit_file::set("./target/logs/my_app.txt")
+                .current_file_symlink()
+                .spawn(),
+        )
+        .init();
+
+    // Your app code goes here
+
+    rt.blocking_flush(std::time::Duration::from_secs(30));
+}
+```
+
+In this example, `./target/logs/my_app.txt` will always be a symlink to the active file. On platforms where symlinks aren't available, use [`FileSetBuilder::current_file_pointer`] instead to maintain a small file containing the name of the active file.
+
+# Sharing file sets
+
+By default, a file set assumes it's the only writer to its directory. If multiple processes, or multiple [`FileSet`]s in the same process, use the same file set template then they may delete each others active files when applying [`FileSetBuilder::max_files`].
+
+Calling [`FileSetBuilder::shared`] with a unique identifier for each writer allows them to safely share the same directory:
+
+```
+fn main() {
+    let rt = emit::setup()
+        .emit_to(
+            emit_file::set("./target/logs/my_app.txt")
+                .shared(format!("worker-{}", std::process::id()))
+                .spawn(),
+        )
+        .init();
+
+    // Your app code goes here
+
+    rt.blocking_flush(std::time::Duration::from_secs(30));
+}
+```
+
+Shared file sets use advisory file locks to coordinate:
+
+- Each writer holds a lock on the file it's actively writing to.
+- Only one writer at a time can create new files or delete old ones. This is coordinated through a lock file in the same directory as the file set, named `.{prefix}.{ext}.lock`.
+- Files that are locked by another writer won't be deleted when applying [`FileSetBuilder::max_files`].
+
 # Durability
 
 Diagnostic events are written to files in asynchronous batches. Under normal operation, after a call to [`emit::Emitter::blocking_flush`], all events emitted before the call are guaranteed to be written and synced via Rust's [`std::fs::File::sync_all`] method. This is usually enough to guarantee durability.
@@ -243,6 +301,8 @@ pub struct FileSetBuilder {
     max_files: usize,
     max_file_size_bytes: usize,
     reuse_files: bool,
+    writer_id: Option<String>,
+    current_file: Option<CurrentFile>,
     writer: Box<
         dyn Fn(&mut FileBuf, &emit::Event<&dyn emit::props::ErasedProps>) -> io::Result<()>
             + Send
@@ -258,6 +318,12 @@ enum RollBy {
     Minute,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CurrentFile {
+    Symlink,
+    Pointer,
+}
+
 impl FileSetBuilder {
     /**
     Create a new [`FileSetBuilder`] using the default newline-delimited JSON format.
@@ -304,6 +370,8 @@ impl FileSetBuilder {
             max_files: DEFAULT_MAX_FILES,
             max_file_size_bytes: DEFAULT_MAX_FILE_SIZE_BYTES,
             reuse_files: DEFAULT_REUSE_FILES,
+            writer_id: None,
+            current_file: None,
             writer: Box::new(writer),
             separator,
         }
@@ -365,6 +433,55 @@ impl FileSetBuilder {
         self
     }
 
+    /**
+    Share the file set with other writers, identifying files written by this one with `writer_id`.
+
+    Multiple writers, whether in the same process or different ones, can safely write to the same file set so long as each uses a different `writer_id`. The `writer_id` is included in the name of each file this writer creates. It should be stable across restarts so [`FileSetBuilder::reuse_files`] can pick up files written previously.
+
+    When a file set is shared:
+
+    - The active file is locked while it's being written to.
+    - Only one writer at a time creates or deletes files.
+    - [`FileSetBuilder::max_files`] applies to all files in the set, but files locked by other writers won't be deleted.
+    - [`FileSetBuilder::reuse_files`] only considers files with the same `writer_id`.
+
+    The `writer_id` must be non-empty and only contain ASCII letters, digits, `-`, or `_`.
+
+    See the _Sharing file sets_ section of the crate root docs for more details.
+    */
+    pub fn shared(mut self, writer_id: impl Into<String>) -> Self {
+        self.writer_id = Some(writer_id.into());
+        self
+    }
+
+    /**
+    Maintain a symlink to the active file.
+
+    The symlink is named after the file set template, so a file set created with `./logs/my_app.txt` will keep `./logs/my_app.txt` pointing at the file currently being written to. If the file set is [shared](FileSetBuilder::shared) then the writer id is included in the name, like `./logs/my_app.{writer}.txt`.
+
+    The symlink is atomically replaced by the background worker whenever it starts writing to a different file. Creating symlinks may need additional permissions on some platforms, like Windows. If the symlink can't be updated then events are still written, but the `file_link_failed` metric on [`FileSet::metric_source`] is incremented.
+
+    This method replaces any previous call to [`FileSetBuilder::current_file_pointer`].
+    */
+    pub fn current_file_symlink(mut self) -> Self {
+        self.current_file = Some(CurrentFile::Symlink);
+        self
+    }
+
+    /**
+    Maintain a pointer file containing the name of the active file.
+
+    The pointer file is named after the file set template with `.current` appended, so a file set created with `./logs/my_app.txt` will keep the name of the file currently being written to in `./logs/my_app.txt.current`. If the file set is [shared](FileSetBuilder::shared) then the writer id is included in the name, like `./logs/my_app.{writer}.txt.current`. The name is relative to the directory of the file set.
+
+    The pointer file is atomically replaced by the background worker whenever it starts writing to a different file. If the pointer file can't be updated then events are still written, but the `file_link_failed` metric on [`FileSet::metric_source`] is incremented.
+
+    This method replaces any previous call to [`FileSetBuilder::current_file_symlink`].
+    */
+    pub fn current_file_pointer(mut self) -> Self {
+        self.current_file = Some(CurrentFile::Pointer);
+        self
+    }
+
     /**
     Specify a writer for incoming [`emit::Event`]s.
 
@@ -413,6 +530,10 @@ impl FileSetBuilder {
     fn spawn_inner(self, metrics: Arc<InternalMetrics>) -> Result<FileSetInner, Error> {
         let (dir, file_prefix, file_ext) = dir_prefix_ext(self.file_set).map_err(Error::new)?;
 
+        if let Some(ref writer_id) = self.writer_id {
+            validate_writer_id(writer_id)?;
+        }
+
         let mut worker = Worker::new(
             metrics.clone(),
             StdFilesystem::new(),
@@ -421,6 +542,8 @@ impl FileSetBuilder {
             dir,
             file_prefix,
             file_ext,
+            self.writer_id,
+            self.current_file,
             self.roll_by,
             self.reuse_files,
             self.max_files,
@@ -618,7 +741,14 @@ fn default_writer(
             let _ = self.0.props().dedup().for_each(|k, v| {
                 match (|| {
                     stream.record_value_begin(None, &sval::Label::new_computed(k.get()))?;
-                    stream.value_computed(&v)?;
+
+                    // Structured errors are written as their chain of causes
+                    if let Some(chain) = v.downcast_ref::<emit::err::Chain>() {
+                        stream.value_computed(chain)?;
+                    } else {
+                        stream.value_computed(&v)?;
+                    }
+
                     stream.record_value_end(None, &sval::Label::new_computed(k.get()))?;
 
                     Ok::<(), sval::Error>(())
@@ -707,6 +837,9 @@ struct Worker {
     dir: String,
     file_prefix: String,
     file_ext: String,
+    writer_id: Option<String>,
+    current_file: Option<CurrentFile>,
+    linked_file_path: Option<PathBuf>,
     separator: &'static [u8],
 }
 
@@ -719,6 +852,8 @@ impl Worker {
         dir: String,
         file_prefix: String,
         file_ext: String,
+        writer_id: Option<String>,
+        current_file: Option<CurrentFile>,
         roll_by: RollBy,
         reuse_files: bool,
         max_files: usize,
@@ -738,10 +873,128 @@ impl Worker {
             dir,
             file_prefix,
             file_ext,
+            writer_id,
+            current_file,
+            linked_file_path: None,
             separator,
         }
     }
 
+    fn link_current_file(&mut self, file_path: &Path) {
+        let Some(current_file) = self.current_file else {
+            return;
+        };
+
+        if self.linked_file_path.as_deref() == Some(file_path) {
+            return;
+        }
+
+        // Only attempt to link each file once, even if it fails
+        // This avoids flooding diagnostics when links aren't supported
+        self.linked_file_path = Some(file_path.to_owned());
+
+        let Some(file_name) = file_path.file_name() else {
+            return;
+        };
+
+        let mut path = PathBuf::from(&self.dir);
+        path.push(current_link_name(
+            &self.file_prefix,
+            &self.file_ext,
+            self.writer_id.as_deref(),
+            current_file,
+        ));
+
+        let linked = match current_file {
+            CurrentFile::Symlink => self.fs.replace_symlink(&path, Path::new(file_name)),
+            CurrentFile::Pointer => self
+                .fs
+                .replace_contents(&path, file_name.as_encoded_bytes()),
+        };
+
+        match linked {
+            Ok(()) => {
+                emit::debug!(
+                    rt: emit::runtime::internal(),
+                    "linked {path} to {file_path}",
+                    #[emit::as_debug]
+                    path,
+                    #[emit::as_debug]
+                    file_path: &file_path,
+                );
+            }
+            Err(err) => {
+                self.metrics.file_link_failed.increment();
+
+                emit::warn!(
+                    rt: emit::runtime::internal(),
+                    "failed to link {path} to {file_path}: {err}",
+                    #[emit::as_debug]
+                    path,
+                    #[emit::as_debug]
+                    file_path: &file_path,
+                    err,
+                );
+            }
+        }
+    }
+
+    fn read_file_set(&self, file_set: &mut ActiveFileSet) {
+        let _ = file_set
+            .read(&self.fs, &self.file_prefix, &self.file_ext)
+            .inspect_err(|err| {
+                self.metrics.file_set_read_failed.increment();
+
+                emit::warn!(
+                    rt: emit::runtime::internal(),
+                    "failed to files in read {path}: {err}",
+                    #[emit::as_debug]
+                    path: &file_set.dir,
+                    err,
+                );
+            });
+    }
+
+    fn lock_file_set(
+        &self,
+        file_set_lock: &mut Option<Box<dyn File + Send + Sync>>,
+    ) -> Result<(), io::Error> {
+        // Only shared file sets need to coordinate with other writers
+        if self.writer_id.is_none() || file_set_lock.is_some() {
+            return Ok(());
+        }
+
+        let mut path = PathBuf::from(&self.dir);
+        path.push(lock_file_name(&self.file_prefix, &self.file_ext));
+
+        let lock = self.fs.open_lock(&path).and_then(|lock| {
+            lock.lock()?;
+
+            Ok(lock)
+        });
+
+        match lock {
+            Ok(lock) => {
+                *file_set_lock = Some(lock);
+
+                Ok(())
+            }
+            Err(err) => {
+                self.metrics.file_set_lock_failed.increment();
+
+                emit::warn!(
+                    rt: emit::runtime::internal(),
+                    "failed to lock {path}: {err}",
+                    #[emit::as_debug]
+                    path,
+                    err,
+                );
+
+                Err(err)
+            }
+        }
+    }
+
     #[emit::span(rt: emit::runtime::internal(), guard: span, "write file batch")]
     fn on_batch(&mut self, mut batch: EventBatch) -> Result<(), BatchError<EventBatch>> {
         let ts = self.clock.now().unwrap();
@@ -752,6 +1005,10 @@ impl Worker {
         let mut file = self.active_file.take();
         let mut file_set = ActiveFileSet::empty(&self.metrics, &self.dir);
 
+        // If the file set is shared then other writers may be creating or deleting files
+        // This lock is held until we've got a file of our own to write to
+        let mut file_set_lock = None;
+
         if file.is_none() {
             if let Err(err) = self.fs.create_dir_all(Path::new(&self.dir)) {
                 span.complete_with(emit::span::completion::from_fn(|span| {
@@ -769,28 +1026,22 @@ impl Worker {
                 return Err(emit_batcher::BatchError::retry(err, batch));
             }
 
-            let _ = file_set
-                .read(&self.fs, &self.file_prefix, &self.file_ext)
-                .map_err(|err| {
-                    self.metrics.file_set_read_failed.increment();
-
-                    emit::warn!(
-                        rt: emit::runtime::internal(),
-                        "failed to files in read {path}: {err}",
-                        #[emit::as_debug]
-                        path: &file_set.dir,
-                        err,
-                    );
+            if let Err(err) = self.lock_file_set(&mut file_set_lock) {
+                return Err(emit_batcher::BatchError::retry(err, batch));
+            }
 
-                    err
-                });
+            self.read_file_set(&mut file_set);
 
             if self.reuse_files {
-                if let Some(file_name) = file_set.current_file_name() {
+                if let Some(file_name) = file_set.current_file_name(
+                    &self.file_prefix,
+                    &self.file_ext,
+                    self.writer_id.as_deref(),
+                ) {
                     let mut path = PathBuf::from(&self.dir);
                     path.push(file_name);
 
-                    file = ActiveFile::try_open_reuse(&self.fs, &path)
+                    file = ActiveFile::try_open_reuse(&self.fs, &path, self.writer_id.is_some())
                         .map_err(|err| {
                             self.metrics.file_open_failed.increment();
 
@@ -817,8 +1068,21 @@ impl Worker {
         let mut file = if let Some(file) = file {
             file
         } else {
+            if self.writer_id.is_some() {
+                if let Err(err) = self.lock_file_set(&mut file_set_lock) {
+                    return Err(emit_batcher::BatchError::retry(err, batch));
+                }
+
+                // Other writers may have created or deleted files since we last looked
+                self.read_file_set(&mut file_set);
+            }
+
             // Leave room for the file we're about to create
-            file_set.apply_retention(&self.fs, self.max_files.saturating_sub(1));
+            file_set.apply_retention(
+                &self.fs,
+                self.max_files.saturating_sub(1),
+                self.writer_id.is_some(),
+            );
 
             let mut path = PathBuf::from(self.dir.clone());
 
@@ -832,9 +1096,10 @@ impl Worker {
                 &self.file_ext,
                 &file_ts,
                 &file_id,
+                self.writer_id.as_deref(),
             ));
 
-            match ActiveFile::try_open_create(&self.fs, &path) {
+            match ActiveFile::try_open_create(&self.fs, &path, self.writer_id.is_some()) {
                 Ok(file) => {
                     self.metrics.file_create.increment();
 
@@ -863,6 +1128,11 @@ impl Worker {
             }
         };
 
+        // The active file is locked now, so other writers won't delete it
+        drop(file_set_lock);
+
+        self.link_current_file(&file.file_path);
+
         let written_bytes = batch.remaining_bytes;
 
         while let Some(buf) = batch.current() {
@@ -962,19 +1232,80 @@ impl<'a> ActiveFileSet<'a> {
         Ok(())
     }
 
-    fn current_file_name(&self) -> Option<&str> {
+    fn current_file_name(
+        &self,
+        file_prefix: &str,
+        file_ext: &str,
+        writer_id: Option<&str>,
+    ) -> Option<&str> {
         // NOTE: If the clock shifts back (either jitters or through daylight savings)
         // Then we may return a file from the future here instead of one that better
         // matches the current timestamp. In these cases we'll end up creating a new file
         // instead of potentially reusing one that does match.
-        self.file_set.first().map(|file_name| &**file_name)
+        match writer_id {
+            // If the file set is shared then only reuse files written by this writer
+            Some(writer_id) => self
+                .file_set
+                .iter()
+                .find(|file_name| {
+                    read_file_name_writer_id(file_name, file_prefix, file_ext) == Some(writer_id)
+                })
+                .map(|file_name| &**file_name),
+            None => self.file_set.first().map(|file_name| &**file_name),
+        }
     }
 
-    fn apply_retention(&mut self, fs: impl Filesystem, max_files: usize) {
-        while self.file_set.len() >= max_files {
+    fn apply_retention(&mut self, fs: impl Filesystem, max_files: usize, shared: bool) {
+        // The number of files that couldn't be deleted because they're in use by other writers
+        let mut retained = 0;
+
+        while !self.file_set.is_empty() && self.file_set.len() + retained >= max_files {
             let mut path = PathBuf::from(self.dir);
             path.push(self.file_set.pop().unwrap());
 
+            // If the file set is shared then lock the file before deleting it
+            // If another writer holds the lock then the file is still in use
+            let lock = if shared {
+                match fs.open_existing(&path).and_then(|file| {
+                    let locked = file.try_lock()?;
+
+                    Ok((file, locked))
+                }) {
+                    Ok((file, true)) => Some(file),
+                    Ok((_, false)) => {
+                        retained += 1;
+
+                        emit::debug!(
+                            rt: emit::runtime::internal(),
+                            "not deleting {path} because it's in use by another writer",
+                            #[emit::as_debug]
+                            path,
+                        );
+
+                        continue;
+                    }
+                    // The file may have already been deleted by another writer
+                    Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                    Err(err) => {
+                        retained += 1;
+
+                        self.metrics.file_delete_failed.increment();
+
+                        emit::warn!(
+                            rt: emit::runtime::internal(),
+                            "failed to lock {path}: {err}",
+                            #[emit::as_debug]
+                            path,
+                            err,
+                        );
+
+                        continue;
+                    }
+                }
+            } else {
+                None
+            };
+
             if let Err(err) = fs.remove_file(&path) {
                 self.metrics.file_delete_failed.increment();
 
@@ -995,6 +1326,8 @@ impl<'a> ActiveFileSet<'a> {
                     path,
                 );
             }
+
+            drop(lock);
         }
     }
 }
@@ -1011,6 +1344,7 @@ impl ActiveFile {
     fn try_open_reuse(
         fs: impl Filesystem,
         file_path: impl AsRef<Path>,
+        lock: bool,
     ) -> Result<ActiveFile, io::Error> {
         let file_path = file_path.as_ref();
 
@@ -1018,6 +1352,13 @@ impl ActiveFile {
 
         let file = fs.open_existing(file_path)?;
 
+        if lock && !file.try_lock()? {
+            return Err(io::Error::new(
+                io::ErrorKind::WouldBlock,
+                "the file is locked by another writer",
+            ));
+        }
+
         let file_size_bytes = file.len()?;
 
         Ok(ActiveFile {
@@ -1034,6 +1375,7 @@ impl ActiveFile {
     fn try_open_create(
         fs: impl Filesystem,
         file_path: impl AsRef<Path>,
+        lock: bool,
     ) -> Result<ActiveFile, io::Error> {
         let file_path = file_path.as_ref();
 
@@ -1041,6 +1383,13 @@ impl ActiveFile {
 
         let file = fs.open_new(file_path)?;
 
+        if lock && !file.try_lock()? {
+            return Err(io::Error::new(
+                io::ErrorKind::WouldBlock,
+                "the file is locked by another writer",
+            ));
+        }
+
         // Sync the existence of this new file to the parent directory
         // This is only important on some platforms and filesystems
         fs.sync_parent(file_path)?;
@@ -1109,6 +1458,23 @@ fn dir_prefix_ext(file_set: impl AsRef<Path>) -> Result<(String, String, String)
     Ok((dir, prefix, ext))
 }
 
+fn validate_writer_id(writer_id: &str) -> Result<(), Error> {
+    if writer_id.is_empty() {
+        return Err(Error::new("writer ids must not be empty"));
+    }
+
+    if !writer_id
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(Error::new(
+            "writer ids must only contain ASCII letters, digits, `-`, or `_`",
+        ));
+    }
+
+    Ok(())
+}
+
 fn rolling_millis(roll_by: RollBy, ts: emit::Timestamp, parts: emit::timestamp::Parts) -> u32 {
     let truncated = match roll_by {
         RollBy::Day => emit::Timestamp::from_parts(emit::timestamp::Parts {
@@ -1184,8 +1550,66 @@ fn read_file_path_ts(path: &Path) -> Result<&str, io::Error> {
     read_file_name_ts(file_name)
 }
 
-fn file_name(file_prefix: &str, file_ext: &str, ts: &str, id: &str) -> String {
-    format!("{}.{}.{}.{}", file_prefix, ts, id, file_ext)
+fn read_file_name_writer_id<'a>(
+    file_name: &'a str,
+    file_prefix: &str,
+    file_ext: &str,
+) -> Option<&'a str> {
+    let parts = file_name
+        .strip_prefix(file_prefix)?
+        .strip_prefix('.')?
+        .strip_suffix(file_ext)?
+        .strip_suffix('.')?;
+
+    // Shared file names have the parts `{date}.{counter}.{id}.{writer}`
+    let mut parts = parts.split('.');
+
+    let (Some(_), Some(_), Some(_), Some(writer_id), None) = (
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+    ) else {
+        return None;
+    };
+
+    Some(writer_id)
+}
+
+fn file_name(
+    file_prefix: &str,
+    file_ext: &str,
+    ts: &str,
+    id: &str,
+    writer_id: Option<&str>,
+) -> String {
+    match writer_id {
+        Some(writer_id) => format!("{}.{}.{}.{}.{}", file_prefix, ts, id, writer_id, file_ext),
+        None => format!("{}.{}.{}.{}", file_prefix, ts, id, file_ext),
+    }
+}
+
+fn current_link_name(
+    file_prefix: &str,
+    file_ext: &str,
+    writer_id: Option<&str>,
+    current_file: CurrentFile,
+) -> String {
+    let link_name = match writer_id {
+        Some(writer_id) => format!("{}.{}.{}", file_prefix, writer_id, file_ext),
+        None => format!("{}.{}", file_prefix, file_ext),
+    };
+
+    match current_file {
+        CurrentFile::Symlink => link_name,
+        CurrentFile::Pointer => format!("{}.current", link_name),
+    }
+}
+
+fn lock_file_name(file_prefix: &str, file_ext: &str) -> String {
+    // The leading `.` ensures the lock file is never considered part of the file set
+    format!(".{}.{}.lock", file_prefix, file_ext)
 }
 
 trait Filesystem {
@@ -1200,6 +1624,12 @@ trait Filesystem {
     fn open_new(&self, path: &Path) -> io::Result<Box<dyn File + Send + Sync>>;
 
     fn open_existing(&self, path: &Path) -> io::Result<Box<dyn File + Send + Sync>>;
+
+    fn open_lock(&self, path: &Path) -> io::Result<Box<dyn File + Send + Sync>>;
+
+    fn replace_symlink(&self, path: &Path, target: &Path) -> io::Result<()>;
+
+    fn replace_contents(&self, path: &Path, contents: &[u8]) -> io::Result<()>;
 }
 
 impl<'a, F: Filesystem + ?Sized> Filesystem for &'a F {
@@ -1226,6 +1656,18 @@ impl<'a, F: Filesystem + ?Sized> Filesystem for &'a F {
     fn open_existing(&self, path: &Path) -> io::Result<Box<dyn File + Send + Sync>> {
         (**self).open_existing(path)
     }
+
+    fn open_lock(&self, path: &Path) -> io::Result<Box<dyn File + Send + Sync>> {
+        (**self).open_lock(path)
+    }
+
+    fn replace_symlink(&self, path: &Path, target: &Path) -> io::Result<()> {
+        (**self).replace_symlink(path, target)
+    }
+
+    fn replace_contents(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        (**self).replace_contents(path, contents)
+    }
 }
 
 impl<F: Filesystem + ?Sized> Filesystem for Box<F> {
@@ -1252,6 +1694,18 @@ impl<F: Filesystem + ?Sized> Filesystem for Box<F> {
     fn open_existing(&self, path: &Path) -> io::Result<Box<dyn File + Send + Sync>> {
         (**self).open_existing(path)
     }
+
+    fn open_lock(&self, path: &Path) -> io::Result<Box<dyn File + Send + Sync>> {
+        (**self).open_lock(path)
+    }
+
+    fn replace_symlink(&self, path: &Path, target: &Path) -> io::Result<()> {
+        (**self).replace_symlink(path, target)
+    }
+
+    fn replace_contents(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        (**self).replace_contents(path, contents)
+    }
 }
 
 struct StdFilesystem;
@@ -1292,6 +1746,9 @@ impl Filesystem for StdFilesystem {
         let iter = std::fs::read_dir(path)?.filter_map(|entry| {
             let entry = entry.ok()?;
 
+            // NOTE: Symlinks aren't followed here, so any symlink to
+            // the current file won't be considered part of the file set
+
             if entry.metadata().ok()?.is_file() {
                 Some(entry.path())
             } else {
@@ -1307,9 +1764,10 @@ impl Filesystem for StdFilesystem {
     }
 
     fn open_new(&self, path: &Path) -> io::Result<Box<dyn File + Send + Sync>> {
+        // NOTE: Files are opened for reading so they can be locked on all platforms
         let file = std::fs::OpenOptions::new()
             .create_new(true)
-            .read(false)
+            .read(true)
             .append(true)
             .open(path)?;
 
@@ -1318,18 +1776,93 @@ impl Filesystem for StdFilesystem {
 
     fn open_existing(&self, path: &Path) -> io::Result<Box<dyn File + Send + Sync>> {
         let file = std::fs::OpenOptions::new()
-            .read(false)
+            .read(true)
             .append(true)
             .open(path)?;
 
         Ok(Box::new(StdFile::new(file)))
     }
+
+    fn open_lock(&self, path: &Path) -> io::Result<Box<dyn File + Send + Sync>> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(path)?;
+
+        Ok(Box::new(StdFile::new(file)))
+    }
+
+    fn replace_symlink(&self, path: &Path, target: &Path) -> io::Result<()> {
+        // Create the symlink at a temporary path, then rename it over the original
+        // This ensures the symlink is replaced atomically
+        let tmp = tmp_path(path)?;
+
+        let _ = std::fs::remove_file(&tmp);
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(target, &tmp)?;
+        }
+
+        #[cfg(windows)]
+        {
+            std::os::windows::fs::symlink_file(target, &tmp)?;
+        }
+
+        #[cfg(not(any(unix, windows)))]
+        {
+            let _ = target;
+
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "symlinks are not supported on this platform",
+            ));
+        }
+
+        std::fs::rename(&tmp, path)
+    }
+
+    fn replace_contents(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        // Write the contents to a temporary file, then rename it over the original
+        // This ensures readers never observe partially written contents
+        let tmp = tmp_path(path)?;
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&tmp)?;
+
+        file.write_all(contents)?;
+        file.sync_all()?;
+        drop(file);
+
+        std::fs::rename(&tmp, path)
+    }
+}
+
+fn tmp_path(path: &Path) -> io::Result<PathBuf> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| io::Error::other("unable to determine filename"))?;
+
+    // The leading `.` ensures the temporary file is never considered part of the file set
+    let mut tmp_name = std::ffi::OsString::from(".");
+    tmp_name.push(file_name);
+    tmp_name.push(".tmp");
+
+    Ok(path.with_file_name(tmp_name))
 }
 
 trait File: Write {
     fn len(&self) -> io::Result<usize>;
 
     fn sync_all(&mut self) -> io::Result<()>;
+
+    fn lock(&self) -> io::Result<()>;
+
+    fn try_lock(&self) -> io::Result<bool>;
 }
 
 impl<'a, F: File + ?Sized> File for &'a mut F {
@@ -1340,6 +1873,14 @@ impl<'a, F: File + ?Sized> File for &'a mut F {
     fn sync_all(&mut self) -> io::Result<()> {
         (**self).sync_all()
     }
+
+    fn lock(&self) -> io::Result<()> {
+        (**self).lock()
+    }
+
+    fn try_lock(&self) -> io::Result<bool> {
+        (**self).try_lock()
+    }
 }
 
 impl<F: File + ?Sized> File for Box<F> {
@@ -1350,6 +1891,14 @@ impl<F: File + ?Sized> File for Box<F> {
     fn sync_all(&mut self) -> io::Result<()> {
         (**self).sync_all()
     }
+
+    fn lock(&self) -> io::Result<()> {
+        (**self).lock()
+    }
+
+    fn try_lock(&self) -> io::Result<bool> {
+        (**self).try_lock()
+    }
 }
 
 struct StdFile(std::fs::File);
@@ -1368,6 +1917,18 @@ impl File for StdFile {
     fn sync_all(&mut self) -> io::Result<()> {
         self.0.sync_all()
     }
+
+    fn lock(&self) -> io::Result<()> {
+        self.0.lock()
+    }
+
+    fn try_lock(&self) -> io::Result<bool> {
+        match self.0.try_lock() {
+            Ok(()) => Ok(true),
+            Err(std::fs::TryLockError::WouldBlock) => Ok(false),
+            Err(std::fs::TryLockError::Error(err)) => Err(err),
+        }
+    }
 }
 
 impl Write for StdFile {
@@ -1388,7 +1949,10 @@ mod tests {
         cmp,
         collections::{HashMap, HashSet},
         mem,
-        sync::Mutex,
+        sync::{
+            Mutex,
+            atomic::{AtomicBool, Ordering},
+        },
         time::Duration,
     };
 
@@ -1397,6 +1961,8 @@ mod tests {
         incoming: Arc<Mutex<HashMap<String, InMemoryFile>>>,
         outgoing: Arc<Mutex<HashMap<String, InMemoryFile>>>,
         committed: Arc<Mutex<HashMap<String, InMemoryFile>>>,
+        locks: Arc<Mutex<HashSet<String>>>,
+        links: Arc<Mutex<HashMap<String, String>>>,
     }
 
     impl InMemoryFilesystem {
@@ -1405,9 +1971,28 @@ mod tests {
                 incoming: Arc::new(Mutex::new(HashMap::new())),
                 outgoing: Arc::new(Mutex::new(HashMap::new())),
                 committed: Arc::new(Mutex::new(HashMap::new())),
+                locks: Arc::new(Mutex::new(HashSet::new())),
+                links: Arc::new(Mutex::new(HashMap::new())),
             }
         }
 
+        fn link(&self, path: impl AsRef<str>) -> Option<String> {
+            self.links.lock().unwrap().get(path.as_ref()).cloned()
+        }
+
+        fn handle(&self, path: String, file: InMemoryFile) -> Box<dyn File + Send + Sync> {
+            Box::new(InMemoryFileHandle {
+                file,
+                path,
+                locks: self.locks.clone(),
+                locked: AtomicBool::new(false),
+            })
+        }
+
+        fn is_locked(&self, path: impl AsRef<str>) -> bool {
+            self.locks.lock().unwrap().contains(path.as_ref())
+        }
+
         fn get(&self, path: impl AsRef<str>) -> InMemoryFile {
             self.committed
                 .lock()
@@ -1447,6 +2032,21 @@ mod tests {
         }
     }
 
+    struct InMemoryFileHandle {
+        file: InMemoryFile,
+        path: String,
+        locks: Arc<Mutex<HashSet<String>>>,
+        locked: AtomicBool,
+    }
+
+    impl Drop for InMemoryFileHandle {
+        fn drop(&mut self) {
+            if self.locked.load(Ordering::Relaxed) {
+                self.locks.lock().unwrap().remove(&self.path);
+            }
+        }
+    }
+
     fn pathstr(path: &Path) -> String {
         path.to_str().unwrap().replace('\\', "/")
     }
@@ -1533,36 +2133,79 @@ mod tests {
                 "file {path} already exists"
             );
 
-            Ok(Box::new(file))
+            Ok(self.handle(path, file))
         }
 
         fn open_existing(&self, path: &Path) -> io::Result<Box<dyn File + Send + Sync>> {
             let path = pathstr(path);
 
-            let committed = self.committed.lock().unwrap();
+            let file = self.committed.lock().unwrap().get(&*path).unwrap().clone();
 
-            Ok(Box::new(committed.get(&*path).unwrap().clone()))
+            Ok(self.handle(path, file))
+        }
+
+        fn open_lock(&self, path: &Path) -> io::Result<Box<dyn File + Send + Sync>> {
+            Ok(self.handle(pathstr(path), InMemoryFile::new()))
+        }
+
+        fn replace_symlink(&self, path: &Path, target: &Path) -> io::Result<()> {
+            self.links
+                .lock()
+                .unwrap()
+                .insert(pathstr(path), pathstr(target));
+
+            Ok(())
+        }
+
+        fn replace_contents(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+            self.links.lock().unwrap().insert(
+                pathstr(path),
+                String::from_utf8(contents.to_owned()).unwrap(),
+            );
+
+            Ok(())
         }
     }
 
-    impl File for InMemoryFile {
+    impl File for InMemoryFileHandle {
         fn len(&self) -> io::Result<usize> {
-            Ok(self.committed.lock().unwrap().len())
+            Ok(self.file.committed.lock().unwrap().len())
         }
 
         fn sync_all(&mut self) -> io::Result<()> {
-            let incoming = mem::take(&mut *self.incoming.lock().unwrap());
-            let mut committed = self.committed.lock().unwrap();
+            let incoming = mem::take(&mut *self.file.incoming.lock().unwrap());
+            let mut committed = self.file.committed.lock().unwrap();
 
             committed.extend(incoming);
 
             Ok(())
         }
+
+        fn lock(&self) -> io::Result<()> {
+            // Tests are single-threaded, so blocking on a lock would never complete
+            assert!(self.try_lock()?, "file {} is already locked", self.path);
+
+            Ok(())
+        }
+
+        fn try_lock(&self) -> io::Result<bool> {
+            if self.locked.load(Ordering::Relaxed) {
+                return Ok(true);
+            }
+
+            if self.locks.lock().unwrap().insert(self.path.clone()) {
+                self.locked.store(true, Ordering::Relaxed);
+
+                Ok(true)
+            } else {
+                Ok(false)
+            }
+        }
     }
 
-    impl Write for InMemoryFile {
+    impl Write for InMemoryFileHandle {
         fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-            self.incoming.lock().unwrap().extend_from_slice(buf);
+            self.file.incoming.lock().unwrap().extend_from_slice(buf);
 
             Ok(buf.len())
         }
@@ -1637,6 +2280,8 @@ mod tests {
             "logs".to_string(),
             "test".to_string(),
             "log".to_string(),
+            None,
+            None,
             RollBy::Minute,
             false,
             10,
@@ -1697,6 +2342,8 @@ mod tests {
             "logs".to_string(),
             "test".to_string(),
             "log".to_string(),
+            None,
+            None,
             RollBy::Minute,
             false,
             10,
@@ -1724,6 +2371,8 @@ mod tests {
             "logs".to_string(),
             "test".to_string(),
             "log".to_string(),
+            None,
+            None,
             RollBy::Minute,
             false,
             10,
@@ -1766,6 +2415,8 @@ mod tests {
             "logs".to_string(),
             "test".to_string(),
             "log".to_string(),
+            None,
+            None,
             RollBy::Minute,
             true,
             10,
@@ -1791,6 +2442,8 @@ mod tests {
             "logs".to_string(),
             "test".to_string(),
             "log".to_string(),
+            None,
+            None,
             RollBy::Minute,
             true,
             10,
@@ -1815,6 +2468,321 @@ mod tests {
         );
     }
 
+    fn shared_worker(
+        fs: &InMemoryFilesystem,
+        clock: &TestClock,
+        rng: &TestRng,
+        writer_id: &str,
+        reuse_files: bool,
+        max_files: usize,
+    ) -> Worker {
+        Worker::new(
+            Arc::new(InternalMetrics::default()),
+            fs.clone(),
+            clock.clone(),
+            rng.clone(),
+            "logs".to_string(),
+            "test".to_string(),
+            "log".to_string(),
+            Some(writer_id.to_string()),
+            None,
+            RollBy::Minute,
+            reuse_files,
+            max_files,
+            1024,
+            b"\n",
+        )
+    }
+
+    #[test]
+    fn worker_shared_basic() {
+        let fs = InMemoryFilesystem::new();
+        let clock = TestClock::new();
+        let rng = TestRng::new();
+
+        let mut a = shared_worker(&fs, &clock, &rng, "a", false, 10);
+        let mut b = shared_worker(&fs, &clock, &rng, "b", false, 10);
+
+        let mut batch = EventBatch::new();
+        batch.push(*b"a1\n");
+        let Ok(()) = a.on_batch(batch) else {
+            panic!("failed to write batch");
+        };
+
+        let mut batch = EventBatch::new();
+        batch.push(*b"b1\n");
+        let Ok(()) = b.on_batch(batch) else {
+            panic!("failed to write batch");
+        };
+
+        assert_eq!(2, fs.iter().count());
+
+        assert_eq!(
+            *b"a1\n",
+            *fs.get("logs/test.1970-01-01-00-00.00000000.00000000.a.log")
+                .contents()
+        );
+        assert_eq!(
+            *b"b1\n",
+            *fs.get("logs/test.1970-01-01-00-00.00000000.00000000.b.log")
+                .contents()
+        );
+
+        // Active files are locked, but the file set isn't
+        assert!(fs.is_locked("logs/test.1970-01-01-00-00.00000000.00000000.a.log"));
+        assert!(fs.is_locked("logs/test.1970-01-01-00-00.00000000.00000000.b.log"));
+        assert!(!fs.is_locked("logs/.test.log.lock"));
+
+        drop(a);
+
+        assert!(!fs.is_locked("logs/test.1970-01-01-00-00.00000000.00000000.a.log"));
+    }
+
+    #[test]
+    fn worker_shared_retention_skips_locked() {
+        let fs = InMemoryFilesystem::new();
+        let clock = TestClock::new();
+        let rng = TestRng::new();
+
+        let mut a = shared_worker(&fs, &clock, &rng, "a", false, 2);
+        let mut b = shared_worker(&fs, &clock, &rng, "b", false, 2);
+
+        let mut batch = EventBatch::new();
+        batch.push(*b"a1\n");
+        let Ok(()) = a.on_batch(batch) else {
+            panic!("failed to write batch");
+        };
+
+        // Roll `b` over a few times; the file owned by `a` is never deleted
+        for _ in 0..3 {
+            clock.advance(Duration::from_secs(60));
+
+            let mut batch = EventBatch::new();
+            batch.push(*b"b1\n");
+            let Ok(()) = b.on_batch(batch) else {
+                panic!("failed to write batch");
+            };
+        }
+
+        let mut files = fs.iter().map(|(path, _)| path).collect::<Vec<_>>();
+        files.sort();
+
+        assert_eq!(
+            vec![
+                "logs/test.1970-01-01-00-00.00000000.00000000.a.log",
+                "logs/test.1970-01-01-00-03.00000000.00000000.b.log",
+            ],
+            files,
+        );
+
+        // Once `a` is no longer writing, its file can be deleted
+        // along with the file `b` is rolling away from
+        drop(a);
+
+        clock.advance(Duration::from_secs(60));
+
+        let mut batch = EventBatch::new();
+        batch.push(*b"b1\n");
+        let Ok(()) = b.on_batch(batch) else {
+            panic!("failed to write batch");
+        };
+
+        let mut files = fs.iter().map(|(path, _)| path).collect::<Vec<_>>();
+        files.sort();
+
+        assert_eq!(
+            vec!["logs/test.1970-01-01-00-04.00000000.00000000.b.log"],
+            files
+        );
+    }
+
+    #[test]
+    fn worker_shared_reuse() {
+        let fs = InMemoryFilesystem::new();
+        let clock = TestClock::new();
+        let rng = TestRng::new();
+
+        let mut a = shared_worker(&fs, &clock, &rng, "a", true, 10);
+
+        let mut batch = EventBatch::new();
+        batch.push(*b"a1\n");
+        let Ok(()) = a.on_batch(batch) else {
+            panic!("failed to write batch");
+        };
+
+        drop(a);
+
+        rng.increment();
+
+        let mut b = shared_worker(&fs, &clock, &rng, "b", true, 10);
+
+        let mut batch = EventBatch::new();
+        batch.push(*b"b1\n");
+        let Ok(()) = b.on_batch(batch) else {
+            panic!("failed to write batch");
+        };
+
+        drop(b);
+
+        // Re-open `a`
+        // This should re-use its own file, rather than the more recent one from `b`
+        let mut a = shared_worker(&fs, &clock, &rng, "a", true, 10);
+
+        let mut batch = EventBatch::new();
+        batch.push(*b"a2\n");
+        let Ok(()) = a.on_batch(batch) else {
+            panic!("failed to write batch");
+        };
+
+        assert_eq!(2, fs.iter().count());
+
+        assert_eq!(
+            *b"a1\n\na2\n",
+            *fs.get("logs/test.1970-01-01-00-00.00000000.00000000.a.log")
+                .contents()
+        );
+        assert_eq!(
+            *b"b1\n",
+            *fs.get("logs/test.1970-01-01-00-00.00000000.00000001.b.log")
+                .contents()
+        );
+    }
+
+    #[test]
+    fn worker_current_file_symlink() {
+        let fs = InMemoryFilesystem::new();
+        let clock = TestClock::new();
+        let rng = TestRng::new();
+        let metrics = Arc::new(InternalMetrics::default());
+
+        let mut worker = Worker::new(
+            metrics.clone(),
+            fs.clone(),
+            clock.clone(),
+            rng.clone(),
+            "logs".to_string(),
+            "test".to_string(),
+            "log".to_string(),
+            None,
+            Some(CurrentFile::Symlink),
+            RollBy::Minute,
+            false,
+            10,
+            1024,
+            b"\n",
+        );
+
+        let mut batch = EventBatch::new();
+        batch.push(*b"1\n");
+        let Ok(()) = worker.on_batch(batch) else {
+            panic!("failed to write batch");
+        };
+
+        assert_eq!(
+            Some("test.1970-01-01-00-00.00000000.00000000.log"),
+            fs.link("logs/test.log").as_deref()
+        );
+
+        // Advance the clock; this will produce a new file and update the link
+        clock.advance(Duration::from_secs(120));
+
+        let mut batch = EventBatch::new();
+        batch.push(*b"2\n");
+        let Ok(()) = worker.on_batch(batch) else {
+            panic!("failed to write batch");
+        };
+
+        assert_eq!(
+            Some("test.1970-01-01-00-02.00000000.00000000.log"),
+            fs.link("logs/test.log").as_deref()
+        );
+
+        // The link isn't considered part of the file set
+        assert_eq!(2, fs.iter().count());
+        assert_eq!(0, metrics.file_link_failed.sample());
+    }
+
+    #[test]
+    fn worker_current_file_pointer_shared() {
+        let fs = InMemoryFilesystem::new();
+        let clock = TestClock::new();
+        let rng = TestRng::new();
+        let metrics = Arc::new(InternalMetrics::default());
+
+        let mut worker = Worker::new(
+            metrics.clone(),
+            fs.clone(),
+            clock.clone(),
+            rng.clone(),
+            "logs".to_string(),
+            "test".to_string(),
+            "log".to_string(),
+            Some("a".to_string()),
+            Some(CurrentFile::Pointer),
+            RollBy::Minute,
+            false,
+            10,
+            1024,
+            b"\n",
+        );
+
+        let mut batch = EventBatch::new();
+        batch.push(*b"1\n");
+        let Ok(()) = worker.on_batch(batch) else {
+            panic!("failed to write batch");
+        };
+
+        assert_eq!(
+            Some("test.1970-01-01-00-00.00000000.00000000.a.log"),
+            fs.link("logs/test.a.log.current").as_deref()
+        );
+    }
+
+    #[test]
+    fn read_file_name_writer_id_valid() {
+        for (file_name, expected) in [
+            ("test.1970-01-01-00-00.00000000.00000000.a.log", Some("a")),
+            ("test.1970-01-01-00-00.00000000.00000000.log", None),
+            ("test.1970-01-01-00-00.00000000.00000000.a.b.log", None),
+            ("other.1970-01-01-00-00.00000000.00000000.a.log", None),
+        ] {
+            assert_eq!(
+                expected,
+                read_file_name_writer_id(file_name, "test", "log"),
+                "{file_name}"
+            );
+        }
+    }
+
+    #[test]
+    fn validate_writer_id_invalid() {
+        for writer_id in ["", "a.b", "a/b", "a b"] {
+            assert!(validate_writer_id(writer_id).is_err(), "{writer_id:?}");
+        }
+
+        for writer_id in ["a", "worker-1", "worker_1"] {
+            assert!(validate_writer_id(writer_id).is_ok(), "{writer_id:?}");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "default_writer")]
+    fn default_writer_err_chain() {
+        let err = emit::err::chain(&io::Error::other("failed to connect"));
+
+        let mut buf = FileBuf::new();
+        default_writer(&mut buf, &emit::evt!("failed: {err}", err).erase()).unwrap();
+
+        let written = str::from_utf8(&buf.0).unwrap();
+
+        assert!(
+            written.contains(
+                r#""err":{"chain":[{"type":"std::io::error::Error","message":"failed to connect"}]"#
+            ),
+            "{written}"
+        );
+    }
+
     #[test]
     fn file_closes_bg_thread_on_drop() {
         let mut files = set_with_writer(
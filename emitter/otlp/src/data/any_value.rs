@@ -80,21 +80,37 @@ impl<'a, K: sval_ref::ValueRef<'a>, V: sval_ref::ValueRef<'a>> sval_ref::ValueRe
     }
 }
 
-#[repr(transparent)]
-pub struct Stacktrace(dyn std::error::Error + 'static);
+/*
+The text of the `exception.stacktrace` attribute.
+
+This includes the chain of sources of an error, and its backtrace if it's a
+structured `emit::err::Chain` that captured one.
+*/
+pub struct Stacktrace<'a> {
+    cause: Option<&'a (dyn std::error::Error + 'static)>,
+    backtrace: Option<&'a std::backtrace::Backtrace>,
+}
+
+impl<'a> Stacktrace<'a> {
+    pub fn new<'v: 'a>(err: &'a emit::value::Value<'v>) -> Option<Self> {
+        let cause = err.to_borrowed_error().and_then(|err| err.source());
+        let backtrace = err
+            .downcast_ref::<emit::err::Chain>()
+            .and_then(|chain| chain.backtrace());
+
+        if cause.is_none() && backtrace.is_none() {
+            return None;
+        }
 
-impl Stacktrace {
-    pub fn new_borrowed<'a>(err: &'a (dyn std::error::Error + 'static)) -> &'a Self {
-        // SAFETY: `Stacktrace` and `dyn std::error::Error + 'static` have the same ABI
-        unsafe { &*(err as *const (dyn std::error::Error + 'static) as *const Stacktrace) }
+        Some(Stacktrace { cause, backtrace })
     }
 }
 
-impl fmt::Display for Stacktrace {
+impl<'a> fmt::Display for Stacktrace<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut first = true;
 
-        for cause in std::iter::successors(Some(&self.0), |err| (*err).source()) {
+        for cause in std::iter::successors(self.cause, |err| (*err).source()) {
             if !first {
                 f.write_str("\n")?;
             }
@@ -104,10 +120,30 @@ impl fmt::Display for Stacktrace {
             fmt::Display::fmt(cause, f)?;
         }
 
+        if let Some(backtrace) = self.backtrace {
+            if !first {
+                f.write_str("\n")?;
+            }
+
+            fmt::Display::fmt(backtrace, f)?;
+        }
+
         Ok(())
     }
 }
 
+/*
+The text of the `exception.type` attribute.
+
+This is only known for structured `emit::err::Chain`s.
+*/
+pub fn exception_type<'a>(err: &'a emit::value::Value) -> Option<&'a str> {
+    err.downcast_ref::<emit::err::Chain>()?
+        .causes()
+        .next()?
+        .ty()
+}
+
 pub struct TextValue<T>(pub T);
 
 impl<T: fmt::Display> sval::Value for TextValue<T> {
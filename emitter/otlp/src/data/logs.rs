@@ -95,7 +95,7 @@ mod tests {
     use prost::Message;
 
     use crate::data::{
-        generated::{collector::logs::v1 as request, logs::v1 as logs, util::*},
+        generated::{collector::logs::v1 as request, common, logs::v1 as logs, util::*},
         util::*,
     };
 
@@ -156,6 +156,16 @@ mod tests {
         });
     }
 
+    #[test]
+    fn encode_dropped_props() {
+        encode_event::<LogsEventEncoder>(emit::evt!("event", dropped_props: 3), |buf| {
+            let de = logs::LogRecord::decode(buf).unwrap();
+
+            assert_eq!(0, de.attributes.len());
+            assert_eq!(3, de.dropped_attributes_count);
+        });
+    }
+
     #[test]
     fn encode_err_str() {
         encode_event::<LogsEventEncoder>(emit::evt!("failed: {err}", err: "test"), |buf| {
@@ -237,6 +247,57 @@ mod tests {
         });
     }
 
+    #[test]
+    fn encode_err_chain() {
+        #[derive(Debug)]
+        struct Error {
+            source: std::io::Error,
+        }
+
+        impl std::fmt::Display for Error {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "something went wrong")
+            }
+        }
+
+        impl std::error::Error for Error {
+            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                Some(&self.source)
+            }
+        }
+
+        let err = emit::err::chain(&Error {
+            source: std::io::Error::other("IO error"),
+        });
+
+        encode_event::<LogsEventEncoder>(emit::evt!("failed: {err}"), |buf| {
+            let de = logs::LogRecord::decode(buf).unwrap();
+
+            assert_eq!(3, de.attributes.len());
+
+            assert_eq!("exception.type", de.attributes[0].key);
+            assert_eq!(
+                Some(string_value(std::any::type_name::<Error>())),
+                de.attributes[0].value
+            );
+
+            // The stacktrace may also include a backtrace, depending on the environment
+            assert_eq!("exception.stacktrace", de.attributes[1].key);
+            let Some(common::v1::any_value::Value::StringValue(ref stacktrace)) =
+                de.attributes[1].value.as_ref().unwrap().value
+            else {
+                panic!("unexpected stacktrace {:?}", de.attributes[1].value);
+            };
+            assert!(stacktrace.starts_with("caused by: IO error"));
+
+            assert_eq!("exception.message", de.attributes[2].key);
+            assert_eq!(
+                Some(string_value("something went wrong")),
+                de.attributes[2].value
+            );
+        });
+    }
+
     #[test]
     fn encode_request_basic() {
         encode_request::<LogsEventEncoder, LogsRequestEncoder>(
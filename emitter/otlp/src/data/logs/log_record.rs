@@ -2,7 +2,9 @@ use std::marker::PhantomData;
 
 use sval_derive::Value;
 
-use crate::data::{AnyValue, KeyValue, Stacktrace, TextValue, stream_attributes, stream_field};
+use crate::data::{
+    AnyValue, KeyValue, Stacktrace, TextValue, exception_type, stream_attributes, stream_field,
+};
 
 #[derive(Value)]
 #[repr(i32)]
@@ -44,10 +46,13 @@ const LOG_RECORD_TRACE_ID_LABEL: sval::Label =
     sval::Label::new("traceId").with_tag(&sval::tags::VALUE_IDENT);
 const LOG_RECORD_SPAN_ID_LABEL: sval::Label =
     sval::Label::new("spanId").with_tag(&sval::tags::VALUE_IDENT);
+const LOG_RECORD_DROPPED_ATTRIBUTES_COUNT_LABEL: sval::Label =
+    sval::Label::new("droppedAttributesCount").with_tag(&sval::tags::VALUE_IDENT);
 
 const LOG_RECORD_SEVERITY_NUMBER_INDEX: sval::Index = sval::Index::new(2);
 const LOG_RECORD_SEVERITY_TEXT_INDEX: sval::Index = sval::Index::new(3);
 const LOG_RECORD_ATTRIBUTES_INDEX: sval::Index = sval::Index::new(6);
+const LOG_RECORD_DROPPED_ATTRIBUTES_COUNT_INDEX: sval::Index = sval::Index::new(7);
 const LOG_RECORD_TRACE_ID_INDEX: sval::Index = sval::Index::new(9);
 const LOG_RECORD_SPAN_ID_INDEX: sval::Index = sval::Index::new(10);
 
@@ -87,6 +92,7 @@ impl<
         let mut trace_id = None;
         let mut span_id = None;
         let mut level = emit::level::Level::default();
+        let mut dropped_attributes_count = 0;
 
         stream.record_tuple_begin(None, None, None, None)?;
 
@@ -115,12 +121,24 @@ impl<
                             .map(|trace_id| TR::from(trace_id));
                         Ok(())
                     }
+                    emit::well_known::KEY_DROPPED_PROPS => {
+                        dropped_attributes_count = v.cast::<u32>().unwrap_or_default();
+                        Ok(())
+                    }
                     emit::well_known::KEY_ERR => {
-                        // If the error has a cause chain then write it into the exception.stacktrace attribute
-                        if let Some(cause) = v.to_borrowed_error().and_then(|err| err.source()) {
+                        // If the error is structured then write its type into the exception.type attribute
+                        if let Some(ty) = exception_type(&v) {
+                            stream.stream_custom_attribute_computed(
+                                emit::Str::new("exception.type"),
+                                TextValue(ty),
+                            )?;
+                        }
+
+                        // If the error has a cause chain or backtrace then write it into the exception.stacktrace attribute
+                        if let Some(stacktrace) = Stacktrace::new(&v) {
                             stream.stream_custom_attribute_computed(
                                 emit::Str::new("exception.stacktrace"),
-                                TextValue(Stacktrace::new_borrowed(cause)),
+                                TextValue(stacktrace),
                             )?;
                         }
 
@@ -159,6 +177,15 @@ impl<
             |stream| sval::stream_display(stream, level),
         )?;
 
+        if dropped_attributes_count > 0 {
+            stream_field(
+                &mut *stream,
+                &LOG_RECORD_DROPPED_ATTRIBUTES_COUNT_LABEL,
+                &LOG_RECORD_DROPPED_ATTRIBUTES_COUNT_INDEX,
+                |stream| stream.u32(dropped_attributes_count),
+            )?;
+        }
+
         if let Some(trace_id) = trace_id {
             stream_field(
                 &mut *stream,
@@ -81,6 +81,8 @@ impl EventEncoder for MetricsEventEncoder {
             let mut metric_unit = None;
             let mut dist_exp_scale = None;
             let mut dist_exp_buckets = None;
+            let mut dist_bucket_bounds = None;
+            let mut dist_bucket_counts = None;
             let mut dist_min = None;
             let mut dist_max = None;
             let mut dist_sum = None;
@@ -103,6 +105,16 @@ impl EventEncoder for MetricsEventEncoder {
 
                     ControlFlow::Continue(())
                 }
+                emit::well_known::KEY_DIST_BUCKET_BOUNDS => {
+                    dist_bucket_bounds = Some(v);
+
+                    ControlFlow::Continue(())
+                }
+                emit::well_known::KEY_DIST_BUCKET_COUNTS => {
+                    dist_bucket_counts = Some(v);
+
+                    ControlFlow::Continue(())
+                }
                 emit::well_known::KEY_DIST_SUM => {
                     dist_sum = Some(v);
 
@@ -161,9 +173,9 @@ impl EventEncoder for MetricsEventEncoder {
                     if let Some(distribution) = Distribution::from_values(
                         dist_exp_scale,
                         dist_exp_buckets,
-                        dist_sum,
-                        dist_min,
-                        dist_max,
+                        dist_sum.clone(),
+                        dist_min.clone(),
+                        dist_max.clone(),
                     ) {
                         E::encode(Metric::<_, _, _> {
                             name: &sval::Display::new(metric_name),
@@ -197,6 +209,31 @@ impl EventEncoder for MetricsEventEncoder {
                                 },
                             ),
                         })
+                    } else if let Some(distribution) = ExplicitDistribution::from_values(
+                        dist_bucket_bounds,
+                        dist_bucket_counts,
+                        dist_sum,
+                        dist_min,
+                        dist_max,
+                    ) {
+                        E::encode(Metric::<_, _, _> {
+                            name: &sval::Display::new(metric_name),
+                            unit: &metric_unit.map(sval::Display::new),
+                            data: &MetricData::Histogram::<_>(Histogram::<_> {
+                                aggregation_temporality,
+                                data_points: &[HistogramDataPoint {
+                                    attributes: &attributes,
+                                    start_time_unix_nano,
+                                    time_unix_nano,
+                                    count: distribution.count,
+                                    sum: distribution.sum,
+                                    bucket_counts: &distribution.counts,
+                                    explicit_bounds: &*distribution.bounds,
+                                    min: distribution.min,
+                                    max: distribution.max,
+                                }],
+                            }),
+                        })
                     } else {
                         E::encode(Metric::<_, _, _> {
                             name: &sval::Display::new(metric_name),
@@ -559,6 +596,61 @@ impl Value for DistributionBuckets {
     }
 }
 
+struct ExplicitDistribution {
+    bounds: Vec<f64>,
+    counts: ExplicitBucketCounts,
+    count: u64,
+    sum: Option<f64>,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+// Bucket counts are `fixed64` in explicit histograms
+struct ExplicitBucketCounts(Vec<u64>);
+
+impl ExplicitDistribution {
+    fn from_values(
+        dist_bucket_bounds: Option<emit::Value>,
+        dist_bucket_counts: Option<emit::Value>,
+        dist_sum: Option<emit::Value>,
+        dist_min: Option<emit::Value>,
+        dist_max: Option<emit::Value>,
+    ) -> Option<Self> {
+        let bounds = dist_bucket_bounds?.to_f64_sequence()?;
+        let counts = dist_bucket_counts?.to_u64_sequence()?;
+
+        // There's always one more bucket than there are bounds
+        if counts.len() != bounds.len() + 1 {
+            return None;
+        }
+
+        Some(ExplicitDistribution {
+            bounds,
+            count: counts.iter().sum(),
+            counts: ExplicitBucketCounts(counts),
+            sum: dist_sum.and_then(|v| v.cast::<f64>()),
+            min: dist_min.and_then(|v| v.cast::<f64>()),
+            max: dist_max.and_then(|v| v.cast::<f64>()),
+        })
+    }
+}
+
+impl Value for ExplicitBucketCounts {
+    fn stream<'sval, S: Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> sval::Result {
+        stream.seq_begin(Some(self.0.len()))?;
+
+        for count in &self.0 {
+            stream.seq_value_begin()?;
+            stream.tagged_begin(Some(&sval_protobuf::tags::PROTOBUF_I64), None, None)?;
+            stream.u64(*count)?;
+            stream.tagged_end(Some(&sval_protobuf::tags::PROTOBUF_I64), None, None)?;
+            stream.seq_value_end()?;
+        }
+
+        stream.seq_end()
+    }
+}
+
 fn bucket(value: f64, gamma: f64) -> isize {
     value.log(gamma).ceil() as isize
 }
@@ -912,6 +1004,108 @@ mod tests {
         );
     }
 
+    #[test]
+    fn encode_histogram_explicit() {
+        let bounds = vec![1.0, 5.0, 10.0];
+        let counts = vec![1u64, 4, 0, 2];
+
+        encode_event::<MetricsEventEncoder>(
+            emit::evt!(
+                "{metric_agg} of {metric_name} is {metric_value}",
+                evt_kind: "metric",
+                metric_name: "test",
+                metric_agg: "count",
+                metric_value: 7,
+                #[emit::as_value]
+                dist_bucket_bounds: bounds,
+                #[emit::as_value]
+                dist_bucket_counts: counts,
+                dist_min: 0.5,
+                dist_max: 12.0,
+                dist_sum: 31.5,
+            ),
+            |buf| {
+                let de = metrics::Metric::decode(buf).unwrap();
+
+                assert_eq!("test", de.name);
+
+                match de.data {
+                    Some(metrics::metric::Data::Histogram(histogram)) => {
+                        assert_eq!(1, histogram.data_points.len());
+
+                        assert_eq!(
+                            &[1.0, 5.0, 10.0],
+                            &*histogram.data_points[0].explicit_bounds
+                        );
+                        assert_eq!(&[1, 4, 0, 2], &*histogram.data_points[0].bucket_counts);
+
+                        assert_eq!(7, histogram.data_points[0].count);
+                        assert_eq!(Some(0.5), histogram.data_points[0].min);
+                        assert_eq!(Some(12.0), histogram.data_points[0].max);
+                        assert_eq!(Some(31.5), histogram.data_points[0].sum);
+                    }
+                    other => panic!("unexpected {other:?}"),
+                }
+            },
+        );
+    }
+
+    #[test]
+    fn encode_histogram_explicit_distribution() {
+        let mut distribution = emit::metric::explicit::Distribution::new([1.0, 5.0]);
+
+        distribution.observe(0.5);
+        distribution.observe(3.0);
+        distribution.observe(3.0);
+
+        let metric = emit::Metric::new(
+            emit::path!("test"),
+            emit::Empty,
+            emit::props! {
+                metric_name: "test",
+                metric_agg: "count",
+                metric_value: distribution.count(),
+            }
+            .and_props(&distribution),
+        );
+
+        encode_event::<MetricsEventEncoder>(emit::event::ToEvent::to_event(&metric), |buf| {
+            let de = metrics::Metric::decode(buf).unwrap();
+
+            match de.data {
+                Some(metrics::metric::Data::Histogram(histogram)) => {
+                    assert_eq!(&[1.0, 5.0], &*histogram.data_points[0].explicit_bounds);
+                    assert_eq!(&[1, 2, 0], &*histogram.data_points[0].bucket_counts);
+                    assert_eq!(3, histogram.data_points[0].count);
+                }
+                other => panic!("unexpected {other:?}"),
+            }
+        });
+    }
+
+    #[test]
+    fn encode_histogram_explicit_mismatched() {
+        encode_event::<MetricsEventEncoder>(
+            emit::evt!(
+                "{metric_agg} of {metric_name} is {metric_value}",
+                evt_kind: "metric",
+                metric_name: "test",
+                metric_agg: "count",
+                metric_value: 7,
+                #[emit::as_sval]
+                dist_bucket_bounds: [1.0, 5.0],
+                #[emit::as_sval]
+                dist_bucket_counts: [1, 4],
+            ),
+            |buf| {
+                let de = metrics::Metric::decode(buf).unwrap();
+
+                // Falls back to a sum when the buckets are invalid
+                assert!(matches!(de.data, Some(metrics::metric::Data::Sum(_))));
+            },
+        );
+    }
+
     #[test]
     fn encode_request_basic() {
         encode_request::<MetricsEventEncoder, MetricsRequestEncoder>(
@@ -18,6 +18,8 @@ pub enum MetricData<'a, DP: ?Sized = [NumberDataPoint<'a>]> {
     Gauge(Gauge<'a, DP>),
     #[sval(label = "sum", index = 7)]
     Sum(Sum<'a, DP>),
+    #[sval(label = "histogram", index = 9)]
+    Histogram(Histogram<'a, DP>),
     #[sval(label = "exponentialHistogram", index = 10)]
     ExponentialHistogram(ExponentialHistogram<'a, DP>),
 }
@@ -38,6 +40,14 @@ pub struct Sum<'a, DP: ?Sized = [NumberDataPoint<'a>]> {
     pub is_monotonic: bool,
 }
 
+#[derive(Value)]
+pub struct Histogram<'a, DP: ?Sized = [HistogramDataPoint<'a>]> {
+    #[sval(label = "dataPoints", index = 1)]
+    pub data_points: &'a DP,
+    #[sval(label = "aggregationTemporality", index = 2)]
+    pub aggregation_temporality: AggregationTemporality,
+}
+
 #[derive(Value)]
 pub struct ExponentialHistogram<'a, DP: ?Sized = [ExponentialHistogramDataPoint<'a>]> {
     #[sval(label = "dataPoints", index = 1)]
@@ -90,6 +100,45 @@ pub struct AsDouble(pub f64);
 #[sval(tag = "sval_protobuf::tags::PROTOBUF_I64")]
 pub struct AsInt(pub i64);
 
+#[derive(Value)]
+pub struct HistogramDataPoint<
+    'a,
+    A: ?Sized = [KeyValue<&'a str, &'a AnyValue<'a>>],
+    C: ?Sized = [u64],
+    B: ?Sized = [f64],
+> {
+    #[sval(label = "attributes", index = 9)]
+    pub attributes: &'a A,
+    #[sval(
+        label = "startTimeUnixNano",
+        index = 2,
+        data_tag = "sval_protobuf::tags::PROTOBUF_I64"
+    )]
+    pub start_time_unix_nano: u64,
+    #[sval(
+        label = "timeUnixNano",
+        index = 3,
+        data_tag = "sval_protobuf::tags::PROTOBUF_I64"
+    )]
+    pub time_unix_nano: u64,
+    #[sval(
+        label = "count",
+        index = 4,
+        data_tag = "sval_protobuf::tags::PROTOBUF_I64"
+    )]
+    pub count: u64,
+    #[sval(label = "sum", index = 5)]
+    pub sum: Option<f64>,
+    #[sval(label = "bucketCounts", index = 6)]
+    pub bucket_counts: &'a C,
+    #[sval(label = "explicitBounds", index = 7)]
+    pub explicit_bounds: &'a B,
+    #[sval(label = "min", index = 11)]
+    pub min: Option<f64>,
+    #[sval(label = "max", index = 12)]
+    pub max: Option<f64>,
+}
+
 #[derive(Value)]
 pub struct ExponentialHistogramDataPoint<
     'a,
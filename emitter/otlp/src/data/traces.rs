@@ -128,7 +128,7 @@ mod tests {
 
     use crate::{
         data::{
-            generated::{collector::trace::v1 as request, trace::v1 as trace, util::*},
+            generated::{collector::trace::v1 as request, common, trace::v1 as trace, util::*},
             util::*,
         },
         util::*,
@@ -159,6 +159,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn encode_dropped_props() {
+        encode_event::<TracesEventEncoder>(
+            emit::evt!(
+                extent: ts(1)..ts(13),
+                "greet {user}",
+                user: "test",
+                evt_kind: "span",
+                span_name: "test",
+                trace_id: "00000000000000000000000000000001",
+                span_id: "0000000000000001",
+                dropped_props: 3,
+            ),
+            |buf| {
+                let de = trace::Span::decode(buf).unwrap();
+
+                assert_eq!(1, de.attributes.len());
+                assert_eq!(3, de.dropped_attributes_count);
+            },
+        );
+    }
+
     #[test]
     fn encode_ids_structured() {
         encode_event::<TracesEventEncoder>(
@@ -434,6 +456,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn encode_err_chain() {
+        #[derive(Debug)]
+        struct Error {
+            source: std::io::Error,
+        }
+
+        impl std::fmt::Display for Error {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "something went wrong")
+            }
+        }
+
+        impl std::error::Error for Error {
+            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                Some(&self.source)
+            }
+        }
+
+        let err = emit::err::chain(&Error {
+            source: std::io::Error::other("IO error"),
+        });
+
+        encode_event::<TracesEventEncoder>(
+            emit::evt!(
+                extent: ts(1)..ts(13),
+                "failed: {err}",
+                err,
+                evt_kind: "span",
+                span_name: "test",
+                trace_id: "00000000000000000000000000000001",
+                span_id: "0000000000000001"
+            ),
+            |buf| {
+                let de = trace::Span::decode(buf).unwrap();
+
+                assert_eq!(1, de.events.len());
+
+                let de = &de.events[0];
+
+                assert_eq!("exception", de.name);
+
+                assert_eq!(3, de.attributes.len());
+
+                assert_eq!("exception.type", de.attributes[0].key);
+                assert_eq!(
+                    Some(string_value(std::any::type_name::<Error>())),
+                    de.attributes[0].value
+                );
+
+                // The stacktrace may also include a backtrace, depending on the environment
+                assert_eq!("exception.stacktrace", de.attributes[1].key);
+                let Some(common::v1::any_value::Value::StringValue(ref stacktrace)) =
+                    de.attributes[1].value.as_ref().unwrap().value
+                else {
+                    panic!("unexpected stacktrace {:?}", de.attributes[1].value);
+                };
+                assert!(stacktrace.starts_with("caused by: IO error"));
+
+                assert_eq!("exception.message", de.attributes[2].key);
+                assert_eq!(
+                    Some(string_value("something went wrong")),
+                    de.attributes[2].value
+                );
+            },
+        );
+    }
+
     #[test]
     fn encode_request_basic() {
         encode_request::<TracesEventEncoder, TracesRequestEncoder>(
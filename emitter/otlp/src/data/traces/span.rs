@@ -3,7 +3,8 @@ use std::marker::PhantomData;
 use sval_derive::Value;
 
 use crate::data::{
-    AnyValue, EmitValue, KeyValue, Stacktrace, TextValue, stream_attributes, stream_field,
+    AnyValue, EmitValue, KeyValue, Stacktrace, TextValue, exception_type, stream_attributes,
+    stream_field,
 };
 
 #[derive(Value)]
@@ -74,8 +75,11 @@ const SPAN_STATUS_LABEL: sval::Label =
 const SPAN_EVENTS_LABEL: sval::Label =
     sval::Label::new("events").with_tag(&sval::tags::VALUE_IDENT);
 const SPAN_LINKS_LABEL: sval::Label = sval::Label::new("links").with_tag(&sval::tags::VALUE_IDENT);
+const SPAN_DROPPED_ATTRIBUTES_COUNT_LABEL: sval::Label =
+    sval::Label::new("droppedAttributesCount").with_tag(&sval::tags::VALUE_IDENT);
 
 const SPAN_ATTRIBUTES_INDEX: sval::Index = sval::Index::new(9);
+const SPAN_DROPPED_ATTRIBUTES_COUNT_INDEX: sval::Index = sval::Index::new(10);
 const SPAN_TRACE_ID_INDEX: sval::Index = sval::Index::new(1);
 const SPAN_SPAN_ID_INDEX: sval::Index = sval::Index::new(2);
 const SPAN_PARENT_SPAN_ID_INDEX: sval::Index = sval::Index::new(4);
@@ -136,6 +140,7 @@ impl<
         let mut links = None;
         let mut level = emit::level::Level::default();
         let mut has_err = false;
+        let mut dropped_attributes_count = 0;
 
         stream.record_tuple_begin(None, None, None, None)?;
 
@@ -179,6 +184,10 @@ impl<
                         has_err = true;
                         Ok(())
                     }
+                    emit::well_known::KEY_DROPPED_PROPS => {
+                        dropped_attributes_count = v.cast::<u32>().unwrap_or_default();
+                        Ok(())
+                    }
                     // Ignored
                     emit::well_known::KEY_EVT_KIND
                     | emit::well_known::KEY_SPAN_NAME
@@ -189,6 +198,15 @@ impl<
             },
         )?;
 
+        if dropped_attributes_count > 0 {
+            stream_field(
+                &mut *stream,
+                &SPAN_DROPPED_ATTRIBUTES_COUNT_LABEL,
+                &SPAN_DROPPED_ATTRIBUTES_COUNT_INDEX,
+                |stream| stream.u32(dropped_attributes_count),
+            )?;
+        }
+
         if let Some(trace_id) = trace_id {
             stream_field(
                 &mut *stream,
@@ -242,39 +260,40 @@ impl<
                 |stream| {
                     let err = err.by_ref();
 
-                    // If the error has a cause chain then write it into the exception.stacktrace attribute
-                    // We need to duplicate the whole event because the type of its attributes collection
-                    // changes depending on whether there's a stacktrace or not
-                    if let Some(cause) = err.to_borrowed_error().and_then(|err| err.source()) {
-                        stream.value_computed(&[Event {
-                            name: "exception",
-                            time_unix_nano: self.time_unix_nano,
-                            attributes: &InlineEventAttributes {
-                                attributes: &[
-                                    KeyValue {
-                                        key: "exception.stacktrace",
-                                        value: &TextValue(Stacktrace::new_borrowed(cause))
-                                            as &dyn sval_dynamic::Value,
-                                    },
-                                    KeyValue {
-                                        key: "exception.message",
-                                        value: &EmitValue(err) as &dyn sval_dynamic::Value,
-                                    },
-                                ],
-                            },
-                        }])
-                    } else {
-                        stream.value_computed(&[Event {
-                            name: "exception",
-                            time_unix_nano: self.time_unix_nano,
-                            attributes: &InlineEventAttributes {
-                                attributes: &[KeyValue {
-                                    key: "exception.message",
-                                    value: EmitValue(err),
-                                }],
-                            },
-                        }])
+                    let ty = exception_type(&err).map(TextValue);
+                    let stacktrace = Stacktrace::new(&err).map(TextValue);
+                    let message = EmitValue(err.by_ref());
+
+                    let mut attributes = Vec::<KeyValue<&str, &dyn sval_dynamic::Value>>::new();
+
+                    // If the error is structured then write its type into the exception.type attribute
+                    if let Some(ref ty) = ty {
+                        attributes.push(KeyValue {
+                            key: "exception.type",
+                            value: ty,
+                        });
                     }
+
+                    // If the error has a cause chain or backtrace then write it into the exception.stacktrace attribute
+                    if let Some(ref stacktrace) = stacktrace {
+                        attributes.push(KeyValue {
+                            key: "exception.stacktrace",
+                            value: stacktrace,
+                        });
+                    }
+
+                    attributes.push(KeyValue {
+                        key: "exception.message",
+                        value: &message,
+                    });
+
+                    stream.value_computed(&[Event {
+                        name: "exception",
+                        time_unix_nano: self.time_unix_nano,
+                        attributes: &InlineEventAttributes {
+                            attributes: &*attributes,
+                        },
+                    }])
                 },
             )?;
 
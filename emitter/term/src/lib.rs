@@ -29,7 +29,7 @@ fn main() {
 }
 ```
 
-`emit_term` uses a format optimized for human legibility, not for machine processing. You may also want to emit diagnostics to another location, such as OTLP through `emit_otlp` or a rolling file through `emit_file` for processing. You can use [`emit::Setup::and_emit_to`] to combine multiple emitters:
+By default, `emit_term` uses a format optimized for human legibility, not for machine processing. See [Output styles](#output-styles) for alternatives that are suitable for machine processing. You may also want to emit diagnostics to another location, such as OTLP through `emit_otlp` or a rolling file through `emit_file` for processing. You can use [`emit::Setup::and_emit_to`] to combine multiple emitters:
 
 ```
 # fn some_other_emitter() -> impl emit::Emitter + Send + Sync + 'static {
@@ -49,7 +49,7 @@ fn main() {
 
 ## Configuration
 
-`emit_term` has a fixed format, but can be configured to force or disable color output instead of detect it.
+`emit_term` can be configured to force or disable color output instead of detect it.
 
 To disable colors, call [`Stdout::colored`] with the value `false`:
 
@@ -80,6 +80,56 @@ fn main() {
     rt.blocking_flush(std::time::Duration::from_secs(5));
 }
 ```
+
+## Output styles
+
+`emit_term` can write events in a few different styles:
+
+- Pretty (default): A human-readable format with trace and span ids, and errors and distributions written on separate lines.
+- Compact: A human-readable format with a single line per event, without trace and span ids. Call [`Stdout::compact`] to use it.
+- JSON: Newline-delimited JSON, with one object per event. Call [`Stdout::json`] to use it.
+- Logfmt: Space-separated `key=value` pairs, with one line per event. Call [`Stdout::logfmt`] to use it.
+- Template: A user-defined layout with a single line per event. Call [`Stdout::template`] with a [`LineTemplate`] to use it.
+- Tree: A human-readable format with a single line per event, indented by the depth of the span it was emitted in. Call [`Stdout::tree`] to use it.
+
+The pretty style summarizes distributions with their quartiles. Call [`Stdout::percentiles`] to summarize them with their p50, p95, and p99 percentiles instead.
+
+For example, to write newline-delimited JSON to `stdout`:
+
+```rust
+fn main() {
+    let rt = emit::setup()
+        .emit_to(emit_term::stdout().json())
+        .init();
+
+    // Your app code goes here
+
+    rt.blocking_flush(std::time::Duration::from_secs(5));
+}
+```
+
+The JSON and logfmt styles are intended for machine processing, so they're never written with colors.
+
+## Line templates
+
+The layout of each line can be customized with a [`LineTemplate`]. Line templates are parsed once, and use holes like `{ts}` and `{msg}` for the parts of the event to write:
+
+```rust
+fn main() {
+    let rt = emit::setup()
+        .emit_to(emit_term::stdout().template(
+            emit_term::LineTemplate::parse("{ts:utc,ms} {lvl:pad5} {mdl:last} {msg} {props:max40}")
+                .expect("invalid line template"),
+        ))
+        .init();
+
+    // Your app code goes here
+
+    rt.blocking_flush(std::time::Duration::from_secs(5));
+}
+```
+
+See the [`LineTemplate`] docs for the full set of holes and options.
 */
 
 #![doc(html_logo_url = "https://raw.githubusercontent.com/emit-rs/emit/main/asset/logo.svg")]
@@ -93,6 +143,11 @@ use emit::well_known::{
 };
 use termcolor::{Buffer, BufferWriter, Color, ColorChoice, ColorSpec, WriteColor};
 
+mod template;
+mod tree;
+
+pub use self::template::*;
+
 /**
 Get an emitter that writes to `stdout`.
 
@@ -142,6 +197,8 @@ impl Stdout {
         Stdout {
             writer: Writer {
                 writer: BufferWriter::stdout(ColorChoice::Auto),
+                style: Style::Pretty,
+                dist: DistStyle::Quartiles,
             },
         }
     }
@@ -153,17 +210,75 @@ impl Stdout {
     */
     pub fn colored(mut self, colored: bool) -> Self {
         if colored {
-            self.writer = Writer {
-                writer: BufferWriter::stdout(ColorChoice::Always),
-            };
+            self.writer.writer = BufferWriter::stdout(ColorChoice::Always);
         } else {
-            self.writer = Writer {
-                writer: BufferWriter::stdout(ColorChoice::Never),
-            };
+            self.writer.writer = BufferWriter::stdout(ColorChoice::Never);
         }
 
         self
     }
+
+    /**
+    Write events in a compact, human-readable format.
+
+    Each event is written on a single line, without trace and span ids.
+    */
+    pub fn compact(mut self) -> Self {
+        self.writer.style = Style::Compact;
+        self
+    }
+
+    /**
+    Write events as newline-delimited JSON.
+
+    Each event is written as a JSON object on a single line, with its extent, module, message, template, and properties as fields. Colors are never used, regardless of [`Stdout::colored`].
+    */
+    pub fn json(mut self) -> Self {
+        self.writer.style = Style::Json;
+        self
+    }
+
+    /**
+    Write events as [logfmt](https://brandur.org/logfmt).
+
+    Each event is written as space-separated `key=value` pairs on a single line, with its extent, module, message, and properties as pairs. Values are quoted if they contain whitespace, `=`, or `"`. Colors are never used, regardless of [`Stdout::colored`].
+    */
+    pub fn logfmt(mut self) -> Self {
+        self.writer.style = Style::Logfmt;
+        self
+    }
+
+    /**
+    Write events using a user-defined [`LineTemplate`].
+
+    See the [`LineTemplate`] docs for the holes and options it supports. Colors are used for each hole, unless disabled by [`Stdout::colored`].
+    */
+    pub fn template(mut self, template: LineTemplate) -> Self {
+        self.writer.style = Style::Template(template);
+        self
+    }
+
+    /**
+    Write events as an indented tree of spans.
+
+    Each event is written on a single line, indented by the depth of the span it was emitted in. Spans are opened when the first event within them is written, and closed with their name and duration when they complete. The nesting of spans is based on the `span_id` and `span_parent` of each event, so events from concurrently executing spans may be interleaved.
+
+    Spans are only emitted when they complete, so the start line of a span is written lazily, when the first event within it is written, rather than when the span itself starts. Spans that complete without any events within them are written as a single line. Spans that never complete are tracked up to a limit, after which the oldest is forgotten.
+    */
+    pub fn tree(mut self) -> Self {
+        self.writer.style = Style::Tree(Default::default());
+        self
+    }
+
+    /**
+    Summarize distributions with their p50, p95, and p99 percentiles instead of their quartiles.
+
+    Distributions are only written by the default pretty style. See [`emit::metric::exp::Distribution::quantile`] for details on how percentiles are estimated.
+    */
+    pub fn percentiles(mut self) -> Self {
+        self.writer.dist = DistStyle::Percentiles;
+        self
+    }
 }
 
 impl emit::emitter::Emitter for Stdout {
@@ -209,6 +324,8 @@ impl Stderr {
         Stderr {
             writer: Writer {
                 writer: BufferWriter::stderr(ColorChoice::Auto),
+                style: Style::Pretty,
+                dist: DistStyle::Quartiles,
             },
         }
     }
@@ -220,17 +337,75 @@ impl Stderr {
     */
     pub fn colored(mut self, colored: bool) -> Self {
         if colored {
-            self.writer = Writer {
-                writer: BufferWriter::stderr(ColorChoice::Always),
-            };
+            self.writer.writer = BufferWriter::stderr(ColorChoice::Always);
         } else {
-            self.writer = Writer {
-                writer: BufferWriter::stderr(ColorChoice::Never),
-            };
+            self.writer.writer = BufferWriter::stderr(ColorChoice::Never);
         }
 
         self
     }
+
+    /**
+    Write events in a compact, human-readable format.
+
+    Each event is written on a single line, without trace and span ids.
+    */
+    pub fn compact(mut self) -> Self {
+        self.writer.style = Style::Compact;
+        self
+    }
+
+    /**
+    Write events as newline-delimited JSON.
+
+    Each event is written as a JSON object on a single line, with its extent, module, message, template, and properties as fields. Colors are never used, regardless of [`Stderr::colored`].
+    */
+    pub fn json(mut self) -> Self {
+        self.writer.style = Style::Json;
+        self
+    }
+
+    /**
+    Write events as [logfmt](https://brandur.org/logfmt).
+
+    Each event is written as space-separated `key=value` pairs on a single line, with its extent, module, message, and properties as pairs. Values are quoted if they contain whitespace, `=`, or `"`. Colors are never used, regardless of [`Stderr::colored`].
+    */
+    pub fn logfmt(mut self) -> Self {
+        self.writer.style = Style::Logfmt;
+        self
+    }
+
+    /**
+    Write events using a user-defined [`LineTemplate`].
+
+    See the [`LineTemplate`] docs for the holes and options it supports. Colors are used for each hole, unless disabled by [`Stderr::colored`].
+    */
+    pub fn template(mut self, template: LineTemplate) -> Self {
+        self.writer.style = Style::Template(template);
+        self
+    }
+
+    /**
+    Write events as an indented tree of spans.
+
+    Each event is written on a single line, indented by the depth of the span it was emitted in. Spans are opened when the first event within them is written, and closed with their name and duration when they complete. The nesting of spans is based on the `span_id` and `span_parent` of each event, so events from concurrently executing spans may be interleaved.
+
+    Spans are only emitted when they complete, so the start line of a span is written lazily, when the first event within it is written, rather than when the span itself starts. Spans that complete without any events within them are written as a single line. Spans that never complete are tracked up to a limit, after which the oldest is forgotten.
+    */
+    pub fn tree(mut self) -> Self {
+        self.writer.style = Style::Tree(Default::default());
+        self
+    }
+
+    /**
+    Summarize distributions with their p50, p95, and p99 percentiles instead of their quartiles.
+
+    Distributions are only written by the default pretty style. See [`emit::metric::exp::Distribution::quantile`] for details on how percentiles are estimated.
+    */
+    pub fn percentiles(mut self) -> Self {
+        self.writer.dist = DistStyle::Percentiles;
+        self
+    }
 }
 
 impl emit::emitter::Emitter for Stderr {
@@ -247,6 +422,24 @@ impl emit::runtime::InternalEmitter for Stderr {}
 
 struct Writer {
     writer: BufferWriter,
+    style: Style,
+    dist: DistStyle,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum DistStyle {
+    Quartiles,
+    Percentiles,
+}
+
+#[derive(Debug)]
+enum Style {
+    Pretty,
+    Compact,
+    Json,
+    Logfmt,
+    Template(LineTemplate),
+    Tree(tree::Tree),
 }
 
 impl Writer {
@@ -254,14 +447,47 @@ impl Writer {
         let evt = evt.to_event();
 
         with_shared_buf(&self.writer, |writer, buf| {
-            write_event(buf, evt);
+            match &self.style {
+                Style::Pretty => write_event(buf, evt, self.dist),
+                Style::Compact => write_event_compact(buf, evt),
+                Style::Json => write_event_json(buf, evt),
+                Style::Logfmt => write_event_logfmt(buf, evt),
+                Style::Template(template) => template.write_event(buf, &evt),
+                Style::Tree(tree) => tree.write_event(buf, evt),
+            }
 
             let _ = writer.print(buf);
         });
     }
 }
 
-fn write_event(buf: &mut Buffer, evt: emit::event::Event<impl emit::props::Props>) {
+fn write_event(
+    buf: &mut Buffer,
+    evt: emit::event::Event<impl emit::props::Props>,
+    dist: DistStyle,
+) {
+    write_ids(buf, &evt);
+
+    let lvl = write_header(buf, &evt);
+    write_plain(buf, "\n");
+
+    write_err(buf, &evt, lvl);
+    write_dist(buf, &evt, dist);
+}
+
+fn write_event_compact(buf: &mut Buffer, evt: emit::event::Event<impl emit::props::Props>) {
+    let lvl = write_header(buf, &evt);
+
+    if let Some(err) = evt.props().get(KEY_ERR) {
+        write_plain(buf, " (");
+        try_write_fg(buf, "err", lvl);
+        write_plain(buf, format_args!(": {err})"));
+    }
+
+    write_plain(buf, "\n");
+}
+
+fn write_ids(buf: &mut Buffer, evt: &emit::event::Event<impl emit::props::Props>) {
     if let Some(span_id) = evt.props().pull::<emit::SpanId, _>(KEY_SPAN_ID) {
         if let Some(trace_id) = evt.props().pull::<emit::TraceId, _>(KEY_TRACE_ID) {
             let trace_id_color = trace_id_color(&trace_id);
@@ -281,7 +507,12 @@ fn write_event(buf: &mut Buffer, evt: emit::event::Event<impl emit::props::Props
         write_plain(buf, hex_slice(&span_id.to_hex(), 4));
         write_plain(buf, " ");
     }
+}
 
+fn write_header(
+    buf: &mut Buffer,
+    evt: &emit::event::Event<impl emit::props::Props>,
+) -> Option<Color> {
     if let Some(extent) = evt.extent() {
         if let Some(len) = extent.len() {
             write_timestamp(buf, *extent.as_point());
@@ -323,8 +554,15 @@ fn write_event(buf: &mut Buffer, evt: emit::event::Event<impl emit::props::Props
     }
 
     let _ = evt.msg().write(TokenWriter { buf });
-    write_plain(buf, "\n");
 
+    lvl
+}
+
+fn write_err(
+    buf: &mut Buffer,
+    evt: &emit::event::Event<impl emit::props::Props>,
+    lvl: Option<Color>,
+) {
     if let Some(err) = evt.props().get(KEY_ERR) {
         write_plain(buf, "  ");
         try_write_fg(buf, "err", lvl);
@@ -337,46 +575,51 @@ fn write_event(buf: &mut Buffer, evt: emit::event::Event<impl emit::props::Props
                 write_plain(buf, format_args!(": {cause}\n"));
             }
         }
+
+        // Structured errors may also carry their type and a backtrace
+        if let Some(chain) = err.downcast_ref::<emit::err::Chain>() {
+            if let Some(ty) = chain.causes().next().and_then(|cause| cause.ty()) {
+                write_plain(buf, "  ");
+                try_write_fg(buf, "type", lvl);
+                write_plain(buf, format_args!(": {ty}\n"));
+            }
+
+            if let Some(backtrace) = chain.backtrace() {
+                write_plain(buf, "  ");
+                try_write_fg(buf, "backtrace", lvl);
+                write_plain(buf, ":\n");
+
+                for line in backtrace.to_string().lines() {
+                    write_plain(buf, format_args!("    {line}\n"));
+                }
+            }
+        }
     }
+}
 
+fn write_dist(
+    buf: &mut Buffer,
+    evt: &emit::event::Event<impl emit::props::Props>,
+    dist: DistStyle,
+) {
     if let (Some(scale), Some(buckets)) = (
         evt.props().pull::<i32, _>(KEY_DIST_EXP_SCALE),
         evt.props()
             .pull::<emit::metric::exp::BucketSet, _>(KEY_DIST_EXP_BUCKETS),
     ) {
-        let error = ((2.0f64.powf(2.0f64.powi(-scale)) - 1.0)
-            / (1.0 + 2.0f64.powf(2.0f64.powi(-scale))))
-            * 100.0;
-
-        let default = buckets.last().unwrap().0.get();
-        let total = buckets.total();
-
-        let mut qs = [
-            ("Q1", total / 4, default),
-            ("Q2", total / 2, default),
-            ("Q3", total - (total / 4), default),
-        ];
-
-        let mut seen = 0;
-        let mut fill = 0;
-        for (bucket, count) in &buckets {
-            seen += count;
-
-            let qs = &mut qs[fill..];
-            if qs.len() == 0 {
-                break;
-            }
+        let error = emit::metric::exp::relative_error(scale) * 100.0;
 
-            for (_, q, v) in qs {
-                if seen >= *q {
-                    *v = bucket.get();
-                    fill += 1;
-                }
-            }
-        }
+        let qs: &[(&str, f64)] = match dist {
+            DistStyle::Quartiles => &[("Q1", 0.25), ("Q2", 0.5), ("Q3", 0.75)],
+            DistStyle::Percentiles => &[("p50", 0.5), ("p95", 0.95), ("p99", 0.99)],
+        };
 
-        for (q, _, v) in qs {
-            write_fg(buf, format_args!("{q}"), FIELD);
+        for (label, q) in qs {
+            let Some(v) = buckets.quantile(*q) else {
+                continue;
+            };
+
+            write_fg(buf, format_args!("{label}"), FIELD);
             write_plain(buf, format_args!(": "));
             write_fg(buf, format_args!("{v}"), NUMBER);
             write_plain(buf, ", ");
@@ -389,6 +632,133 @@ fn write_event(buf: &mut Buffer, evt: emit::event::Event<impl emit::props::Props
     }
 }
 
+fn write_event_json(buf: &mut Buffer, evt: emit::event::Event<impl emit::props::Props>) {
+    use std::ops::ControlFlow;
+
+    use emit::{
+        Props as _,
+        well_known::{KEY_MDL, KEY_MSG, KEY_TPL, KEY_TS, KEY_TS_START},
+    };
+
+    struct EventValue<'a, P>(&'a emit::Event<'a, P>);
+
+    impl<'a, P: emit::Props> sval::Value for EventValue<'a, P> {
+        fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
+            &'sval self,
+            stream: &mut S,
+        ) -> sval::Result {
+            stream.record_begin(None, None, None, None)?;
+
+            if let Some(extent) = self.0.extent() {
+                if let Some(range) = extent.as_range() {
+                    stream.record_value_begin(None, &sval::Label::new(KEY_TS_START))?;
+                    sval::stream_display(&mut *stream, range.start)?;
+                    stream.record_value_end(None, &sval::Label::new(KEY_TS_START))?;
+                }
+
+                stream.record_value_begin(None, &sval::Label::new(KEY_TS))?;
+                sval::stream_display(&mut *stream, extent.as_point())?;
+                stream.record_value_end(None, &sval::Label::new(KEY_TS))?;
+            }
+
+            stream.record_value_begin(None, &sval::Label::new(KEY_MDL))?;
+            sval::stream_display(&mut *stream, self.0.mdl())?;
+            stream.record_value_end(None, &sval::Label::new(KEY_MDL))?;
+
+            stream.record_value_begin(None, &sval::Label::new(KEY_MSG))?;
+            sval::stream_display(&mut *stream, self.0.msg())?;
+            stream.record_value_end(None, &sval::Label::new(KEY_MSG))?;
+
+            stream.record_value_begin(None, &sval::Label::new(KEY_TPL))?;
+            sval::stream_display(&mut *stream, self.0.tpl())?;
+            stream.record_value_end(None, &sval::Label::new(KEY_TPL))?;
+
+            let _ = self.0.props().dedup().for_each(|k, v| {
+                match (|| {
+                    stream.record_value_begin(None, &sval::Label::new_computed(k.get()))?;
+                    stream.value_computed(&v)?;
+                    stream.record_value_end(None, &sval::Label::new_computed(k.get()))?;
+
+                    Ok::<(), sval::Error>(())
+                })() {
+                    Ok(()) => ControlFlow::Continue(()),
+                    Err(_) => ControlFlow::Break(()),
+                }
+            });
+
+            stream.record_end(None, None, None)
+        }
+    }
+
+    // If the event fails to serialize then discard any partial output
+    if sval_json::stream_to_io_write(&mut *buf, EventValue(&evt)).is_err() {
+        buf.clear();
+        return;
+    }
+
+    write_plain(buf, "\n");
+}
+
+fn write_event_logfmt(buf: &mut Buffer, evt: emit::event::Event<impl emit::props::Props>) {
+    use std::ops::ControlFlow;
+
+    use emit::{
+        Props as _,
+        well_known::{KEY_MDL, KEY_MSG, KEY_TS, KEY_TS_START},
+    };
+
+    if let Some(extent) = evt.extent() {
+        if let Some(range) = extent.as_range() {
+            write_logfmt_pair(buf, KEY_TS_START, range.start);
+        }
+
+        write_logfmt_pair(buf, KEY_TS, extent.as_point());
+    }
+
+    write_logfmt_pair(buf, KEY_MDL, evt.mdl());
+    write_logfmt_pair(buf, KEY_MSG, evt.msg());
+
+    let _ = evt.props().dedup().for_each(|k, v| {
+        write_logfmt_pair(buf, k, v);
+
+        ControlFlow::Continue(())
+    });
+
+    write_plain(buf, "\n");
+}
+
+fn write_logfmt_pair(buf: &mut Buffer, k: impl fmt::Display, v: impl fmt::Display) {
+    if !buf.is_empty() {
+        write_plain(buf, " ");
+    }
+
+    let v = v.to_string();
+
+    let needs_quotes = v.is_empty()
+        || v.chars()
+            .any(|c| c.is_whitespace() || c.is_control() || c == '=' || c == '"');
+
+    if !needs_quotes {
+        write_plain(buf, format_args!("{k}={v}"));
+        return;
+    }
+
+    write_plain(buf, format_args!("{k}=\""));
+
+    for c in v.chars() {
+        match c {
+            '"' => write_plain(buf, "\\\""),
+            '\\' => write_plain(buf, "\\\\"),
+            '\n' => write_plain(buf, "\\n"),
+            '\r' => write_plain(buf, "\\r"),
+            '\t' => write_plain(buf, "\\t"),
+            c => write_plain(buf, c),
+        }
+    }
+
+    write_plain(buf, "\"");
+}
+
 fn hex_slice<'a>(hex: &'a [u8], len: usize) -> impl fmt::Display + 'a {
     struct HexSlice<'a>(&'a [u8], usize);
 
@@ -405,7 +775,7 @@ struct LocalTime {
     h: u8,
     m: u8,
     s: u8,
-    ms: u16,
+    nanos: u32,
 }
 
 fn local_ts(ts: emit::Timestamp) -> Option<LocalTime> {
@@ -431,17 +801,17 @@ fn local_ts(ts: emit::Timestamp) -> Option<LocalTime> {
         .ok()?;
         let local = local.checked_to_offset(time::UtcOffset::local_offset_at(local).ok()?)?;
 
-        let (h, m, s, ms) = local.time().as_hms_milli();
+        let (h, m, s, nanos) = local.time().as_hms_nano();
 
-        Some(LocalTime { h, m, s, ms })
+        Some(LocalTime { h, m, s, nanos })
     }
 }
 
 fn write_timestamp(buf: &mut Buffer, ts: emit::Timestamp) {
-    if let Some(LocalTime { h, m, s, ms }) = local_ts(ts) {
+    if let Some(LocalTime { h, m, s, nanos }) = local_ts(ts) {
         write_plain(
             buf,
-            format_args!("{:>02}:{:>02}:{:>02}.{:>03}", h, m, s, ms),
+            format_args!("{:>02}:{:>02}:{:>02}.{:>03}", h, m, s, nanos / 1_000_000),
         );
     } else {
         write_plain(buf, format_args!("{:.0}", ts));
@@ -679,6 +1049,7 @@ mod tests {
                 user: "Rust",
                 extra: true,
             ),
+            DistStyle::Quartiles,
         );
 
         assert_eq!(
@@ -699,6 +1070,7 @@ mod tests {
                 lvl: "error",
                 err: std::io::Error::new(std::io::ErrorKind::Other, "Something went wrong"),
             ),
+            DistStyle::Quartiles,
         );
 
         assert_eq!(
@@ -707,6 +1079,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn write_log_err_chain() {
+        let mut buf = Buffer::no_color();
+
+        write_event(
+            &mut buf,
+            emit::evt!(
+                extent: emit::Timestamp::try_from_str("2024-01-01T01:02:03.000Z").unwrap(),
+                "An error",
+                lvl: "error",
+                err: emit::err::chain(&std::io::Error::other("Something went wrong")),
+            ),
+            DistStyle::Quartiles,
+        );
+
+        // A backtrace may also be written, depending on the environment
+        assert!(
+            str::from_utf8(buf.as_slice()).unwrap().starts_with(
+                "2024-01-01T01:02:03Z error emit_term tests An error\n  err: Something went wrong\n  type: std::io::error::Error\n"
+            )
+        );
+    }
+
     #[test]
     fn write_span() {
         let mut buf = Buffer::no_color();
@@ -724,6 +1119,7 @@ mod tests {
                 span_id: "00f067aa0ba902b7",
                 extra: true,
             ),
+            DistStyle::Quartiles,
         );
 
         assert_eq!(
@@ -747,6 +1143,7 @@ mod tests {
                 metric_agg: "count",
                 metric_value: 42,
             ),
+            DistStyle::Quartiles,
         );
 
         assert_eq!(
@@ -754,4 +1151,152 @@ mod tests {
             str::from_utf8(buf.as_slice()).unwrap()
         );
     }
+
+    #[test]
+    fn write_metric_dist() {
+        use emit::Props as _;
+
+        let mut distribution = emit::metric::exp::Distribution::new(2, 160);
+
+        for i in 1..=100 {
+            distribution.observe(i as f64);
+        }
+
+        let metric = emit::Metric::new(
+            emit::path!("emit_term::tests"),
+            emit::Timestamp::try_from_str("2024-01-01T01:02:03.000Z").unwrap(),
+            emit::props! {
+                metric_name: "test",
+                metric_agg: "count",
+                metric_value: distribution.count(),
+            }
+            .and_props(&distribution),
+        );
+
+        for (dist, expected) in [
+            (
+                DistStyle::Quartiles,
+                "2024-01-01T01:02:03Z metric emit_term tests count of test is 100\nQ1: 24.76805114304418, Q2: 49.53610228608835, Q3: 70.05462768008702, error: 8.64%\n",
+            ),
+            (
+                DistStyle::Percentiles,
+                "2024-01-01T01:02:03Z metric emit_term tests count of test is 100\np50: 49.53610228608835, p95: 99.07220457217667, p99: 99.07220457217667, error: 8.64%\n",
+            ),
+        ] {
+            let mut buf = Buffer::no_color();
+
+            write_event(&mut buf, emit::event::ToEvent::to_event(&metric), dist);
+
+            assert_eq!(expected, str::from_utf8(buf.as_slice()).unwrap());
+        }
+    }
+
+    #[test]
+    fn write_log_compact() {
+        let mut buf = Buffer::no_color();
+
+        write_event_compact(
+            &mut buf,
+            emit::evt!(
+                extent: emit::Timestamp::try_from_str("2024-01-01T01:02:03.000Z").unwrap(),
+                "An error",
+                lvl: "error",
+                err: std::io::Error::other("Something went wrong"),
+            ),
+        );
+
+        assert_eq!(
+            "2024-01-01T01:02:03Z error emit_term tests An error (err: Something went wrong)\n",
+            str::from_utf8(buf.as_slice()).unwrap()
+        );
+    }
+
+    #[test]
+    fn write_span_compact() {
+        let mut buf = Buffer::no_color();
+
+        write_event_compact(
+            &mut buf,
+            emit::evt!(
+                extent:
+                    emit::Timestamp::try_from_str("2024-01-01T01:02:03.000Z").unwrap()..
+                    emit::Timestamp::try_from_str("2024-01-01T01:02:04.000Z").unwrap(),
+                "Hello, {user}",
+                user: "Rust",
+                evt_kind: "span",
+                trace_id: "4bf92f3577b34da6a3ce929d0e0e4736",
+                span_id: "00f067aa0ba902b7",
+            ),
+        );
+
+        assert_eq!(
+            "2024-01-01T01:02:04Z 1000ms span emit_term tests Hello, Rust\n",
+            str::from_utf8(buf.as_slice()).unwrap()
+        );
+    }
+
+    #[test]
+    fn write_log_json() {
+        let mut buf = Buffer::no_color();
+
+        write_event_json(
+            &mut buf,
+            emit::evt!(
+                extent: emit::Timestamp::try_from_str("2024-01-01T01:02:03.000Z").unwrap(),
+                "Hello, {user}",
+                user: "Rust",
+                extra: true,
+            ),
+        );
+
+        assert_eq!(
+            "{\"ts\":\"2024-01-01T01:02:03.000000000Z\",\"mdl\":\"emit_term::tests\",\"msg\":\"Hello, Rust\",\"tpl\":\"Hello, {user}\",\"extra\":true,\"user\":\"Rust\"}\n",
+            str::from_utf8(buf.as_slice()).unwrap()
+        );
+    }
+
+    #[test]
+    fn write_span_json() {
+        let mut buf = Buffer::no_color();
+
+        write_event_json(
+            &mut buf,
+            emit::evt!(
+                extent:
+                    emit::Timestamp::try_from_str("2024-01-01T01:02:03.000Z").unwrap()..
+                    emit::Timestamp::try_from_str("2024-01-01T01:02:04.000Z").unwrap(),
+                "Hello",
+                evt_kind: "span",
+                span_id: "00f067aa0ba902b7",
+            ),
+        );
+
+        assert_eq!(
+            "{\"ts_start\":\"2024-01-01T01:02:03.000000000Z\",\"ts\":\"2024-01-01T01:02:04.000000000Z\",\"mdl\":\"emit_term::tests\",\"msg\":\"Hello\",\"tpl\":\"Hello\",\"evt_kind\":\"span\",\"span_id\":\"00f067aa0ba902b7\"}\n",
+            str::from_utf8(buf.as_slice()).unwrap()
+        );
+    }
+
+    #[test]
+    fn write_log_logfmt() {
+        let mut buf = Buffer::no_color();
+
+        write_event_logfmt(
+            &mut buf,
+            emit::evt!(
+                extent: emit::Timestamp::try_from_str("2024-01-01T01:02:03.000Z").unwrap(),
+                "Hello, {user}",
+                user: "Rust",
+                lvl: "info",
+                quoted: "a \"b\"\nc",
+                empty: "",
+                n: 42,
+            ),
+        );
+
+        assert_eq!(
+            "ts=2024-01-01T01:02:03.000000000Z mdl=emit_term::tests msg=\"Hello, Rust\" empty=\"\" lvl=info n=42 quoted=\"a \\\"b\\\"\\nc\" user=Rust\n",
+            str::from_utf8(buf.as_slice()).unwrap()
+        );
+    }
 }
@@ -0,0 +1,703 @@
+use std::{fmt, ops::ControlFlow, str::FromStr};
+
+use emit::{
+    Props as _,
+    well_known::{KEY_ERR, KEY_EVT_KIND, KEY_LVL, KEY_SPAN_ID, KEY_TRACE_ID},
+};
+use termcolor::{Buffer, Color};
+
+use crate::{
+    FIELD, FriendlyDuration, KIND, LocalTime, MDL_FIRST, MDL_LAST, NUMBER, TEXT, TokenWriter,
+    friendly_duration, hex_slice, level_color, local_ts, span_id_color, trace_id_color,
+    try_write_fg, write_fg, write_plain,
+};
+
+/**
+A user-defined layout for each line written to the console.
+
+Line templates are parsed once from a string, like `"{ts:local} {lvl:pad5} {mdl} {msg} {props}"`, and then used to write each event. Pass a line template to [`crate::Stdout::template`] or [`crate::Stderr::template`] to use it.
+
+Text outside of holes is written as-is. Use `{{` and `}}` to write literal `{` and `}` characters.
+
+# Holes
+
+The following holes are supported:
+
+- `{ts}`: The timestamp of the event. For spans, this is when the span completed.
+- `{ts_start}`: The timestamp the event started at, if it's a span.
+- `{dur}`: The duration of the event, if it's a span.
+- `{lvl}`: The level of the event.
+- `{kind}`: The kind of the event, like `span` or `metric`.
+- `{mdl}`: The module the event was emitted from. Leave this hole out to hide modules.
+- `{msg}`: The rendered message of the event.
+- `{err}`: The error attached to the event.
+- `{trace_id}`: The trace id of the event.
+- `{span_id}`: The span id of the event.
+- `{props}`: Any properties of the event that aren't used by its message or by other holes in the line template, as `key=value` pairs.
+
+If an event doesn't have a value for a hole, like `{lvl}` on an event without a level, then nothing is written for it, along with any text immediately before it. If the line starts with holes that have no value, then the text immediately after them is skipped too.
+
+# Options
+
+Holes accept a comma-separated list of options after a `:`, like `{ts:utc,ms}`:
+
+- `local` (default), `utc` (on `ts` and `ts_start`): Whether to write the time of day in the local timezone, or a full RFC3339 timestamp in UTC. If the local timezone can't be determined then UTC is used.
+- `s`, `ms`, `us`, `ns` (on `ts` and `ts_start`): The precision of the fractional seconds to write. The default is `ms` for local timestamps and `s` for UTC ones.
+- `full`, `first`, `last` (on `mdl`): Write the full module path, or only its first or last segment. By default, the first and last segments are written.
+- `full` (on `trace_id` and `span_id`): Write the full id instead of a short prefix of it.
+- `max{N}` (on `err` and `props`): Truncate values longer than `N` characters.
+- `pad{N}` (on `ts`, `ts_start`, `dur`, `lvl`, `kind`, `mdl`, and `err`): Pad the value with trailing spaces to at least `N` characters.
+
+The colors used for each hole are the same as the default format.
+*/
+#[derive(Debug, Clone)]
+pub struct LineTemplate {
+    parts: Vec<LinePart>,
+    // Properties that are written by holes other than `{props}`
+    written_keys: Vec<&'static str>,
+    has_msg: bool,
+}
+
+#[derive(Debug, Clone)]
+enum LinePart {
+    Text(String),
+    Hole(Hole),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Hole {
+    kind: HoleKind,
+    pad: Option<usize>,
+    max: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum HoleKind {
+    Ts(TsFormat),
+    TsStart(TsFormat),
+    Dur,
+    Lvl,
+    Kind,
+    Mdl(MdlFormat),
+    Msg,
+    Err,
+    TraceId { full: bool },
+    SpanId { full: bool },
+    Props,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct TsFormat {
+    utc: bool,
+    precision: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum MdlFormat {
+    FirstLast,
+    Full,
+    First,
+    Last,
+}
+
+/**
+An error attempting to parse a [`LineTemplate`].
+*/
+#[derive(Debug)]
+pub struct LineTemplateError {
+    pos: usize,
+    msg: String,
+}
+
+impl LineTemplateError {
+    fn new(pos: usize, msg: impl Into<String>) -> Self {
+        LineTemplateError {
+            pos,
+            msg: msg.into(),
+        }
+    }
+
+    /**
+    The byte offset in the input where the error was detected.
+    */
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+}
+
+impl fmt::Display for LineTemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.msg, self.pos)
+    }
+}
+
+impl std::error::Error for LineTemplateError {}
+
+impl FromStr for LineTemplate {
+    type Err = LineTemplateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        LineTemplate::parse(s)
+    }
+}
+
+impl LineTemplate {
+    /**
+    Parse a line template from a string.
+
+    See the [`LineTemplate`] docs for details on the syntax.
+    */
+    pub fn parse(template: &str) -> Result<Self, LineTemplateError> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+
+        let mut pos = 0;
+        while let Some(c) = template[pos..].chars().next() {
+            match c {
+                '{' if template[pos + 1..].starts_with('{') => {
+                    text.push('{');
+                    pos += 2;
+                }
+                '}' if template[pos + 1..].starts_with('}') => {
+                    text.push('}');
+                    pos += 2;
+                }
+                '}' => {
+                    return Err(LineTemplateError::new(
+                        pos,
+                        "unexpected `}`; use `}}` to write a literal `}`",
+                    ));
+                }
+                '{' => {
+                    let start = pos + 1;
+
+                    let Some(len) = template[start..].find(['{', '}']) else {
+                        return Err(LineTemplateError::new(pos, "unterminated hole"));
+                    };
+
+                    let end = start + len;
+
+                    if template[end..].starts_with('{') {
+                        return Err(LineTemplateError::new(
+                            end,
+                            "unexpected `{` in hole; holes can't be nested",
+                        ));
+                    }
+
+                    let hole = parse_hole(&template[start..end], start)?;
+
+                    if !text.is_empty() {
+                        parts.push(LinePart::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(LinePart::Hole(hole));
+
+                    pos = end + 1;
+                }
+                c => {
+                    text.push(c);
+                    pos += c.len_utf8();
+                }
+            }
+        }
+
+        if !text.is_empty() {
+            parts.push(LinePart::Text(text));
+        }
+
+        let mut written_keys = Vec::new();
+        let mut has_msg = false;
+        for part in &parts {
+            let LinePart::Hole(hole) = part else {
+                continue;
+            };
+
+            match hole.kind {
+                HoleKind::Lvl => written_keys.push(KEY_LVL),
+                HoleKind::Kind => written_keys.push(KEY_EVT_KIND),
+                HoleKind::Err => written_keys.push(KEY_ERR),
+                HoleKind::TraceId { .. } => written_keys.push(KEY_TRACE_ID),
+                HoleKind::SpanId { .. } => written_keys.push(KEY_SPAN_ID),
+                HoleKind::Msg => has_msg = true,
+                _ => (),
+            }
+        }
+
+        Ok(LineTemplate {
+            parts,
+            written_keys,
+            has_msg,
+        })
+    }
+
+    pub(crate) fn write_event(
+        &self,
+        buf: &mut Buffer,
+        evt: &emit::event::Event<impl emit::props::Props>,
+    ) {
+        // Text is only written if the hole that follows it is
+        let mut pending = None;
+        // Text following skipped holes at the start of the line is also skipped
+        let mut written = false;
+        let mut skip_leading = false;
+
+        for part in &self.parts {
+            match part {
+                LinePart::Text(text) => pending = Some(&**text),
+                LinePart::Hole(hole) => {
+                    if !self.has_value(hole, evt) {
+                        pending = None;
+                        skip_leading = !written;
+                        continue;
+                    }
+
+                    if let Some(text) = pending.take()
+                        && !skip_leading
+                    {
+                        write_plain(buf, text);
+                    }
+                    written = true;
+                    skip_leading = false;
+
+                    self.write_hole(buf, hole, evt);
+                }
+            }
+        }
+
+        if let Some(text) = pending {
+            write_plain(buf, text);
+        }
+
+        write_plain(buf, "\n");
+    }
+
+    fn has_value(&self, hole: &Hole, evt: &emit::event::Event<impl emit::props::Props>) -> bool {
+        match hole.kind {
+            HoleKind::Ts(_) => evt.extent().is_some(),
+            HoleKind::TsStart(_) => evt.extent().and_then(|extent| extent.as_range()).is_some(),
+            HoleKind::Dur => evt.extent().and_then(|extent| extent.len()).is_some(),
+            HoleKind::Lvl => evt.props().pull::<emit::Level, _>(KEY_LVL).is_some(),
+            HoleKind::Kind => evt.props().get(KEY_EVT_KIND).is_some(),
+            HoleKind::Mdl(_) => evt.mdl().segments().next().is_some(),
+            HoleKind::Msg => true,
+            HoleKind::Err => evt.props().get(KEY_ERR).is_some(),
+            HoleKind::TraceId { .. } => {
+                evt.props().pull::<emit::TraceId, _>(KEY_TRACE_ID).is_some()
+            }
+            HoleKind::SpanId { .. } => evt.props().pull::<emit::SpanId, _>(KEY_SPAN_ID).is_some(),
+            HoleKind::Props => evt
+                .props()
+                .dedup()
+                .for_each(|k, _| {
+                    if self.is_prop(evt, k.get()) {
+                        ControlFlow::Break(())
+                    } else {
+                        ControlFlow::Continue(())
+                    }
+                })
+                .is_break(),
+        }
+    }
+
+    fn is_prop(&self, evt: &emit::event::Event<impl emit::props::Props>, key: &str) -> bool {
+        if self.written_keys.contains(&key) {
+            return false;
+        }
+
+        // Properties used as holes in the message don't need to be written again
+        if self.has_msg
+            && evt
+                .tpl()
+                .parts()
+                .any(|part| part.label().map(|label| label.get()) == Some(key))
+        {
+            return false;
+        }
+
+        true
+    }
+
+    fn write_hole(
+        &self,
+        buf: &mut Buffer,
+        hole: &Hole,
+        evt: &emit::event::Event<impl emit::props::Props>,
+    ) {
+        let lvl = evt
+            .props()
+            .pull::<emit::Level, _>(KEY_LVL)
+            .and_then(|level| level_color(&level))
+            .map(Color::Ansi256);
+
+        match hole.kind {
+            HoleKind::Ts(format) => {
+                if let Some(extent) = evt.extent() {
+                    write_padded(
+                        buf,
+                        FormatTimestamp(*extent.as_point(), format),
+                        None,
+                        hole.pad,
+                    );
+                }
+            }
+            HoleKind::TsStart(format) => {
+                if let Some(range) = evt.extent().and_then(|extent| extent.as_range()) {
+                    write_padded(buf, FormatTimestamp(range.start, format), None, hole.pad);
+                }
+            }
+            HoleKind::Dur => {
+                if let Some(len) = evt.extent().and_then(|extent| extent.len()) {
+                    let FriendlyDuration { value, unit } = friendly_duration(len);
+
+                    write_fg(buf, value, NUMBER);
+                    write_fg(buf, unit, TEXT);
+
+                    write_pad(
+                        buf,
+                        value.to_string().chars().count() + unit.chars().count(),
+                        hole.pad,
+                    );
+                }
+            }
+            HoleKind::Lvl => {
+                if let Some(level) = evt.props().pull::<emit::Level, _>(KEY_LVL) {
+                    write_padded(buf, level, lvl, hole.pad);
+                }
+            }
+            HoleKind::Kind => {
+                if let Some(kind) = evt.props().get(KEY_EVT_KIND) {
+                    write_padded(buf, kind, Some(KIND), hole.pad);
+                }
+            }
+            HoleKind::Mdl(format) => {
+                let mut segments = evt.mdl().segments();
+
+                let (first, last) = (segments.next(), segments.last());
+
+                match (format, first, last) {
+                    (MdlFormat::Full, _, _) => {
+                        write_padded(buf, evt.mdl(), Some(MDL_FIRST), hole.pad)
+                    }
+                    (MdlFormat::First, Some(first), _) | (MdlFormat::Last, Some(first), None) => {
+                        write_padded(buf, first, Some(MDL_FIRST), hole.pad)
+                    }
+                    (MdlFormat::Last, Some(_), Some(last)) => {
+                        write_padded(buf, last, Some(MDL_LAST), hole.pad)
+                    }
+                    (MdlFormat::FirstLast, Some(first), Some(last)) => {
+                        write_fg(buf, &first, MDL_FIRST);
+                        write_plain(buf, " ");
+                        write_fg(buf, &last, MDL_LAST);
+
+                        write_pad(
+                            buf,
+                            first.get().chars().count() + 1 + last.get().chars().count(),
+                            hole.pad,
+                        );
+                    }
+                    (MdlFormat::FirstLast, Some(first), None) => {
+                        write_padded(buf, first, Some(MDL_FIRST), hole.pad)
+                    }
+                    (_, None, _) => (),
+                }
+            }
+            HoleKind::Msg => {
+                let _ = evt.msg().write(TokenWriter { buf });
+            }
+            HoleKind::Err => {
+                if let Some(err) = evt.props().get(KEY_ERR) {
+                    write_padded(buf, Truncate(err, hole.max), lvl, hole.pad);
+                }
+            }
+            HoleKind::TraceId { full } => {
+                if let Some(trace_id) = evt.props().pull::<emit::TraceId, _>(KEY_TRACE_ID) {
+                    let hex = trace_id.to_hex();
+
+                    write_fg(buf, "▓", Color::Ansi256(trace_id_color(&trace_id)));
+                    write_plain(buf, " ");
+                    write_plain(buf, hex_slice(&hex, if full { hex.len() } else { 6 }));
+                }
+            }
+            HoleKind::SpanId { full } => {
+                if let Some(span_id) = evt.props().pull::<emit::SpanId, _>(KEY_SPAN_ID) {
+                    let hex = span_id.to_hex();
+
+                    write_fg(buf, "▓", Color::Ansi256(span_id_color(&span_id)));
+                    write_plain(buf, " ");
+                    write_plain(buf, hex_slice(&hex, if full { hex.len() } else { 4 }));
+                }
+            }
+            HoleKind::Props => {
+                let mut first = true;
+
+                let _ = evt.props().dedup().for_each(|k, v| {
+                    if !self.is_prop(evt, k.get()) {
+                        return ControlFlow::Continue(());
+                    }
+
+                    if !first {
+                        write_plain(buf, " ");
+                    }
+                    first = false;
+
+                    write_fg(buf, k, FIELD);
+                    write_plain(buf, "=");
+
+                    match hole.max {
+                        Some(max) if v.to_string().chars().count() > max => {
+                            write_fg(buf, Truncate(v, Some(max)), TEXT);
+                        }
+                        _ => {
+                            let _ = sval_fmt::stream_to_token_write(&mut TokenWriter { buf }, v);
+                        }
+                    }
+
+                    ControlFlow::Continue(())
+                });
+            }
+        }
+    }
+}
+
+fn parse_hole(hole: &str, offset: usize) -> Result<Hole, LineTemplateError> {
+    let (name, opts) = match hole.split_once(':') {
+        Some((name, opts)) => (name, Some(opts)),
+        None => (hole, None),
+    };
+
+    let mut kind = match name {
+        "ts" => HoleKind::Ts(Default::default()),
+        "ts_start" => HoleKind::TsStart(Default::default()),
+        "dur" => HoleKind::Dur,
+        "lvl" => HoleKind::Lvl,
+        "kind" => HoleKind::Kind,
+        "mdl" => HoleKind::Mdl(MdlFormat::FirstLast),
+        "msg" => HoleKind::Msg,
+        "err" => HoleKind::Err,
+        "trace_id" => HoleKind::TraceId { full: false },
+        "span_id" => HoleKind::SpanId { full: false },
+        "props" => HoleKind::Props,
+        name => {
+            return Err(LineTemplateError::new(
+                offset,
+                format!("unknown hole `{name}`"),
+            ));
+        }
+    };
+
+    let mut pad = None;
+    let mut max = None;
+
+    let mut opt_offset = offset + name.len() + 1;
+    for opt in opts.into_iter().flat_map(|opts| opts.split(',')) {
+        let unsupported = || {
+            LineTemplateError::new(
+                opt_offset,
+                format!("unsupported option `{opt}` for `{name}`"),
+            )
+        };
+
+        match (&mut kind, opt) {
+            (HoleKind::Ts(format) | HoleKind::TsStart(format), "local" | "utc") => {
+                format.utc = opt == "utc";
+            }
+            (HoleKind::Ts(format) | HoleKind::TsStart(format), "s" | "ms" | "us" | "ns") => {
+                format.precision = Some(match opt {
+                    "s" => 0,
+                    "ms" => 3,
+                    "us" => 6,
+                    _ => 9,
+                });
+            }
+            (HoleKind::Mdl(format), "full" | "first" | "last") => {
+                *format = match opt {
+                    "full" => MdlFormat::Full,
+                    "first" => MdlFormat::First,
+                    _ => MdlFormat::Last,
+                };
+            }
+            (HoleKind::TraceId { full } | HoleKind::SpanId { full }, "full") => {
+                *full = true;
+            }
+            (
+                HoleKind::Ts(_)
+                | HoleKind::TsStart(_)
+                | HoleKind::Dur
+                | HoleKind::Lvl
+                | HoleKind::Kind
+                | HoleKind::Mdl(_)
+                | HoleKind::Err,
+                opt,
+            ) if opt.starts_with("pad") => {
+                pad = Some(parse_opt_usize(&opt[3..]).ok_or_else(unsupported)?);
+            }
+            (HoleKind::Err | HoleKind::Props, opt) if opt.starts_with("max") => {
+                max = Some(parse_opt_usize(&opt[3..]).ok_or_else(unsupported)?);
+            }
+            _ => return Err(unsupported()),
+        }
+
+        opt_offset += opt.len() + 1;
+    }
+
+    Ok(Hole { kind, pad, max })
+}
+
+fn parse_opt_usize(opt: &str) -> Option<usize> {
+    if opt.is_empty() || !opt.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    opt.parse().ok()
+}
+
+fn write_padded(buf: &mut Buffer, v: impl fmt::Display, color: Option<Color>, pad: Option<usize>) {
+    match pad {
+        Some(pad) => try_write_fg(buf, format_args!("{:<pad$}", v.to_string()), color),
+        None => try_write_fg(buf, v, color),
+    }
+}
+
+fn write_pad(buf: &mut Buffer, written: usize, pad: Option<usize>) {
+    if let Some(pad) = pad {
+        for _ in written..pad {
+            write_plain(buf, " ");
+        }
+    }
+}
+
+struct FormatTimestamp(emit::Timestamp, TsFormat);
+
+impl fmt::Display for FormatTimestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let FormatTimestamp(ts, TsFormat { utc, precision }) = *self;
+
+        if !utc && let Some(LocalTime { h, m, s, nanos }) = local_ts(ts) {
+            write!(f, "{:>02}:{:>02}:{:>02}", h, m, s)?;
+
+            let precision = precision.unwrap_or(3);
+
+            if precision > 0 {
+                let frac = nanos / 10u32.pow(9 - precision as u32);
+
+                write!(f, ".{:>0precision$}", frac)?;
+            }
+
+            return Ok(());
+        }
+
+        write!(f, "{:.*}", precision.unwrap_or(0), ts)
+    }
+}
+
+struct Truncate<V>(V, Option<usize>);
+
+impl<V: fmt::Display> fmt::Display for Truncate<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(max) = self.1 else {
+            return fmt::Display::fmt(&self.0, f);
+        };
+
+        let v = self.0.to_string();
+
+        match v.char_indices().nth(max) {
+            Some((i, _)) => write!(f, "{}…", &v[..i]),
+            None => f.write_str(&v),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::str;
+
+    fn write(template: &str, evt: emit::event::Event<impl emit::props::Props>) -> String {
+        let mut buf = Buffer::no_color();
+
+        LineTemplate::parse(template)
+            .unwrap()
+            .write_event(&mut buf, &evt);
+
+        str::from_utf8(buf.as_slice()).unwrap().to_owned()
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for (template, pos) in [
+            ("{ts", 0),
+            ("ts}", 2),
+            ("{ts {lvl}}", 4),
+            ("{unknown}", 1),
+            ("{ts:pad}", 4),
+            ("{ts:ms,bad}", 7),
+            ("{msg:pad5}", 5),
+            ("{lvl:max5}", 5),
+        ] {
+            let err = LineTemplate::parse(template).unwrap_err();
+
+            assert_eq!(pos, err.pos(), "{template}: {err}");
+        }
+    }
+
+    #[test]
+    fn write_log() {
+        assert_eq!(
+            "[2024-01-01T01:02:03.000Z info ] emit_term::template::tests Hello, Rust {extra=true}\n",
+            write(
+                "[{ts:utc,ms} {lvl:pad5}] {mdl:full} {msg} {{{props}}}",
+                emit::evt!(
+                    extent: emit::Timestamp::try_from_str("2024-01-01T01:02:03.000Z").unwrap(),
+                    "Hello, {user}",
+                    user: "Rust",
+                    lvl: "info",
+                    extra: true,
+                ),
+            )
+        );
+    }
+
+    #[test]
+    fn write_missing_values() {
+        assert_eq!(
+            "emit_term tests Hello\n",
+            write("{lvl} {mdl} {msg} {props}", emit::evt!("Hello"),)
+        );
+    }
+
+    #[test]
+    fn write_span() {
+        assert_eq!(
+            "▓ 4bf92f ▓ 00f0 2024-01-01T01:02:03Z 1000ms span tests Hello, Rust\n",
+            write(
+                "{trace_id} {span_id} {ts_start} {dur} {kind} {mdl:last} {msg}",
+                emit::evt!(
+                    extent:
+                        emit::Timestamp::try_from_str("2024-01-01T01:02:03.000Z").unwrap()..
+                        emit::Timestamp::try_from_str("2024-01-01T01:02:04.000Z").unwrap(),
+                    "Hello, {user}",
+                    user: "Rust",
+                    evt_kind: "span",
+                    trace_id: "4bf92f3577b34da6a3ce929d0e0e4736",
+                    span_id: "00f067aa0ba902b7",
+                ),
+            )
+        );
+    }
+
+    #[test]
+    fn write_truncated() {
+        assert_eq!(
+            "An error err: Somethin… long=abcde… short=abc\n",
+            write(
+                "{msg} err: {err:max8} {props:max5}",
+                emit::evt!(
+                    "An error",
+                    err: std::io::Error::other("Something went wrong"),
+                    long: "abcdefghij",
+                    short: "abc",
+                ),
+            )
+        );
+    }
+}
@@ -0,0 +1,222 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use emit::well_known::{KEY_EVT_KIND, KEY_SPAN_ID, KEY_SPAN_PARENT};
+use termcolor::{Buffer, Color};
+
+use crate::{hex_slice, span_id_color, write_event_compact, write_fg, write_plain};
+
+// The maximum number of spans to track before assuming
+// the oldest isn't being completed and forgetting it
+const MAX_OPEN_SPANS: usize = 1024;
+
+/*
+Spans are only emitted when they complete, so the tree is rebuilt from
+the `span_id` and `span_parent` of each event instead. Spans are considered
+open from the first time an event is seen in them until they complete.
+*/
+#[derive(Debug, Default)]
+pub(crate) struct Tree {
+    open: Mutex<Open>,
+}
+
+#[derive(Debug, Default)]
+struct Open {
+    // The depth of each open span, along with the order it was opened in
+    spans: HashMap<emit::SpanId, (usize, u64)>,
+    opened: u64,
+}
+
+impl Tree {
+    pub(crate) fn write_event(
+        &self,
+        buf: &mut Buffer,
+        evt: emit::event::Event<impl emit::props::Props>,
+    ) {
+        let mut open = self.open.lock().unwrap_or_else(|e| e.into_inner());
+
+        let span_id = evt.props().pull::<emit::SpanId, _>(KEY_SPAN_ID);
+        let span_parent = evt.props().pull::<emit::SpanId, _>(KEY_SPAN_PARENT);
+
+        match span_id {
+            // A span completing
+            Some(span_id)
+                if evt.props().pull::<emit::Kind, _>(KEY_EVT_KIND) == Some(emit::Kind::Span) =>
+            {
+                match open.spans.remove(&span_id) {
+                    Some((depth, _)) => {
+                        write_indent(buf, depth);
+                        write_plain(buf, "└ ");
+                    }
+                    // If the span was never opened then it had no events in it
+                    None => {
+                        let depth = span_parent
+                            .map(|span_parent| open.open_span(buf, span_parent, None) + 1)
+                            .unwrap_or(0);
+
+                        write_indent(buf, depth);
+                        write_plain(buf, "─ ");
+                    }
+                }
+            }
+            // An event within a span
+            Some(span_id) => {
+                let depth = open.open_span(buf, span_id, span_parent);
+
+                write_indent(buf, depth + 1);
+            }
+            // An event outside of any span
+            None => (),
+        }
+
+        write_event_compact(buf, evt);
+    }
+}
+
+impl Open {
+    fn open_span(
+        &mut self,
+        buf: &mut Buffer,
+        span_id: emit::SpanId,
+        span_parent: Option<emit::SpanId>,
+    ) -> usize {
+        if let Some((depth, _)) = self.spans.get(&span_id) {
+            return *depth;
+        }
+
+        // If the parent hasn't been seen then it's opened too
+        // Any of its own parents that haven't been seen can't be known
+        let depth = span_parent
+            .map(|span_parent| self.open_span(buf, span_parent, None) + 1)
+            .unwrap_or(0);
+
+        write_indent(buf, depth);
+        write_plain(buf, "┌ ");
+        write_fg(buf, "▓", Color::Ansi256(span_id_color(&span_id)));
+        write_plain(buf, " ");
+        write_plain(buf, hex_slice(&span_id.to_hex(), 4));
+        write_plain(buf, "\n");
+
+        if self.spans.len() >= MAX_OPEN_SPANS {
+            self.evict_oldest();
+        }
+
+        self.spans.insert(span_id, (depth, self.opened));
+        self.opened += 1;
+
+        depth
+    }
+
+    fn evict_oldest(&mut self) {
+        let oldest = self
+            .spans
+            .iter()
+            .min_by_key(|(_, (_, opened))| *opened)
+            .map(|(span_id, _)| *span_id);
+
+        if let Some(oldest) = oldest {
+            self.spans.remove(&oldest);
+        }
+    }
+}
+
+fn write_indent(buf: &mut Buffer, depth: usize) {
+    for _ in 0..depth {
+        write_plain(buf, "│ ");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::str;
+
+    #[test]
+    fn write_nested() {
+        let tree = Tree::default();
+        let mut buf = Buffer::no_color();
+
+        tree.write_event(&mut buf, emit::evt!("outside"));
+        tree.write_event(&mut buf, emit::evt!("in a", span_id: "a1a1000000000000"));
+        tree.write_event(
+            &mut buf,
+            emit::evt!(
+                "in b",
+                span_id: "b1b1000000000000",
+                span_parent: "a1a1000000000000",
+            ),
+        );
+        tree.write_event(
+            &mut buf,
+            emit::evt!(
+                "c",
+                evt_kind: "span",
+                span_id: "c1c1000000000000",
+                span_parent: "b1b1000000000000",
+            ),
+        );
+        tree.write_event(
+            &mut buf,
+            emit::evt!(
+                "b",
+                evt_kind: "span",
+                span_id: "b1b1000000000000",
+                span_parent: "a1a1000000000000",
+            ),
+        );
+        tree.write_event(
+            &mut buf,
+            emit::evt!("in a again", span_id: "a1a1000000000000"),
+        );
+        tree.write_event(
+            &mut buf,
+            emit::evt!("a", evt_kind: "span", span_id: "a1a1000000000000"),
+        );
+
+        assert_eq!(
+            "emit_term tests outside\n┌ ▓ a1a1\n│ emit_term tests in a\n│ ┌ ▓ b1b1\n│ │ emit_term tests in b\n│ │ ─ span emit_term tests c\n│ └ span emit_term tests b\n│ emit_term tests in a again\n└ span emit_term tests a\n",
+            str::from_utf8(buf.as_slice()).unwrap()
+        );
+
+        assert!(tree.open.lock().unwrap().spans.is_empty());
+    }
+
+    #[test]
+    fn write_unseen_parent() {
+        let tree = Tree::default();
+        let mut buf = Buffer::no_color();
+
+        tree.write_event(
+            &mut buf,
+            emit::evt!(
+                "in b",
+                span_id: "b1b1000000000000",
+                span_parent: "a1a1000000000000",
+            ),
+        );
+
+        assert_eq!(
+            "┌ ▓ a1a1\n│ ┌ ▓ b1b1\n│ │ emit_term tests in b\n",
+            str::from_utf8(buf.as_slice()).unwrap()
+        );
+    }
+
+    #[test]
+    fn evict_oldest_open_span() {
+        let tree = Tree::default();
+        let mut buf = Buffer::no_color();
+
+        for i in 0..MAX_OPEN_SPANS as u64 + 1 {
+            tree.write_event(
+                &mut buf,
+                emit::evt!("in span", span_id: emit::SpanId::from_u64(i + 1).unwrap()),
+            );
+        }
+
+        let open = tree.open.lock().unwrap();
+
+        assert_eq!(MAX_OPEN_SPANS, open.spans.len());
+        assert!(!open.spans.contains_key(&emit::SpanId::from_u64(1).unwrap()));
+        assert!(open.spans.contains_key(&emit::SpanId::from_u64(2).unwrap()));
+    }
+}
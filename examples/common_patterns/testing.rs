@@ -30,9 +30,7 @@ fn setup() -> Option<impl Drop> {
     // Set a panic hook so the location of the panic will also be captured
     // We only need to do this once
     if rt.is_some() {
-        std::panic::set_hook(Box::new(|payload| {
-            emit::error!("panic detected", #[emit::as_display] err: payload);
-        }));
+        emit::panic::install_hook();
     }
 
     rt
@@ -84,7 +84,7 @@ pub fn value_with_hook(
     };
 
     quote_spanned!(expr.span()=> #[allow(unused_imports)] {
-        use emit::__private::{__PrivateCaptureHook as _, __PrivateOptionalCaptureHook as _, __PrivateOptionalHook as _, __PrivateNullableCaptureHook as _, __PrivateNullableHook as _, __PrivateInterpolatedHook as _, __PrivateKeyExternalHook as _};
+        use emit::__private::{__PrivateCaptureHook as _, __PrivateSensitiveCaptureHook as _, __PrivateOptionalCaptureHook as _, __PrivateOptionalHook as _, __PrivateNullableCaptureHook as _, __PrivateNullableHook as _, __PrivateInterpolatedHook as _, __PrivateKeyExternalHook as _};
         (#expr).#fn_name().__private_key_external() #interpolated_expr #captured_expr
     })
 }
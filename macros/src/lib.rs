@@ -202,6 +202,33 @@ fn hooks() -> HashMap<&'static str, fn(TokenStream, TokenStream) -> syn::Result<
         }) as fn(TokenStream, TokenStream) -> syn::Result<TokenStream>,
     );
 
+    map.insert(
+        "sensitive",
+        (|args: TokenStream, expr: TokenStream| {
+            #[cfg(feature = "std")]
+            {
+                capture_as(
+                    "sensitive",
+                    args,
+                    expr,
+                    quote!(__private_capture_as_sensitive),
+                    quote!(__private_capture_as_sensitive),
+                )
+            }
+            #[cfg(not(feature = "std"))]
+            {
+                use syn::spanned::Spanned;
+
+                let _ = args;
+
+                Err(syn::Error::new(
+                    expr.span(),
+                    "capturing sensitive values is only possible when the `std` Cargo feature is enabled",
+                ))
+            }
+        }) as fn(TokenStream, TokenStream) -> syn::Result<TokenStream>,
+    );
+
     map
 }
 
@@ -1083,6 +1110,28 @@ pub fn as_error(
         .unwrap_or_compile_error()
 }
 
+/**
+Capture a property as sensitive, so it can be redacted by `emit::redact::Redact::sensitive`.
+
+The value is captured using its `ToValue` implementation, and converted into an owned `emit::redact::Sensitive`. Sensitive values are formatted normally unless they're redacted.
+
+# Syntax
+
+This macro doesn't accept any arguments.
+
+# Applicable to
+
+This attribute can be applied to properties.
+*/
+#[proc_macro_attribute]
+pub fn sensitive(
+    args: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    (hook::get("sensitive").unwrap())(TokenStream::from(args), TokenStream::from(item))
+        .unwrap_or_compile_error()
+}
+
 fn capture_as(
     name: &'static str,
     args: TokenStream,
@@ -45,6 +45,33 @@ impl KeyValue {
         self.fv.span()
     }
 
+    /**
+    Whether the value is captured with the `#[emit::sensitive]` attribute.
+    */
+    fn is_sensitive(&self) -> bool {
+        self.attrs.iter().any(|attr| {
+            let path = attr.path();
+
+            path.segments.last().map(|name| name.ident == "sensitive") == Some(true)
+                && (path.segments.len() == 1 || path.segments[0].ident == "emit")
+        })
+    }
+
+    /**
+    The expression to bind the value from.
+
+    Sensitive values are wrapped in an owned `emit::redact::Sensitive` here, so that's what their hook captures.
+    */
+    fn value_expr_tokens(&self) -> TokenStream {
+        let expr = &self.fv.expr;
+
+        if self.is_sensitive() {
+            quote_spanned!(self.span()=> emit::__private::__private_sensitive(&(#expr)))
+        } else {
+            quote!(#expr)
+        }
+    }
+
     pub fn hole_tokens(&self) -> Result<TokenStream, syn::Error> {
         let label = self.fv.key_ident()?;
         let attrs = &self.attrs;
@@ -78,7 +105,11 @@ impl Props {
             // `match`. In the 2024 edition, that doesn't happen anymore. So to keep the semantics that you can capture a value in scope
             // by reference, and supply temporaries inline, we check whether the field value is a local like `x: a.b.c` and take a reference
             // inside the `match expr`, or a complex expression like `x: a.b()`, where it's taken by value in the `match expr`.
-            let (kv_match_input_tokens, kv_match_bound_tokens) = if kv.fv.expr.is_local_variable() {
+            //
+            // Sensitive values are always taken by value, since they're wrapped in an owned `Sensitive` before they're captured.
+            let (kv_match_input_tokens, kv_match_bound_tokens) = if kv.fv.expr.is_local_variable()
+                && !kv.is_sensitive()
+            {
                 let key_value_tokens = maybe_cfg(
                     kv.cfg_attr.as_ref(),
                     kv.span(),
@@ -107,7 +138,7 @@ impl Props {
 
                 let key_tokens =
                     capture::eval_key_with_hook(&kv.attrs, &kv.fv, kv.interpolated, kv.captured)?;
-                let value_expr = &kv.fv.expr;
+                let value_expr = kv.value_expr_tokens();
 
                 let kv_match_input_tokens = maybe_cfg(
                     kv.cfg_attr.as_ref(),
@@ -207,7 +238,7 @@ impl Props {
             struct_decl_markers.push(quote!(#input_ty));
             struct_decl_markers.push(quote!(#fn_ty));
 
-            let value = &kv.fv.expr;
+            let value = kv.value_expr_tokens();
             let value = maybe_cfg(cfg_attr, kv.span(), quote!({#value}));
 
             let_bindings.push(quote!(let #input_ident = { #value }));
@@ -0,0 +1,114 @@
+/*!
+Process metrics for `emit`.
+
+This library provides a [`Process`] type, which is an [`emit::metric::Source`] that reports metrics about the current process, like its CPU time and memory usage.
+
+# Getting started
+
+Add `emit` and `emit_process` to your `Cargo.toml`:
+
+```toml
+[dependencies.emit]
+version = "2.22.3"
+
+[dependencies.emit_process]
+version = "2.22.3"
+```
+
+Register a [`Process`] with a [`emit::metric::Reporter`], and sample it along with any other sources:
+
+```
+fn main() {
+    let rt = emit::setup()
+        .emit_to(emit::emitter::from_fn(|evt| println!("{evt:?}")))
+        .init();
+
+    let mut reporter = emit::metric::Reporter::new();
+
+    reporter.add_source(emit_process::Process::new());
+
+    // Your app code goes here
+
+    // At some regular interval, sample the reporter
+    reporter.emit_metrics(rt.emitter());
+
+    rt.blocking_flush(std::time::Duration::from_secs(5));
+}
+```
+
+# Metrics
+
+Metrics are named following the [OpenTelemetry semantic conventions for process metrics](https://opentelemetry.io/docs/specs/semconv/system/process-metrics/):
+
+| `metric_name` | `metric_agg` | `metric_unit` | Description |
+| ------------- | ------------ | ------------- | ----------- |
+| `process.cpu.time` | `count` | `s` | CPU time spent by the process, with a `cpu.mode` of `user` or `system`. |
+| `process.memory.usage` | `sum` | `By` | The resident set size of the process. |
+| `process.memory.virtual` | `sum` | `By` | The virtual memory size of the process. |
+| `process.thread.count` | `sum` | `{thread}` | The number of threads in the process. |
+| `process.unix.file_descriptor.count` | `sum` | `{file_descriptor}` | The number of file descriptors the process has open. |
+| `process.context_switches` | `count` | `{context_switch}` | The number of times the process has been context switched, with a `process.context_switch.type` of `voluntary` or `involuntary`. |
+| `process.uptime` | `last` | `s` | The time since the process started. |
+
+All metrics are cumulative, so their extent is a point at the time they were sampled, based on [`emit::metric::Sampler::sampled_at`].
+
+# Platform support
+
+Metrics are read from `/proc/self/stat`, `/proc/self/status`, `/proc/self/fd`, and `/proc/uptime`, so are only available on Linux. On other platforms, [`Process`] doesn't report any metrics. If a file can't be read then any metrics derived from it are skipped.
+*/
+
+#![deny(missing_docs)]
+
+use emit::{Path, metric::Sampler};
+
+#[cfg(target_os = "linux")]
+mod linux;
+
+/**
+An [`emit::metric::Source`] for metrics about the current process.
+
+See the [crate root](crate) for details.
+*/
+pub struct Process {
+    mdl: Path<'static>,
+}
+
+impl Default for Process {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Process {
+    /**
+    Create a new source for metrics about the current process.
+
+    Metric samples use `emit_process` as their module by default.
+    */
+    pub const fn new() -> Self {
+        Process {
+            mdl: Path::new_raw("emit_process"),
+        }
+    }
+
+    /**
+    Set the module to use for metric samples.
+    */
+    pub fn with_mdl(mut self, mdl: impl Into<Path<'static>>) -> Self {
+        self.mdl = mdl.into();
+        self
+    }
+}
+
+impl emit::metric::Source for Process {
+    fn sample_metrics<S: Sampler>(&self, sampler: S) {
+        #[cfg(target_os = "linux")]
+        {
+            linux::sample_metrics(&self.mdl, &sampler);
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = sampler;
+        }
+    }
+}
@@ -0,0 +1,294 @@
+use std::fs;
+
+use emit::{
+    Path,
+    metric::{Metric, Sampler},
+    well_known::{METRIC_AGG_COUNT, METRIC_AGG_LAST, METRIC_AGG_SUM},
+};
+
+pub(crate) fn sample_metrics(mdl: &Path<'static>, sampler: &impl Sampler) {
+    let ts = sampler.sampled_at();
+
+    let sample = |name: &'static str,
+                  agg: &'static str,
+                  unit: &'static str,
+                  value: emit::Value,
+                  props: &dyn emit::props::ErasedProps| {
+        sampler.metric(
+            Metric::new(mdl.by_ref(), ts, props)
+                .with_name(name)
+                .with_agg(agg)
+                .with_unit(unit)
+                .with_value(value),
+        );
+    };
+
+    let ticks = clock_ticks();
+
+    let stat = fs::read_to_string("/proc/self/stat")
+        .ok()
+        .and_then(|stat| parse_stat(&stat));
+
+    if let (Some(stat), Some(ticks)) = (&stat, ticks) {
+        sample(
+            "process.cpu.time",
+            METRIC_AGG_COUNT,
+            "s",
+            (stat.utime as f64 / ticks).into(),
+            &("cpu.mode", "user"),
+        );
+        sample(
+            "process.cpu.time",
+            METRIC_AGG_COUNT,
+            "s",
+            (stat.stime as f64 / ticks).into(),
+            &("cpu.mode", "system"),
+        );
+    }
+
+    let status = fs::read_to_string("/proc/self/status")
+        .map(|status| parse_status(&status))
+        .unwrap_or_default();
+
+    if let Some(rss) = status.rss {
+        sample(
+            "process.memory.usage",
+            METRIC_AGG_SUM,
+            "By",
+            rss.into(),
+            &emit::Empty,
+        );
+    }
+    if let Some(vm_size) = status.vm_size {
+        sample(
+            "process.memory.virtual",
+            METRIC_AGG_SUM,
+            "By",
+            vm_size.into(),
+            &emit::Empty,
+        );
+    }
+    if let Some(threads) = status.threads {
+        sample(
+            "process.thread.count",
+            METRIC_AGG_SUM,
+            "{thread}",
+            threads.into(),
+            &emit::Empty,
+        );
+    }
+
+    if let Some(fds) = open_fds() {
+        sample(
+            "process.unix.file_descriptor.count",
+            METRIC_AGG_SUM,
+            "{file_descriptor}",
+            fds.into(),
+            &emit::Empty,
+        );
+    }
+
+    if let Some(voluntary) = status.voluntary_ctxt_switches {
+        sample(
+            "process.context_switches",
+            METRIC_AGG_COUNT,
+            "{context_switch}",
+            voluntary.into(),
+            &("process.context_switch.type", "voluntary"),
+        );
+    }
+    if let Some(involuntary) = status.nonvoluntary_ctxt_switches {
+        sample(
+            "process.context_switches",
+            METRIC_AGG_COUNT,
+            "{context_switch}",
+            involuntary.into(),
+            &("process.context_switch.type", "involuntary"),
+        );
+    }
+
+    if let (Some(stat), Some(ticks), Some(system_uptime)) = (&stat, ticks, system_uptime()) {
+        let uptime = (system_uptime - stat.start_time as f64 / ticks).max(0.0);
+
+        sample(
+            "process.uptime",
+            METRIC_AGG_LAST,
+            "s",
+            uptime.into(),
+            &emit::Empty,
+        );
+    }
+}
+
+fn clock_ticks() -> Option<f64> {
+    // SAFETY: `sysconf` has no preconditions
+    let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+
+    if ticks > 0 { Some(ticks as f64) } else { None }
+}
+
+fn open_fds() -> Option<u64> {
+    let fds = fs::read_dir("/proc/self/fd").ok()?.count() as u64;
+
+    // Reading the directory opens a file descriptor for it, which is included in the count
+    Some(fds.saturating_sub(1))
+}
+
+fn system_uptime() -> Option<f64> {
+    fs::read_to_string("/proc/uptime")
+        .ok()?
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+#[derive(Debug, PartialEq)]
+struct Stat {
+    utime: u64,
+    stime: u64,
+    start_time: u64,
+}
+
+// See `proc_pid_stat(5)`
+fn parse_stat(stat: &str) -> Option<Stat> {
+    // The command name is wrapped in parens and may itself contain spaces or parens,
+    // so the remaining fields start after the last `)`
+    let (_, fields) = stat.rsplit_once(')')?;
+
+    // Fields are numbered from `1`, and the first field after the command name is `3`
+    let field = |n: usize| -> Option<u64> { fields.split_whitespace().nth(n - 3)?.parse().ok() };
+
+    Some(Stat {
+        utime: field(14)?,
+        stime: field(15)?,
+        start_time: field(22)?,
+    })
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Status {
+    rss: Option<u64>,
+    vm_size: Option<u64>,
+    threads: Option<u64>,
+    voluntary_ctxt_switches: Option<u64>,
+    nonvoluntary_ctxt_switches: Option<u64>,
+}
+
+// See `proc_pid_status(5)`
+fn parse_status(status: &str) -> Status {
+    let mut parsed = Status::default();
+
+    for line in status.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+
+        let mut value = value.split_whitespace();
+        let Some(n) = value.next().and_then(|n| n.parse::<u64>().ok()) else {
+            continue;
+        };
+
+        // Memory sizes are reported in kibibytes
+        let unit = value.next();
+        let bytes = || match unit {
+            Some("kB") => n.checked_mul(1024),
+            _ => Some(n),
+        };
+
+        match key {
+            "VmRSS" => parsed.rss = bytes(),
+            "VmSize" => parsed.vm_size = bytes(),
+            "Threads" => parsed.threads = Some(n),
+            "voluntary_ctxt_switches" => parsed.voluntary_ctxt_switches = Some(n),
+            "nonvoluntary_ctxt_switches" => parsed.nonvoluntary_ctxt_switches = Some(n),
+            _ => (),
+        }
+    }
+
+    parsed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::{cell::RefCell, collections::BTreeMap};
+
+    #[test]
+    fn parse_stat_fields() {
+        let stat = "4242 (my (weird) app) S 1 4242 4242 0 -1 4194560 1209 0 0 0 17 4 0 0 20 0 3 0 123456 10485760 512 18446744073709551615 1 1 0 0 0 0 0 4096 0 0 0 0 17 2 0 0 0 0 0";
+
+        assert_eq!(
+            Some(Stat {
+                utime: 17,
+                stime: 4,
+                start_time: 123456,
+            }),
+            parse_stat(stat)
+        );
+    }
+
+    #[test]
+    fn parse_stat_invalid() {
+        assert_eq!(None, parse_stat(""));
+        assert_eq!(None, parse_stat("4242 (app) S 1"));
+    }
+
+    #[test]
+    fn parse_status_fields() {
+        let status = "Name:\tapp\nVmSize:\t   10240 kB\nVmRSS:\t    2048 kB\nThreads:\t3\nvoluntary_ctxt_switches:\t15\nnonvoluntary_ctxt_switches:\t2\n";
+
+        assert_eq!(
+            Status {
+                rss: Some(2048 * 1024),
+                vm_size: Some(10240 * 1024),
+                threads: Some(3),
+                voluntary_ctxt_switches: Some(15),
+                nonvoluntary_ctxt_switches: Some(2),
+            },
+            parse_status(status)
+        );
+    }
+
+    #[test]
+    fn sample_current_process() {
+        let samples = RefCell::new(BTreeMap::<String, (String, String)>::new());
+
+        sample_metrics(
+            &Path::new_raw("test"),
+            &emit::metric::sampler::from_fn(|metric| {
+                let name = metric.name().unwrap().to_string();
+                let agg = metric.agg().unwrap().to_string();
+
+                assert!(metric.value().unwrap().cast::<f64>().unwrap() >= 0.0);
+
+                samples
+                    .borrow_mut()
+                    .insert(name, (agg, metric.unit().unwrap().to_string()));
+            }),
+        );
+
+        let samples = samples.into_inner();
+
+        for (name, agg, unit) in [
+            ("process.cpu.time", "count", "s"),
+            ("process.memory.usage", "sum", "By"),
+            ("process.memory.virtual", "sum", "By"),
+            ("process.thread.count", "sum", "{thread}"),
+            (
+                "process.unix.file_descriptor.count",
+                "sum",
+                "{file_descriptor}",
+            ),
+            ("process.context_switches", "count", "{context_switch}"),
+            ("process.uptime", "last", "s"),
+        ] {
+            assert_eq!(
+                Some(&(agg.to_string(), unit.to_string())),
+                samples.get(name),
+                "{name}"
+            );
+        }
+    }
+}
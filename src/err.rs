@@ -1,8 +1,23 @@
 /*!
 Utilities for working with the `err` well-known property.
+
+Errors are normally captured using their [`Error`] implementation, which lets emitters format them and walk their [`Error::source`] chain. [`chain`] can be used instead to capture an error as a structured [`Chain`] of causes, along with a backtrace if one is enabled:
+
+```
+# fn try_connect() -> Result<(), std::io::Error> { Ok(()) }
+if let Err(err) = try_connect() {
+    emit::error!("failed to connect: {err}", err: emit::err::chain(&err));
+}
+```
 */
 
-use std::error::Error;
+use core::fmt;
+use std::{
+    backtrace::{Backtrace, BacktraceStatus},
+    error::Error,
+};
+
+use emit_core::value::{ToValue, Value};
 
 /**
 Convert an error implementing `AsRef<dyn Error + 'static>` into an [`Error`].
@@ -12,3 +27,450 @@ This method can be used to accept types like `anyhow::Error` when using the `err
 pub fn as_ref<'a>(err: &'a impl AsRef<dyn Error + 'static>) -> &'a (dyn Error + 'static) {
     err.as_ref()
 }
+
+/**
+Capture an error and its chain of sources into a structured [`Chain`].
+
+A backtrace is also captured if they're enabled through the `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE` environment variables. See [`Backtrace::capture`] for details.
+*/
+#[track_caller]
+pub fn chain<E: Error + 'static>(err: &E) -> Chain {
+    Chain::new(Some(core::any::type_name::<E>()), err)
+}
+
+/**
+Capture an error and its chain of sources into a structured [`Chain`].
+
+This method is like [`chain`], but accepts a `dyn Error`, such as the result of [`as_ref`]. The type of the error won't be known.
+*/
+#[track_caller]
+pub fn chain_dyn(err: &(dyn Error + 'static)) -> Chain {
+    Chain::new(None, err)
+}
+
+/**
+A structured error, with a [`Cause`] for it and each of its sources, and an optional [`Backtrace`].
+
+A `Chain` can be created through [`chain`] or [`chain_dyn`]. It implements [`Error`] itself, so it's captured and formatted like any other error. Emitters can downcast values to a `Chain` with [`Value::downcast_ref`] to serialize it structurally, through its `sval::Value` or `serde::Serialize` implementations, like:
+
+```json
+{
+    "chain": [
+        { "type": "my_app::Error", "message": "failed to connect" },
+        { "message": "connection refused" }
+    ],
+    "backtrace": "..."
+}
+```
+
+The `type` is only present for the outermost error, when it's known. The `backtrace` is only present when one was captured.
+*/
+pub struct Chain {
+    root: Cause,
+    backtrace: Option<Backtrace>,
+}
+
+/**
+A single error in a [`Chain`].
+
+`Cause` implements [`Error`], with its source being the next `Cause` in the chain.
+*/
+pub struct Cause {
+    ty: Option<&'static str>,
+    message: String,
+    source: Option<Box<Cause>>,
+}
+
+impl Chain {
+    #[track_caller]
+    fn new(ty: Option<&'static str>, err: &(dyn Error + 'static)) -> Self {
+        let mut root = Cause {
+            ty,
+            message: err.to_string(),
+            source: None,
+        };
+
+        let mut next = &mut root.source;
+        for source in std::iter::successors(err.source(), |err| (*err).source()) {
+            next = &mut next
+                .insert(Box::new(Cause {
+                    ty: None,
+                    message: source.to_string(),
+                    source: None,
+                }))
+                .source;
+        }
+
+        let backtrace = Backtrace::capture();
+
+        Chain {
+            root,
+            backtrace: if backtrace.status() == BacktraceStatus::Captured {
+                Some(backtrace)
+            } else {
+                None
+            },
+        }
+    }
+
+    /**
+    Iterate over the causes in the chain, starting from the outermost error.
+    */
+    pub fn causes(&self) -> impl Iterator<Item = &Cause> {
+        std::iter::successors(Some(&self.root), |cause| cause.source.as_deref())
+    }
+
+    /**
+    Get the backtrace captured along with the error, if there is one.
+    */
+    pub fn backtrace(&self) -> Option<&Backtrace> {
+        self.backtrace.as_ref()
+    }
+}
+
+impl Cause {
+    /**
+    Get the name of the type of the error, if it's known.
+    */
+    pub fn ty(&self) -> Option<&str> {
+        self.ty
+    }
+
+    /**
+    Get the message of the error.
+    */
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Debug for Chain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Chain")
+            .field("chain", &self.causes().collect::<Vec<_>>())
+            .field("backtrace", &self.backtrace)
+            .finish()
+    }
+}
+
+impl fmt::Display for Chain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.root, f)
+    }
+}
+
+impl fmt::Debug for Cause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cause")
+            .field("type", &self.ty)
+            .field("message", &self.message)
+            .finish()
+    }
+}
+
+impl fmt::Display for Cause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for Chain {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.root.source()
+    }
+}
+
+impl Error for Cause {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|source| source as &(dyn Error + 'static))
+    }
+}
+
+impl ToValue for Chain {
+    fn to_value(&self) -> Value<'_> {
+        Value::capture_error(self)
+    }
+}
+
+#[cfg(feature = "sval")]
+const CHAIN_LABEL: sval::Label = sval::Label::new("Chain");
+#[cfg(feature = "sval")]
+const CHAIN_CHAIN_LABEL: sval::Label = sval::Label::new("chain");
+#[cfg(feature = "sval")]
+const CHAIN_BACKTRACE_LABEL: sval::Label = sval::Label::new("backtrace");
+#[cfg(feature = "sval")]
+const CAUSE_LABEL: sval::Label = sval::Label::new("Cause");
+#[cfg(feature = "sval")]
+const CAUSE_TYPE_LABEL: sval::Label = sval::Label::new("type");
+#[cfg(feature = "sval")]
+const CAUSE_MESSAGE_LABEL: sval::Label = sval::Label::new("message");
+
+#[cfg(feature = "sval")]
+impl sval::Value for Chain {
+    fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> sval::Result {
+        stream.record_begin(None, Some(&CHAIN_LABEL), None, None)?;
+
+        stream.record_value_begin(None, &CHAIN_CHAIN_LABEL)?;
+        stream.seq_begin(None)?;
+        for cause in self.causes() {
+            stream.seq_value_begin()?;
+            stream.value(cause)?;
+            stream.seq_value_end()?;
+        }
+        stream.seq_end()?;
+        stream.record_value_end(None, &CHAIN_CHAIN_LABEL)?;
+
+        if let Some(ref backtrace) = self.backtrace {
+            stream.record_value_begin(None, &CHAIN_BACKTRACE_LABEL)?;
+            sval::stream_display(&mut *stream, backtrace)?;
+            stream.record_value_end(None, &CHAIN_BACKTRACE_LABEL)?;
+        }
+
+        stream.record_end(None, Some(&CHAIN_LABEL), None)
+    }
+}
+
+#[cfg(feature = "sval")]
+impl sval::Value for Cause {
+    fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(&'sval self, stream: &mut S) -> sval::Result {
+        stream.record_begin(None, Some(&CAUSE_LABEL), None, None)?;
+
+        if let Some(ty) = self.ty {
+            stream.record_value_begin(None, &CAUSE_TYPE_LABEL)?;
+            stream.value(ty)?;
+            stream.record_value_end(None, &CAUSE_TYPE_LABEL)?;
+        }
+
+        stream.record_value_begin(None, &CAUSE_MESSAGE_LABEL)?;
+        stream.value(&*self.message)?;
+        stream.record_value_end(None, &CAUSE_MESSAGE_LABEL)?;
+
+        stream.record_end(None, Some(&CAUSE_LABEL), None)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Chain {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct as _;
+
+        struct Causes<'a>(&'a Chain);
+
+        impl<'a> serde::Serialize for Causes<'a> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_seq(self.0.causes())
+            }
+        }
+
+        struct Display<'a, T>(&'a T);
+
+        impl<'a, T: fmt::Display> serde::Serialize for Display<'a, T> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self.0)
+            }
+        }
+
+        let mut chain = serializer.serialize_struct("Chain", 2)?;
+
+        chain.serialize_field("chain", &Causes(self))?;
+
+        match self.backtrace {
+            Some(ref backtrace) => chain.serialize_field("backtrace", &Display(backtrace))?,
+            None => chain.skip_field("backtrace")?,
+        }
+
+        chain.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Cause {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct as _;
+
+        let mut cause = serializer.serialize_struct("Cause", 2)?;
+
+        match self.ty {
+            Some(ty) => cause.serialize_field("type", ty)?,
+            None => cause.skip_field("type")?,
+        }
+
+        cause.serialize_field("message", &self.message)?;
+
+        cause.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct TestError {
+        msg: &'static str,
+        source: Option<std::io::Error>,
+    }
+
+    impl fmt::Display for TestError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(self.msg)
+        }
+    }
+
+    impl Error for TestError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            self.source
+                .as_ref()
+                .map(|err| err as &(dyn Error + 'static))
+        }
+    }
+
+    fn test_error() -> TestError {
+        TestError {
+            msg: "failed to connect",
+            source: Some(std::io::Error::other("connection refused")),
+        }
+    }
+
+    #[test]
+    fn chain_causes() {
+        let chain = chain(&test_error());
+
+        let causes = chain
+            .causes()
+            .map(|cause| (cause.ty(), cause.message()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![
+                (
+                    Some(core::any::type_name::<TestError>()),
+                    "failed to connect"
+                ),
+                (None, "connection refused"),
+            ],
+            causes
+        );
+
+        assert_eq!("failed to connect", chain.to_string());
+        assert_eq!("connection refused", chain.source().unwrap().to_string());
+        assert!(chain.source().unwrap().source().is_none());
+    }
+
+    #[test]
+    fn chain_dyn_causes() {
+        let err = test_error();
+        let chain = chain_dyn(&err);
+
+        assert_eq!(None, chain.causes().next().unwrap().ty());
+        assert_eq!(2, chain.causes().count());
+    }
+
+    #[test]
+    fn chain_to_value() {
+        let chain = chain(&test_error());
+
+        let value = chain.to_value();
+
+        assert!(value.downcast_ref::<Chain>().is_some());
+        assert_eq!(
+            "connection refused",
+            value
+                .to_borrowed_error()
+                .unwrap()
+                .source()
+                .unwrap()
+                .to_string()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "sval")]
+    fn chain_stream() {
+        let chain = Chain {
+            root: Cause {
+                ty: Some("TestError"),
+                message: "failed to connect".into(),
+                source: Some(Box::new(Cause {
+                    ty: None,
+                    message: "connection refused".into(),
+                    source: None,
+                })),
+            },
+            backtrace: None,
+        };
+
+        sval_test::assert_tokens(
+            &chain,
+            &[
+                sval_test::Token::RecordBegin(None, Some(CHAIN_LABEL), None, None),
+                sval_test::Token::RecordValueBegin(None, CHAIN_CHAIN_LABEL),
+                sval_test::Token::SeqBegin(None),
+                sval_test::Token::SeqValueBegin,
+                sval_test::Token::RecordBegin(None, Some(CAUSE_LABEL), None, None),
+                sval_test::Token::RecordValueBegin(None, CAUSE_TYPE_LABEL),
+                sval_test::Token::TextBegin(Some(9)),
+                sval_test::Token::TextFragment("TestError"),
+                sval_test::Token::TextEnd,
+                sval_test::Token::RecordValueEnd(None, CAUSE_TYPE_LABEL),
+                sval_test::Token::RecordValueBegin(None, CAUSE_MESSAGE_LABEL),
+                sval_test::Token::TextBegin(Some(17)),
+                sval_test::Token::TextFragment("failed to connect"),
+                sval_test::Token::TextEnd,
+                sval_test::Token::RecordValueEnd(None, CAUSE_MESSAGE_LABEL),
+                sval_test::Token::RecordEnd(None, Some(CAUSE_LABEL), None),
+                sval_test::Token::SeqValueEnd,
+                sval_test::Token::SeqValueBegin,
+                sval_test::Token::RecordBegin(None, Some(CAUSE_LABEL), None, None),
+                sval_test::Token::RecordValueBegin(None, CAUSE_MESSAGE_LABEL),
+                sval_test::Token::TextBegin(Some(18)),
+                sval_test::Token::TextFragment("connection refused"),
+                sval_test::Token::TextEnd,
+                sval_test::Token::RecordValueEnd(None, CAUSE_MESSAGE_LABEL),
+                sval_test::Token::RecordEnd(None, Some(CAUSE_LABEL), None),
+                sval_test::Token::SeqValueEnd,
+                sval_test::Token::SeqEnd,
+                sval_test::Token::RecordValueEnd(None, CHAIN_CHAIN_LABEL),
+                sval_test::Token::RecordEnd(None, Some(CHAIN_LABEL), None),
+            ],
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn chain_serialize() {
+        let chain = Chain {
+            root: Cause {
+                ty: Some("TestError"),
+                message: "failed to connect".into(),
+                source: None,
+            },
+            backtrace: None,
+        };
+
+        serde_test::assert_ser_tokens(
+            &chain,
+            &[
+                serde_test::Token::Struct {
+                    name: "Chain",
+                    len: 2,
+                },
+                serde_test::Token::Str("chain"),
+                serde_test::Token::Seq { len: None },
+                serde_test::Token::Struct {
+                    name: "Cause",
+                    len: 2,
+                },
+                serde_test::Token::Str("type"),
+                serde_test::Token::Str("TestError"),
+                serde_test::Token::Str("message"),
+                serde_test::Token::Str("failed to connect"),
+                serde_test::Token::StructEnd,
+                serde_test::Token::SeqEnd,
+                serde_test::Token::StructEnd,
+            ],
+        );
+    }
+}
@@ -17,6 +17,8 @@ A set of ambient properties that are cleaned up automatically.
 This type is a wrapper around a [`Ctxt`] that simplifies ambient property management. A frame containing ambient properties can be created through [`Frame::push`] or [`Frame::root`]. Those properties can be activated by calling [`Frame::enter`]. The returned [`EnterGuard`] will automatically deactivate those properties when dropped.
 
 A frame can be converted into a future through [`Frame::in_future`] that enters and exits on each call to [`Future::poll`] so ambient properties can follow a future as it executes in an async runtime.
+
+The [`FutureExt`] and [`FnExt`] traits are shorthands for capturing the current frame and carrying it into a future or closure, so ambient properties can follow work spawned onto other tasks or threads.
 */
 pub struct Frame<C: Ctxt> {
     scope: mem::ManuallyDrop<C::Frame>,
@@ -248,6 +250,68 @@ impl<C: Ctxt, F: Future> Future for FrameFuture<C, F> {
     }
 }
 
+/**
+Extension methods for carrying ambient properties into futures.
+
+This trait is implemented for all [`Future`]s.
+*/
+pub trait FutureExt: Future + Sized {
+    /**
+    Capture the current set of ambient properties in `ctxt`, and activate them on each call to [`Future::poll`].
+
+    This method is a shorthand for [`Frame::current`] followed by [`Frame::in_future`].
+    */
+    #[track_caller]
+    #[must_use = "futures do nothing unless polled"]
+    fn in_ctxt<C: Ctxt>(self, ctxt: C) -> FrameFuture<C, Self> {
+        Frame::current(ctxt).in_future(self)
+    }
+
+    /**
+    Capture the current set of ambient properties, and activate them on each call to [`Future::poll`].
+
+    This method will use the [`Ctxt`] from [`crate::runtime::shared()`].
+    */
+    #[cfg(feature = "implicit_rt")]
+    #[track_caller]
+    #[must_use = "futures do nothing unless polled"]
+    fn in_current_ctxt(self) -> FrameFuture<crate::runtime::AmbientCtxt<'static>, Self> {
+        self.in_ctxt(crate::ctxt())
+    }
+}
+
+impl<F: Future> FutureExt for F {}
+
+/**
+Extension methods for carrying ambient properties into closures.
+
+This trait is implemented for all [`FnOnce`] closures that don't take any arguments.
+*/
+pub trait FnExt<R>: FnOnce() -> R + Sized {
+    /**
+    Capture the current set of ambient properties in `ctxt`, and activate them when the returned closure is called.
+
+    This method is a shorthand for [`Frame::current`] followed by [`Frame::in_fn`].
+    */
+    #[track_caller]
+    fn in_ctxt<C: Ctxt>(self, ctxt: C) -> impl FnOnce() -> R {
+        Frame::current(ctxt).in_fn(self)
+    }
+
+    /**
+    Capture the current set of ambient properties, and activate them when the returned closure is called.
+
+    This method will use the [`Ctxt`] from [`crate::runtime::shared()`].
+    */
+    #[cfg(feature = "implicit_rt")]
+    #[track_caller]
+    fn in_current_ctxt(self) -> impl FnOnce() -> R {
+        self.in_ctxt(crate::ctxt())
+    }
+}
+
+impl<F: FnOnce() -> R, R> FnExt<R> for F {}
+
 #[cfg(test)]
 mod tests {
     #[cfg(feature = "std")]
@@ -347,4 +411,144 @@ mod tests {
             })
             .await;
     }
+
+    #[cfg(all(
+        feature = "std",
+        not(all(
+            target_arch = "wasm32",
+            target_vendor = "unknown",
+            target_os = "unknown"
+        ))
+    ))]
+    mod spawn {
+        use super::*;
+
+        use crate::{Path, span::SpanGuard, testing::Harness, well_known::KEY_SPAN_NAME};
+
+        macro_rules! span {
+            ($rt:expr, $name:expr) => {
+                SpanGuard::new(
+                    $rt.filter(),
+                    $rt.ctxt(),
+                    $rt.clock(),
+                    $rt.rng(),
+                    crate::span::completion::default_from_runtime($rt),
+                    Empty,
+                    Path::new_raw("test"),
+                    [(KEY_SPAN_NAME, $name)],
+                )
+            };
+        }
+
+        fn harness() -> &'static Harness {
+            Box::leak(Box::new(Harness::new()))
+        }
+
+        #[tokio::test]
+        async fn future_in_ctxt_across_tasks() {
+            let harness = harness();
+            let rt = harness.rt();
+
+            let (mut outer, frame) = span!(rt, "outer");
+
+            frame
+                .in_future(async move {
+                    outer.start();
+
+                    tokio::spawn(
+                        async move {
+                            let (mut inner, frame) = span!(rt, "inner");
+
+                            frame.in_future(async move { inner.start() }).await
+                        }
+                        .in_ctxt(rt.ctxt()),
+                    )
+                    .await
+                    .unwrap();
+                })
+                .await;
+
+            harness.assert_span_with_parent("inner", "outer");
+        }
+
+        #[tokio::test]
+        async fn future_without_ctxt_across_tasks() {
+            let harness = harness();
+            let rt = harness.rt();
+
+            let (mut outer, frame) = span!(rt, "outer");
+
+            frame
+                .in_future(async move {
+                    outer.start();
+
+                    tokio::spawn(async move {
+                        let (mut inner, frame) = span!(rt, "inner");
+
+                        frame.in_future(async move { inner.start() }).await
+                    })
+                    .await
+                    .unwrap();
+                })
+                .await;
+
+            let inner = harness.assert_span("inner");
+            assert!(inner.props().get("span_parent").is_none());
+        }
+
+        #[tokio::test]
+        async fn fn_in_ctxt_across_blocking_threads() {
+            let harness = harness();
+            let rt = harness.rt();
+
+            let (mut outer, frame) = span!(rt, "outer");
+
+            frame
+                .in_future(async move {
+                    outer.start();
+
+                    tokio::task::spawn_blocking(
+                        (move || {
+                            let (mut inner, frame) = span!(rt, "inner");
+
+                            frame.call(move || inner.start())
+                        })
+                        .in_ctxt(rt.ctxt()),
+                    )
+                    .await
+                    .unwrap();
+                })
+                .await;
+
+            harness.assert_span_with_parent("inner", "outer");
+        }
+
+        #[test]
+        fn fn_in_ctxt_across_rayon() {
+            let harness = harness();
+            let rt = harness.rt();
+
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(2)
+                .build()
+                .unwrap();
+
+            let (mut outer, frame) = span!(rt, "outer");
+
+            frame.call(move || {
+                outer.start();
+
+                pool.install(
+                    (move || {
+                        let (mut inner, frame) = span!(rt, "inner");
+
+                        frame.call(move || inner.start())
+                    })
+                    .in_ctxt(rt.ctxt()),
+                );
+            });
+
+            harness.assert_span_with_parent("inner", "outer");
+        }
+    }
 }
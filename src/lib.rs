@@ -91,6 +91,7 @@ Its macros can be grouped based on the kind of diagnostic signal they relate to.
 - `implicit_internal_rt` (default): Enable configuring the internal runtime for `emit`'s own diagnostics.
 - `sval`: Enable capturing complex properties using `sval`.
 - `serde`: Enable capturing complex properties using `serde`.
+- `tokio`: Enable integration with `tokio`, like [`spawn`], [`testing::TokioClock`], and [`metric::tokio::RuntimeMetrics`]. Implies `std`.
 - `web` (default): Use JavaScript built-in APIs on WebAssembly targets for platform support. This feature is a no-op outside of `wasm32-unknown-unknown`.
 - `rand` (default): Use `rand` as the default source of randomness on targets with a default provider. The specific version of `rand` is not guaranteed to remain the same. This feature is a no-op outside of the following targets:
     - `linux`
@@ -176,6 +177,14 @@ pub mod timer;
 
 #[cfg(feature = "std")]
 pub mod err;
+#[cfg(feature = "std")]
+pub mod limit;
+#[cfg(feature = "std")]
+pub mod panic;
+#[cfg(feature = "std")]
+pub mod redact;
+#[cfg(feature = "std")]
+pub mod testing;
 
 pub use self::{
     clock::Clock,
@@ -291,6 +300,44 @@ pub fn sample(source: impl metric::Source) {
     source.sample_metrics(metric::sampler::from_runtime(runtime::shared()))
 }
 
+/**
+Spawn a future onto the current `tokio` runtime, carrying the current set of ambient properties with it.
+
+This method will use the [`Ctxt`] from [`runtime::shared()`]. It's a shorthand for calling [`tokio::spawn`] with [`frame::FutureExt::in_current_ctxt`], so spans started within the future will be children of the span that spawned it.
+
+# Panics
+
+This method will panic if called outside of a `tokio` runtime.
+*/
+#[cfg(all(feature = "implicit_rt", feature = "tokio"))]
+#[track_caller]
+pub fn spawn<F>(future: F) -> tokio::task::JoinHandle<F::Output>
+where
+    F: core::future::Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    tokio::spawn(frame::FutureExt::in_current_ctxt(future))
+}
+
+/**
+Run a blocking closure on the current `tokio` runtime's blocking thread pool, carrying the current set of ambient properties with it.
+
+This method will use the [`Ctxt`] from [`runtime::shared()`]. It's a shorthand for calling [`tokio::task::spawn_blocking`] with [`frame::FnExt::in_current_ctxt`], so spans started within the closure will be children of the span that spawned it.
+
+# Panics
+
+This method will panic if called outside of a `tokio` runtime.
+*/
+#[cfg(all(feature = "implicit_rt", feature = "tokio"))]
+#[track_caller]
+pub fn spawn_blocking<F, R>(f: F) -> tokio::task::JoinHandle<R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    tokio::task::spawn_blocking(frame::FnExt::in_current_ctxt(f))
+}
+
 #[doc(hidden)]
 pub mod __private {
     pub extern crate core;
@@ -0,0 +1,807 @@
+/*!
+Limiting the number and size of properties on events.
+
+A single oversized property, like an entire HTTP body captured through its `Debug` implementation, can produce events that are too large for emitters to handle efficiently, or at all. [`Limits`] caps the properties on events before they reach an emitter:
+
+- [`Limits::max_props`]: The number of properties on an event.
+- [`Limits::max_key_len`]: The length of property keys.
+- [`Limits::max_value_len`]: The length of property values.
+- [`Limits::max_depth`]: The nesting depth of structured property values. This requires the `sval` Cargo feature.
+- [`Limits::max_event_size`]: The total size of properties on an event.
+
+Keys and values that are too long are truncated, with `…` appended to them. Properties that can't fit within the limits at all are dropped, and the number of properties dropped is recorded in the well-known [`crate::well_known::KEY_DROPPED_PROPS`] property. If a property that's dropped, or has its key truncated, is used in the template of an event then its value is still rendered into the message, with any value limits applied. Other well-known properties, like [`crate::well_known::KEY_LVL`], [`crate::well_known::KEY_ERR`], or [`crate::well_known::KEY_TRACE_ID`], are never truncated or dropped, and don't count towards any limits.
+
+[`Limits`] is a [`crate::emitter::Wrapping`], so it can be applied to the emitter configured in [`crate::Setup`] with [`crate::emitter::wrap`]:
+
+```
+fn main() {
+    let rt = emit::setup()
+        .emit_to(emit::emitter::from_fn(|evt| println!("{evt:?}")))
+        .map_emitter(|emitter| {
+            emit::emitter::wrap(
+                emitter,
+                emit::limit::Limits::new()
+                    .max_props(64)
+                    .max_value_len(1024)
+                    .max_event_size(16 * 1024),
+            )
+        })
+        .init();
+
+    // Your app code goes here
+
+    rt.blocking_flush(std::time::Duration::from_secs(5));
+}
+```
+
+Limits can also be applied to individual emitters, by wrapping them before they're passed to [`crate::Setup::emit_to`] or [`crate::Setup::and_emit_to`].
+*/
+
+use core::{fmt, ops::ControlFlow};
+
+use emit_core::{
+    emitter::{Emitter, wrapping::Wrapping},
+    event::{Event, ToEvent},
+    props::Props,
+    str::Str,
+    template::Template,
+    value::{ToValue, Value},
+    well_known::{self, KEY_DROPPED_PROPS},
+};
+
+/**
+A set of limits on the properties of events.
+
+See the [module docs](self) for details.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct Limits {
+    max_props: Option<usize>,
+    max_key_len: Option<usize>,
+    max_value_len: Option<usize>,
+    #[cfg(feature = "sval")]
+    max_depth: Option<usize>,
+    max_event_size: Option<usize>,
+}
+
+impl Limits {
+    /**
+    Create a new set of limits that doesn't limit anything.
+    */
+    pub fn new() -> Self {
+        Limits::default()
+    }
+
+    /**
+    Limit the number of properties on an event.
+
+    Properties beyond the first `max` are dropped.
+    */
+    pub fn max_props(mut self, max: usize) -> Self {
+        self.max_props = Some(max);
+        self
+    }
+
+    /**
+    Limit the length of property keys, in characters.
+
+    Keys longer than `max` are truncated.
+    */
+    pub fn max_key_len(mut self, max: usize) -> Self {
+        self.max_key_len = Some(max);
+        self
+    }
+
+    /**
+    Limit the length of the textual representation of property values, in characters.
+
+    Values longer than `max` are replaced with a truncated string.
+    */
+    pub fn max_value_len(mut self, max: usize) -> Self {
+        self.max_value_len = Some(max);
+        self
+    }
+
+    /**
+    Limit the nesting depth of structured property values.
+
+    Primitive values like numbers and strings have a depth of `0`. Each map, sequence, record, or tuple a value is nested within adds `1` to its depth. Values deeper than `max` are replaced with their textual representation.
+    */
+    #[cfg(feature = "sval")]
+    pub fn max_depth(mut self, max: usize) -> Self {
+        self.max_depth = Some(max);
+        self
+    }
+
+    /**
+    Limit the total size of the properties on an event, in bytes.
+
+    The size of each property is the length of its key plus the length of the textual representation of its value. Properties that would push the total size beyond `max` are dropped.
+    */
+    pub fn max_event_size(mut self, max: usize) -> Self {
+        self.max_event_size = Some(max);
+        self
+    }
+
+    fn limit_props<'kv>(&self, tpl: &Template, props: &'kv impl Props) -> LimitedEvent<'kv> {
+        let mut limited = Vec::new();
+        let mut holes = Vec::new();
+
+        let in_tpl = |key: &Str| {
+            tpl.parts()
+                .any(|part| part.label().is_some_and(|label| label.get() == key.get()))
+        };
+
+        let mut dropped = 0;
+        let mut count = 0;
+        let mut size = 0;
+
+        let _ = props.dedup().for_each(|k, v| {
+            // Carry through the number of properties dropped by any earlier limits
+            if k == KEY_DROPPED_PROPS {
+                dropped += v.cast::<usize>().unwrap_or_default();
+                return ControlFlow::Continue(());
+            }
+
+            if is_well_known(k.get()) {
+                limited.push((k, Limited::Value(v)));
+                return ControlFlow::Continue(());
+            }
+
+            if let Some(max) = self.max_props
+                && count >= max
+            {
+                if in_tpl(&k) {
+                    holes.push((k, self.limit_value(v)));
+                }
+
+                dropped += 1;
+                return ControlFlow::Continue(());
+            }
+
+            let original = k.clone();
+
+            let k = self.limit_key(k);
+            let v = self.limit_value(v);
+
+            if let Some(max) = self.max_event_size {
+                match v.len(max.saturating_sub(size + k.get().len())) {
+                    Some(len) => size += k.get().len() + len,
+                    None => {
+                        if in_tpl(&original) {
+                            holes.push((original, v));
+                        }
+
+                        dropped += 1;
+                        return ControlFlow::Continue(());
+                    }
+                }
+            }
+
+            if k != original && in_tpl(&original) {
+                holes.push((original, v.clone()));
+            }
+
+            count += 1;
+            limited.push((k, v));
+
+            ControlFlow::Continue(())
+        });
+
+        if dropped > 0 {
+            limited.push((Str::new(KEY_DROPPED_PROPS), Limited::Dropped(dropped)));
+        }
+
+        LimitedEvent {
+            props: LimitedProps(limited),
+            holes: LimitedProps(holes),
+        }
+    }
+
+    fn limit_key<'kv>(&self, key: Str<'kv>) -> Str<'kv> {
+        if let Some(max) = self.max_key_len
+            && let Some(truncated) = truncate(&key, max)
+        {
+            return Str::new_owned(truncated);
+        }
+
+        key
+    }
+
+    fn limit_value<'kv>(&self, value: Value<'kv>) -> Limited<'kv> {
+        #[cfg(feature = "sval")]
+        if let Some(max) = self.max_depth
+            && exceeds_depth(&value, max)
+        {
+            let value = value.to_string();
+
+            return match self.max_value_len.and_then(|max| truncate(&value, max)) {
+                Some(truncated) => Limited::Text(truncated),
+                None => Limited::Text(value),
+            };
+        }
+
+        if let Some(max) = self.max_value_len
+            && let Some(truncated) = truncate(&value, max)
+        {
+            return Limited::Text(truncated);
+        }
+
+        Limited::Value(value)
+    }
+}
+
+impl Wrapping for Limits {
+    fn wrap<O: Emitter, E: ToEvent>(&self, output: O, evt: E) {
+        let evt = evt.to_event();
+
+        let limited = self.limit_props(evt.tpl(), evt.props());
+
+        // Render any holes for dropped or renamed properties using their original keys,
+        // so the message of the event doesn't lose them
+        let tpl = match evt.tpl().render_holes(&limited.holes) {
+            Some(tpl) => tpl,
+            None => evt.tpl().by_ref(),
+        };
+
+        output.emit(Event::new(
+            evt.mdl().by_ref(),
+            tpl,
+            evt.extent().cloned(),
+            limited.props,
+        ))
+    }
+}
+
+fn is_well_known(key: &str) -> bool {
+    matches!(
+        key,
+        well_known::KEY_MDL
+            | well_known::KEY_TS
+            | well_known::KEY_TS_START
+            | well_known::KEY_TPL
+            | well_known::KEY_MSG
+            | well_known::KEY_EVT_KIND
+            | well_known::KEY_LVL
+            | well_known::KEY_ERR
+            | well_known::KEY_SPAN_NAME
+            | well_known::KEY_SPAN_KIND
+            | well_known::KEY_TRACE_ID
+            | well_known::KEY_SPAN_ID
+            | well_known::KEY_SPAN_PARENT
+            | well_known::KEY_SPAN_LINKS
+            | well_known::KEY_METRIC_NAME
+            | well_known::KEY_METRIC_AGG
+            | well_known::KEY_METRIC_VALUE
+            | well_known::KEY_METRIC_UNIT
+            | well_known::KEY_METRIC_DESCRIPTION
+            | well_known::KEY_DIST_EXP_BUCKETS
+            | well_known::KEY_DIST_EXP_SCALE
+            | well_known::KEY_DIST_BUCKET_BOUNDS
+            | well_known::KEY_DIST_BUCKET_COUNTS
+            | well_known::KEY_DIST_COUNT
+            | well_known::KEY_DIST_SUM
+            | well_known::KEY_DIST_MIN
+            | well_known::KEY_DIST_MAX
+    )
+}
+
+struct LimitedEvent<'kv> {
+    props: LimitedProps<'kv>,
+    // Properties referenced by the template that were dropped or had their keys truncated
+    holes: LimitedProps<'kv>,
+}
+
+#[derive(Clone)]
+enum Limited<'kv> {
+    Value(Value<'kv>),
+    Text(String),
+    Dropped(usize),
+}
+
+impl<'kv> Limited<'kv> {
+    // Get the length of the textual representation of the value,
+    // or `None` if it's longer than `max`
+    fn len(&self, max: usize) -> Option<usize> {
+        let mut counter = Counter { len: 0, max };
+
+        match self {
+            Limited::Value(value) => fmt::write(&mut counter, format_args!("{value}")).ok()?,
+            Limited::Text(value) => fmt::write(&mut counter, format_args!("{value}")).ok()?,
+            Limited::Dropped(value) => fmt::write(&mut counter, format_args!("{value}")).ok()?,
+        }
+
+        Some(counter.len)
+    }
+}
+
+impl<'kv> ToValue for Limited<'kv> {
+    fn to_value(&self) -> Value<'_> {
+        match self {
+            Limited::Value(value) => value.by_ref(),
+            Limited::Text(value) => value.to_value(),
+            Limited::Dropped(value) => value.to_value(),
+        }
+    }
+}
+
+struct LimitedProps<'kv>(Vec<(Str<'kv>, Limited<'kv>)>);
+
+impl<'kv> Props for LimitedProps<'kv> {
+    fn for_each<'a, F: FnMut(Str<'a>, Value<'a>) -> ControlFlow<()>>(
+        &'a self,
+        mut for_each: F,
+    ) -> ControlFlow<()> {
+        for (k, v) in &self.0 {
+            for_each(k.by_ref(), v.to_value())?;
+        }
+
+        ControlFlow::Continue(())
+    }
+
+    fn is_unique(&self) -> bool {
+        true
+    }
+}
+
+// Get a truncated copy of the textual representation of a value,
+// or `None` if it's not longer than `max` characters
+fn truncate(value: impl fmt::Display, max: usize) -> Option<String> {
+    let mut truncate = Truncate {
+        buf: String::new(),
+        len: 0,
+        max,
+    };
+
+    if fmt::write(&mut truncate, format_args!("{value}")).is_ok() {
+        return None;
+    }
+
+    truncate.buf.push('…');
+
+    Some(truncate.buf)
+}
+
+struct Truncate {
+    buf: String,
+    len: usize,
+    max: usize,
+}
+
+impl fmt::Write for Truncate {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if self.len == self.max {
+                return Err(fmt::Error);
+            }
+
+            self.buf.push(c);
+            self.len += 1;
+        }
+
+        Ok(())
+    }
+}
+
+struct Counter {
+    len: usize,
+    max: usize,
+}
+
+impl fmt::Write for Counter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.len += s.len();
+
+        if self.len > self.max {
+            return Err(fmt::Error);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sval")]
+fn exceeds_depth(value: &Value, max: usize) -> bool {
+    struct Depth {
+        depth: usize,
+        max: usize,
+    }
+
+    impl Depth {
+        fn begin(&mut self) -> sval::Result {
+            self.depth += 1;
+
+            if self.depth > self.max {
+                return sval::error();
+            }
+
+            Ok(())
+        }
+
+        fn end(&mut self) -> sval::Result {
+            self.depth -= 1;
+
+            Ok(())
+        }
+    }
+
+    impl<'sval> sval::Stream<'sval> for Depth {
+        fn null(&mut self) -> sval::Result {
+            Ok(())
+        }
+
+        fn bool(&mut self, _: bool) -> sval::Result {
+            Ok(())
+        }
+
+        fn text_begin(&mut self, _: Option<usize>) -> sval::Result {
+            Ok(())
+        }
+
+        fn text_fragment_computed(&mut self, _: &str) -> sval::Result {
+            Ok(())
+        }
+
+        fn text_end(&mut self) -> sval::Result {
+            Ok(())
+        }
+
+        fn i64(&mut self, _: i64) -> sval::Result {
+            Ok(())
+        }
+
+        fn f64(&mut self, _: f64) -> sval::Result {
+            Ok(())
+        }
+
+        fn map_begin(&mut self, _: Option<usize>) -> sval::Result {
+            self.begin()
+        }
+
+        fn map_key_begin(&mut self) -> sval::Result {
+            Ok(())
+        }
+
+        fn map_key_end(&mut self) -> sval::Result {
+            Ok(())
+        }
+
+        fn map_value_begin(&mut self) -> sval::Result {
+            Ok(())
+        }
+
+        fn map_value_end(&mut self) -> sval::Result {
+            Ok(())
+        }
+
+        fn map_end(&mut self) -> sval::Result {
+            self.end()
+        }
+
+        fn seq_begin(&mut self, _: Option<usize>) -> sval::Result {
+            self.begin()
+        }
+
+        fn seq_value_begin(&mut self) -> sval::Result {
+            Ok(())
+        }
+
+        fn seq_value_end(&mut self) -> sval::Result {
+            Ok(())
+        }
+
+        fn seq_end(&mut self) -> sval::Result {
+            self.end()
+        }
+    }
+
+    sval::stream(&mut Depth { depth: 0, max }, value).is_err()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{Path, Template};
+
+    fn limit_to_string(limits: &Limits, evt: Event<impl Props>) -> String {
+        let emitted = std::sync::Mutex::new(String::new());
+
+        limits.wrap(
+            crate::emitter::from_fn(|evt| {
+                let mut props = Vec::new();
+                let _ = evt.props().for_each(|k, v| {
+                    props.push(format!("{k}={v}"));
+                    ControlFlow::Continue(())
+                });
+
+                *emitted.lock().unwrap() = props.join(", ");
+            }),
+            evt,
+        );
+
+        emitted.into_inner().unwrap()
+    }
+
+    fn event(props: impl Props) -> Event<'static, impl Props> {
+        Event::new(
+            Path::new_raw("test"),
+            Template::literal(""),
+            crate::Empty,
+            props,
+        )
+    }
+
+    #[test]
+    fn limit_none() {
+        assert_eq!(
+            "a=1, b=Some text",
+            limit_to_string(
+                &Limits::new(),
+                event([("a", Value::from(1)), ("b", Value::from("Some text"))]),
+            )
+        );
+    }
+
+    #[test]
+    fn limit_max_props() {
+        assert_eq!(
+            "a=1, lvl=info, b=2, dropped_props=2",
+            limit_to_string(
+                &Limits::new().max_props(2),
+                event([
+                    ("a", Value::from(1)),
+                    ("lvl", Value::from("info")),
+                    ("b", Value::from(2)),
+                    ("c", Value::from(3)),
+                    ("d", Value::from(4)),
+                ]),
+            )
+        );
+    }
+
+    #[test]
+    fn limit_max_props_dedup() {
+        assert_eq!(
+            "a=1, b=3",
+            limit_to_string(
+                &Limits::new().max_props(2),
+                event([
+                    ("a", Value::from(1)),
+                    ("a", Value::from(2)),
+                    ("b", Value::from(3)),
+                ]),
+            )
+        );
+    }
+
+    #[test]
+    fn limit_dropped_props_accumulates() {
+        let limits = Limits::new().max_props(1);
+
+        let emitted = std::sync::Mutex::new(String::new());
+
+        limits.wrap(
+            crate::emitter::from_fn(|evt| {
+                *emitted.lock().unwrap() = limit_to_string(&limits, evt);
+            }),
+            event([
+                ("a", Value::from(1)),
+                ("b", Value::from(2)),
+                ("c", Value::from(3)),
+            ]),
+        );
+
+        assert_eq!("a=1, dropped_props=2", emitted.into_inner().unwrap());
+    }
+
+    #[test]
+    fn limit_max_key_len() {
+        assert_eq!(
+            "abc…=1, abc=2, trace_id=4bf92f3577b34da6a3ce929d0e0e4736",
+            limit_to_string(
+                &Limits::new().max_key_len(3),
+                event([
+                    ("abcdef", Value::from(1)),
+                    ("abc", Value::from(2)),
+                    ("trace_id", Value::from("4bf92f3577b34da6a3ce929d0e0e4736")),
+                ]),
+            )
+        );
+    }
+
+    #[test]
+    fn limit_max_value_len() {
+        assert_eq!(
+            "a=Some…, b=1234, c=Text, msg=Some text",
+            limit_to_string(
+                &Limits::new().max_value_len(4),
+                event([
+                    ("a", Value::from("Some text")),
+                    ("b", Value::from(1234)),
+                    ("c", Value::from("Text")),
+                    ("msg", Value::from("Some text")),
+                ]),
+            )
+        );
+    }
+
+    #[test]
+    fn limit_max_value_len_preserves_type() {
+        let limits = Limits::new().max_value_len(4);
+
+        let emitted = std::sync::Mutex::new(None);
+
+        limits.wrap(
+            crate::emitter::from_fn(|evt| {
+                *emitted.lock().unwrap() = evt.props().pull::<i32, _>("a");
+            }),
+            event([("a", Value::from(1234))]),
+        );
+
+        assert_eq!(Some(1234), emitted.into_inner().unwrap());
+    }
+
+    #[test]
+    fn limit_preserves_err() {
+        let limits = Limits::new().max_props(0).max_value_len(1);
+
+        let err = crate::err::chain(&std::io::Error::other("failed to connect"));
+
+        let emitted = std::sync::Mutex::new(None);
+
+        limits.wrap(
+            crate::emitter::from_fn(|evt| {
+                let err = evt.props().get(well_known::KEY_ERR).unwrap();
+
+                *emitted.lock().unwrap() = Some((
+                    err.downcast_ref::<crate::err::Chain>().is_some(),
+                    err.to_string(),
+                ));
+            }),
+            event([("a", Value::from(1)), (well_known::KEY_ERR, err.to_value())]),
+        );
+
+        assert_eq!(
+            Some((true, "failed to connect".to_string())),
+            emitted.into_inner().unwrap()
+        );
+    }
+
+    #[test]
+    fn limit_preserves_msg() {
+        let limits = Limits::new().max_props(2).max_key_len(4).max_value_len(6);
+
+        let emitted = std::sync::Mutex::new(None);
+
+        limits.wrap(
+            crate::emitter::from_fn(|evt| {
+                *emitted.lock().unwrap() = Some((evt.msg().to_string(), evt.tpl().to_string()));
+            }),
+            Event::new(
+                Path::new_raw("test"),
+                Template::parse("{a} {long_key} {c} {missing}").unwrap(),
+                crate::Empty,
+                [
+                    ("a", Value::from(1)),
+                    ("long_key", Value::from(2)),
+                    ("b", Value::from(3)),
+                    ("c", Value::from("Some text")),
+                ],
+            ),
+        );
+
+        // `long_key` is truncated and `c` is dropped, but they're still rendered with their limited values
+        assert_eq!(
+            Some((
+                "1 2 Some t… {missing}".to_string(),
+                "{a} 2 Some t… {missing}".to_string()
+            )),
+            emitted.into_inner().unwrap()
+        );
+    }
+
+    #[test]
+    fn limit_max_event_size() {
+        assert_eq!(
+            "a=1234, c=1, lvl=info, dropped_props=1",
+            limit_to_string(
+                &Limits::new().max_event_size(8),
+                event([
+                    ("a", Value::from(1234)),
+                    ("b", Value::from(1234)),
+                    ("c", Value::from(1)),
+                    ("lvl", Value::from("info")),
+                ]),
+            )
+        );
+    }
+
+    #[test]
+    fn limit_max_event_size_truncated() {
+        assert_eq!(
+            "a=Some…, b=Some…",
+            limit_to_string(
+                &Limits::new().max_value_len(4).max_event_size(16),
+                event([
+                    ("a", Value::from("Some text")),
+                    ("b", Value::from("Some text")),
+                ]),
+            )
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "sval")]
+    fn limit_max_depth() {
+        struct Map<const N: usize>([(&'static str, i32); N]);
+
+        impl<const N: usize> sval::Value for Map<N> {
+            fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
+                &'sval self,
+                stream: &mut S,
+            ) -> sval::Result {
+                stream.map_begin(Some(N))?;
+
+                for (k, v) in &self.0 {
+                    stream.map_key_begin()?;
+                    stream.value(*k)?;
+                    stream.map_key_end()?;
+
+                    stream.map_value_begin()?;
+                    stream.value(v)?;
+                    stream.map_value_end()?;
+                }
+
+                stream.map_end()
+            }
+        }
+
+        struct Nested(Map<1>);
+
+        impl sval::Value for Nested {
+            fn stream<'sval, S: sval::Stream<'sval> + ?Sized>(
+                &'sval self,
+                stream: &mut S,
+            ) -> sval::Result {
+                stream.seq_begin(Some(1))?;
+                stream.seq_value_begin()?;
+                stream.value(&self.0)?;
+                stream.seq_value_end()?;
+                stream.seq_end()
+            }
+        }
+
+        let flat = Map([("a", 1)]);
+        let nested = Nested(Map([("a", 1)]));
+
+        assert!(!exceeds_depth(&Value::from(1), 0));
+        assert!(!exceeds_depth(&Value::from_sval(&flat), 1));
+        assert!(exceeds_depth(&Value::from_sval(&flat), 0));
+        assert!(!exceeds_depth(&Value::from_sval(&nested), 2));
+        assert!(exceeds_depth(&Value::from_sval(&nested), 1));
+
+        let limits = Limits::new().max_depth(1);
+
+        let emitted = std::sync::Mutex::new((None, None));
+
+        limits.wrap(
+            crate::emitter::from_fn(|evt| {
+                *emitted.lock().unwrap() = (
+                    evt.props()
+                        .get("flat")
+                        .map(|v| v.to_borrowed_str().is_some()),
+                    evt.props()
+                        .get("nested")
+                        .map(|v| v.to_borrowed_str().is_some()),
+                );
+            }),
+            event([
+                ("flat", Value::from_sval(&flat)),
+                ("nested", Value::from_sval(&nested)),
+            ]),
+        );
+
+        assert_eq!((Some(false), Some(true)), emitted.into_inner().unwrap());
+    }
+}
@@ -694,6 +694,28 @@ pub trait __PrivateCaptureHook {
 
 impl<T: ?Sized> __PrivateCaptureHook for T {}
 
+/**
+An API to capture values as sensitive for consuming in a macro.
+
+Sensitive values need to own their value, so they're wrapped by `__private_sensitive`
+where they're bound in a macro. This hook then captures the wrapped value.
+*/
+pub trait __PrivateSensitiveCaptureHook {
+    fn __private_capture_as_sensitive(&self) -> Option<Value<'_>>;
+}
+
+#[cfg(feature = "std")]
+impl __PrivateSensitiveCaptureHook for crate::redact::Sensitive {
+    fn __private_capture_as_sensitive(&self) -> Option<Value<'_>> {
+        Some(self.to_value())
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn __private_sensitive(value: &(impl ToValue + ?Sized)) -> crate::redact::Sensitive {
+    crate::redact::Sensitive::new(value)
+}
+
 pub trait __PrivateFmtHook<'a> {
     fn __private_fmt_as_default(self) -> Self;
     fn __private_fmt_as(self, formatter: Formatter) -> Self;
@@ -22,12 +22,54 @@ use emit_core::{
 };
 
 #[cfg(feature = "alloc")]
-use emit_core::well_known::KEY_DIST_EXP_BUCKETS;
+use emit_core::well_known::{KEY_DIST_BUCKET_BOUNDS, KEY_DIST_BUCKET_COUNTS, KEY_DIST_EXP_BUCKETS};
 
 use crate::kind::Kind;
 
 pub use self::{sampler::Sampler, source::Source};
 
+#[cfg(feature = "alloc")]
+pub mod explicit;
+
+#[cfg(feature = "std")]
+pub mod instrument;
+
+#[cfg(feature = "std")]
+pub mod family;
+
+#[cfg(feature = "std")]
+pub mod convert;
+
+#[cfg(feature = "std")]
+pub mod span;
+
+#[cfg(feature = "std")]
+pub mod volume;
+
+#[cfg(feature = "tokio")]
+pub mod tokio;
+
+#[cfg(feature = "std")]
+pub use self::instrument::{Counter, Gauge, Histogram, Temporality, UpDownCounter};
+
+#[cfg(feature = "std")]
+pub use self::family::Family;
+
+#[cfg(feature = "std")]
+pub use self::convert::Convert;
+
+#[cfg(feature = "std")]
+pub use self::span::SpanMetrics;
+
+#[cfg(feature = "std")]
+pub use self::volume::EventVolume;
+
+#[cfg(feature = "std")]
+mod spawned;
+
+#[cfg(feature = "std")]
+pub use self::spawned::{ReportOnFlush, ReporterHandle};
+
 /**
 A diagnostic event that represents a metric sample.
 
@@ -356,6 +398,48 @@ impl<'a, P: Props> Metric<'a, P> {
     ) -> Metric<'a, And<(&'static str, exp::BucketSet), P>> {
         self.map_props(|props| (KEY_DIST_EXP_BUCKETS, dist_exp_buckets.into()).and_props(props))
     }
+
+    /**
+    Get the upper bounds of explicit histogram buckets.
+    */
+    #[cfg(feature = "alloc")]
+    pub fn dist_bucket_bounds(&self) -> Option<alloc::vec::Vec<f64>> {
+        self.props
+            .get(KEY_DIST_BUCKET_BOUNDS)
+            .and_then(|bounds| bounds.to_f64_sequence())
+    }
+
+    /**
+    Set the upper bounds of explicit histogram buckets.
+    */
+    #[cfg(feature = "alloc")]
+    pub fn with_dist_bucket_bounds(
+        self,
+        dist_bucket_bounds: impl Into<Value<'a>>,
+    ) -> Metric<'a, And<(&'static str, Value<'a>), P>> {
+        self.map_props(|props| (KEY_DIST_BUCKET_BOUNDS, dist_bucket_bounds.into()).and_props(props))
+    }
+
+    /**
+    Get the counts of explicit histogram buckets.
+    */
+    #[cfg(feature = "alloc")]
+    pub fn dist_bucket_counts(&self) -> Option<alloc::vec::Vec<u64>> {
+        self.props
+            .get(KEY_DIST_BUCKET_COUNTS)
+            .and_then(|counts| counts.to_u64_sequence())
+    }
+
+    /**
+    Set the counts of explicit histogram buckets.
+    */
+    #[cfg(feature = "alloc")]
+    pub fn with_dist_bucket_counts(
+        self,
+        dist_bucket_counts: impl Into<Value<'a>>,
+    ) -> Metric<'a, And<(&'static str, Value<'a>), P>> {
+        self.map_props(|props| (KEY_DIST_BUCKET_COUNTS, dist_bucket_counts.into()).and_props(props))
+    }
 }
 
 impl<'a, P: Props> fmt::Debug for Metric<'a, P> {
@@ -798,13 +882,26 @@ mod alloc_support {
         Produce a current sample for all metrics.
         */
         pub fn sample_metrics<S: sampler::Sampler>(&self, sampler: S) {
-            let sampler = TimeNormalizer::new(self.clock.now(), sampler);
+            self.sample_metrics_at(self.clock.now(), sampler)
+        }
+
+        pub(super) fn sample_metrics_at<S: sampler::Sampler>(
+            &self,
+            now: Option<Timestamp>,
+            sampler: S,
+        ) {
+            let sampler = TimeNormalizer::new(now, sampler);
 
             for source in &self.sources {
                 source.sample_metrics(&sampler);
             }
         }
 
+        #[cfg(feature = "std")]
+        pub(super) fn now(&self) -> Option<Timestamp> {
+            self.clock.now()
+        }
+
         /**
         Produce a current sample for all metrics, emitting them as diagnostic events to the given [`Emitter`].
         */
@@ -1635,6 +1732,17 @@ pub mod exp {
         libm::pow(2.0, libm::pow(2.0, -(scale as f64)))
     }
 
+    /**
+    Compute the maximum relative error of bucket midpoints at a given scale.
+
+    Any value `v` in a bucket with midpoint `p` satisfies `|v - p| <= relative_error(scale) * |p|`. The error is computed from γ by `(γ - 1) / (γ + 1)`.
+    */
+    pub const fn relative_error(scale: i32) -> f64 {
+        let gamma = gamma(scale);
+
+        (gamma - 1.0) / (gamma + 1.0)
+    }
+
     /**
     Compute the exponential bucket midpoint for the given input value at a given scale.
 
@@ -1758,13 +1866,19 @@ pub mod exp {
                 }
             }
 
+            impl Default for BucketSet {
+                fn default() -> Self {
+                    Self::new()
+                }
+            }
+
             impl BucketSet {
                 /**
                 Create a new empty `BucketSet`.
 
                 This method does not allocate.
                 */
-                pub fn new() -> Self {
+                pub const fn new() -> Self {
                     BucketSet {
                         buckets: BTreeMap::new(),
                         total: 0,
@@ -1985,6 +2099,33 @@ pub mod exp {
                     self.buckets.last_key_value().map(|(k, v)| (*k, *v))
                 }
 
+                /**
+                Estimate the value at quantile `q`, where `q` is between `0.0` and `1.0`.
+
+                The result is the midpoint of the bucket containing the `⌈q * total⌉`th smallest value. If the buckets were computed at a given `scale` then the value `v` at quantile `q` is within [`crate::metric::exp::relative_error`] of the result `p`, so `|v - p| <= relative_error(scale) * |p|`.
+
+                This method returns `None` if the set is empty or `q` is outside the range `0.0..=1.0`.
+                */
+                pub fn quantile(&self, q: f64) -> Option<Point> {
+                    if self.total == 0 || !(0.0..=1.0).contains(&q) {
+                        return None;
+                    }
+
+                    let rank = (crate::platform::libm::ceil(q * self.total as f64) as u64)
+                        .clamp(1, self.total);
+
+                    let mut seen = 0;
+                    for (bucket, count) in self {
+                        seen += count;
+
+                        if seen >= rank {
+                            return Some(bucket);
+                        }
+                    }
+
+                    self.last().map(|(bucket, _)| bucket)
+                }
+
                 /**
                 Iterate over buckets in order.
                 */
@@ -2669,6 +2810,28 @@ pub mod exp {
                     assert_eq!((Point::new(1.0), 2), set.last().unwrap());
                 }
 
+                #[test]
+                fn bucket_set_quantile() {
+                    let set = BucketSet::from_iter([
+                        (Point::new(-1.0), 1),
+                        (Point::new(1.0), 2),
+                        (Point::new(2.0), 1),
+                        (Point::new(4.0), 6),
+                    ]);
+
+                    assert_eq!(Some(Point::new(-1.0)), set.quantile(0.0));
+                    assert_eq!(Some(Point::new(-1.0)), set.quantile(0.1));
+                    assert_eq!(Some(Point::new(1.0)), set.quantile(0.2));
+                    assert_eq!(Some(Point::new(2.0)), set.quantile(0.4));
+                    assert_eq!(Some(Point::new(4.0)), set.quantile(0.5));
+                    assert_eq!(Some(Point::new(4.0)), set.quantile(1.0));
+
+                    assert_eq!(None, set.quantile(-0.1));
+                    assert_eq!(None, set.quantile(1.1));
+                    assert_eq!(None, set.quantile(f64::NAN));
+                    assert_eq!(None, BucketSet::new().quantile(0.5));
+                }
+
                 #[test]
                 fn bucket_set_remap() {
                     let mut set = BucketSet::new();
@@ -2961,7 +3124,7 @@ pub mod exp {
 
             The distribution uses a large scale initially. Whenever the number of buckets would overflow `max_buckets`, the scale is decremented and the buckets are rescaled. This reduces the number of buckets by half while also decreasing precision.
             */
-            pub fn new(max_scale: i32, max_buckets: usize) -> Self {
+            pub const fn new(max_scale: i32, max_buckets: usize) -> Self {
                 Distribution {
                     max_buckets,
                     max_scale,
@@ -3019,6 +3182,55 @@ pub mod exp {
                 }
             }
 
+            /**
+            Merge the observations from `other` into this distribution.
+
+            If `other` uses a smaller scale then this distribution will be rescaled to match it. The merged distribution may also be rescaled further to fit within its maximum number of buckets.
+
+            # Panics
+
+            This method will panic if merging the counts for a bucket would overflow.
+            */
+            pub fn merge(&mut self, other: &Distribution) {
+                if other.count() == 0 {
+                    return;
+                }
+
+                if other.scale < self.scale {
+                    self.scale = other.scale;
+                    self.buckets
+                        .remap(|value| midpoint(value.get(), self.scale));
+                }
+
+                for (value, count) in other.buckets() {
+                    self.buckets
+                        .observe_all(midpoint(value.get(), self.scale), count);
+                }
+
+                // Merge the extrema
+                if let Some(other_min) = other.min {
+                    self.min = self
+                        .min
+                        .map(|min| cmp::min_by(min, other_min, |a, b| a.total_cmp(b)))
+                        .or(Some(other_min));
+                }
+                if let Some(other_max) = other.max {
+                    self.max = self
+                        .max
+                        .map(|max| cmp::max_by(max, other_max, |a, b| a.total_cmp(b)))
+                        .or(Some(other_max));
+                }
+                if let Some(other_sum) = other.sum {
+                    self.sum = self.sum.map(|sum| sum + other_sum).or(Some(other_sum));
+                }
+
+                while self.buckets.len() > self.max_buckets {
+                    self.scale -= 1;
+                    self.buckets
+                        .remap(|value| midpoint(value.get(), self.scale));
+                }
+            }
+
             /**
             Clear the distribution of any data so it can be re-used.
 
@@ -3078,6 +3290,29 @@ pub mod exp {
                 self.sum
             }
 
+            /**
+            Estimate the value at quantile `q`, where `q` is between `0.0` and `1.0`.
+
+            The estimate is within [`relative_error`] of the true value at the current [`Distribution::scale`], and is clamped to the observed [`Distribution::min`] and [`Distribution::max`]. The quantiles `0.0` and `1.0` are exactly the observed minimum and maximum. See [`BucketSet::quantile`] for details.
+
+            This method returns `None` if no values have been seen or `q` is outside the range `0.0..=1.0`.
+            */
+            pub fn quantile(&self, q: f64) -> Option<f64> {
+                let estimate = self.buckets.quantile(q)?.get();
+
+                if q == 0.0 && self.min.is_some() {
+                    return self.min;
+                }
+                if q == 1.0 && self.max.is_some() {
+                    return self.max;
+                }
+
+                match (self.min, self.max) {
+                    (Some(min), Some(max)) if min <= max => Some(estimate.clamp(min, max)),
+                    _ => Some(estimate),
+                }
+            }
+
             /**
             Get the current scale used to bucket values.
             */
@@ -3172,6 +3407,88 @@ pub mod exp {
                 assert_eq!(0, distribution.count());
             }
 
+            #[test]
+            fn distribution_merge() {
+                let mut a = Distribution::new(10, 10);
+                let mut b = Distribution::new(10, 10);
+                let mut expected = Distribution::new(10, 10);
+
+                for i in 0..5 {
+                    a.observe(i as f64);
+                    expected.observe(i as f64);
+                }
+
+                for i in 3..9 {
+                    b.observe(i as f64 * 1.5);
+                    expected.observe(i as f64 * 1.5);
+                }
+
+                a.merge(&b);
+
+                assert_eq!(expected.count(), a.count());
+                assert_eq!(expected.min(), a.min());
+                assert_eq!(expected.max(), a.max());
+                assert_eq!(expected.sum(), a.sum());
+                assert_eq!(expected.count(), a.buckets().total());
+                assert!(a.scale() < a.max_scale());
+                assert!(a.buckets().len() <= a.max_buckets());
+
+                a.merge(&Distribution::default());
+
+                assert_eq!(expected.count(), a.count());
+            }
+
+            #[test]
+            fn distribution_merge_rescale() {
+                let mut a = Distribution::new(10, 10);
+                let mut b = Distribution::new(10, 10);
+
+                a.observe(1.0);
+
+                for i in 0..100 {
+                    b.observe(i as f64);
+                }
+
+                assert!(b.scale() < a.scale());
+
+                a.merge(&b);
+
+                assert_eq!(101, a.count());
+                assert_eq!(b.scale(), a.scale());
+                assert!(a.buckets().len() <= a.max_buckets());
+            }
+
+            #[test]
+            fn distribution_quantile() {
+                for scale in [0, 2, 8, 20] {
+                    let mut distribution = Distribution::new(scale, 160);
+
+                    let values = (1..=1000).map(|i| i as f64 * 1.7).collect::<Vec<_>>();
+
+                    for value in &values {
+                        distribution.observe(*value);
+                    }
+
+                    let error = relative_error(distribution.scale());
+
+                    for q in [0.5, 0.95, 0.99] {
+                        let expected = values[(q * values.len() as f64).ceil() as usize - 1];
+                        let actual = distribution.quantile(q).unwrap();
+
+                        assert!(
+                            (expected - actual).abs() <= error * actual.abs(),
+                            "expected {q} at scale {scale} to be {expected} (within {error}), but got {actual}"
+                        );
+                    }
+
+                    // The extremes are the observed range
+                    assert_eq!(Some(1.7), distribution.quantile(0.0));
+                    assert_eq!(Some(1700.0), distribution.quantile(1.0));
+                }
+
+                assert_eq!(None, Distribution::default().quantile(0.5));
+            }
+
             #[test]
             fn distribution_rescale() {
                 let mut distribution = Distribution::new(10, 10);
@@ -3289,6 +3606,22 @@ pub mod exp {
             assert_eq!(point, Point::from_value(point.to_value()).unwrap());
         }
 
+        #[test]
+        fn compute_relative_error() {
+            for scale in [-2, 0, 2, 8, 20] {
+                let gamma = gamma(scale);
+                let error = relative_error(scale);
+
+                // The edges of a bucket are the furthest values from its midpoint
+                let lower = gamma.powi(3);
+                let upper = lower * gamma;
+                let midpoint = lower.midpoint(upper);
+
+                assert!(((midpoint - lower) / midpoint - error).abs() < 1e-9);
+                assert!(((upper - midpoint) / midpoint - error).abs() < 1e-9);
+            }
+        }
+
         #[test]
         fn compute_midpoints() {
             let cases = [
@@ -3608,6 +3941,16 @@ mod tests {
 
             assert_eq!(-1, metric.dist_exp_scale().unwrap());
             assert_eq!(set, metric.dist_exp_buckets().unwrap());
+
+            let bounds = vec![1.0, 5.0];
+            let counts = vec![1u64, 2, 3];
+
+            let metric = metric
+                .with_dist_bucket_bounds(&bounds)
+                .with_dist_bucket_counts(&counts);
+
+            assert_eq!(bounds, metric.dist_bucket_bounds().unwrap());
+            assert_eq!(counts, metric.dist_bucket_counts().unwrap());
         }
     }
 
@@ -0,0 +1,1068 @@
+/*!
+The [`Convert`] type.
+
+Metric samples are either cumulative, where each sample reports the total since some fixed point, or delta, where each sample reports the change since the previous one. See [`crate::metric`] for how temporality is represented in the metric data model. Some backends only accept one temporality or the other. [`Convert`] is a [`crate::emitter::wrapping::Wrapping`] that converts metric samples into a single temporality before they reach an emitter:
+
+```
+fn main() {
+    let rt = emit::setup()
+        .emit_to(emit::emitter::from_fn(|evt| println!("{evt:?}")))
+        .map_emitter(|emitter| {
+            emit::emitter::wrap(emitter, emit::metric::Convert::to_delta())
+        })
+        .init();
+
+    // Your app code goes here
+
+    rt.blocking_flush(std::time::Duration::from_secs(5));
+}
+```
+
+Only metric samples with a `count` or `sum` aggregation are converted. Other events, and metric samples that are already in the target temporality, are passed through unchanged.
+
+# Streams
+
+Conversion needs to remember the previous sample for each metric. Samples are grouped into streams by their module, name, aggregation, and any other properties besides their value, distribution, and the `trace_id`, `span_id`, and `span_parent` of any span they were sampled within. The number of streams is capped by [`Convert::with_max_streams`]. When the cap is reached, the least recently used stream is forgotten.
+
+# Converting cumulative samples to deltas
+
+The first sample in a stream is used as a baseline and isn't emitted, since there's no previous sample to compute a delta from. Each following sample is emitted with an extent covering the time since the previous one, and a value that's the difference between them.
+
+If the value of a `count` sample decreases then the underlying counter is assumed to have restarted, and the sample is emitted as-is instead of as a difference. Samples that are older than the previous one in their stream are discarded.
+
+Distributions are converted by subtracting the counts of their buckets. The `dist_min` and `dist_max` of cumulative samples can't be converted, so they're only kept when a counter restarts.
+
+# Converting delta samples to cumulative ones
+
+Each sample is added to a running total for its stream, and emitted with a point extent at its end. Distributions are converted by merging the counts of their buckets. Exponential buckets are rescaled as needed to fit within [`crate::metric::exp::Distribution::DEFAULT_MAX_BUCKETS`].
+*/
+
+use core::ops::ControlFlow;
+use std::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    sync::Mutex,
+    vec::Vec,
+};
+
+use emit_core::{
+    emitter::{Emitter, wrapping::Wrapping},
+    event::{Event, ToEvent},
+    extent::Extent,
+    props::Props,
+    str::{Str, ToStr},
+    timestamp::Timestamp,
+    value::{ToValue, Value},
+    well_known::{
+        KEY_DIST_BUCKET_BOUNDS, KEY_DIST_BUCKET_COUNTS, KEY_DIST_COUNT, KEY_DIST_EXP_BUCKETS,
+        KEY_DIST_EXP_SCALE, KEY_DIST_MAX, KEY_DIST_MIN, KEY_DIST_SUM, KEY_EVT_KIND, KEY_METRIC_AGG,
+        KEY_METRIC_VALUE, KEY_SPAN_ID, KEY_SPAN_PARENT, KEY_TRACE_ID, METRIC_AGG_COUNT,
+        METRIC_AGG_SUM,
+    },
+};
+
+use crate::{
+    kind::Kind,
+    metric::{Temporality, exp},
+};
+
+/**
+A [`Wrapping`] that converts metric samples into a single [`Temporality`].
+
+See the [`mod@crate::metric::convert`] module for details.
+*/
+pub struct Convert {
+    target: Temporality,
+    max_streams: usize,
+    streams: Mutex<Streams>,
+}
+
+#[derive(Default)]
+struct Streams {
+    tick: u64,
+    streams: BTreeMap<StreamKey, Stream>,
+}
+
+// The module and rendered properties of a stream
+type StreamKey = (String, Box<[(String, String)]>);
+
+struct Stream {
+    last_used: u64,
+    ts: Timestamp,
+    sample: Sample,
+}
+
+#[derive(Clone)]
+struct Sample {
+    is_count: bool,
+    value: Number,
+    count: Option<u64>,
+    sum: Option<Number>,
+    min: Option<f64>,
+    max: Option<f64>,
+    buckets: Option<Buckets>,
+}
+
+#[derive(Clone)]
+enum Buckets {
+    Exp { scale: i32, buckets: exp::BucketSet },
+    Explicit { bounds: Vec<f64>, counts: Vec<u64> },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Number {
+    Int(i128),
+    Float(f64),
+}
+
+impl Convert {
+    /**
+    The default maximum number of streams to track.
+    */
+    pub const DEFAULT_MAX_STREAMS: usize = 2000;
+
+    /**
+    Convert metric samples into the given `target` temporality.
+    */
+    pub const fn new(target: Temporality) -> Self {
+        Convert {
+            target,
+            max_streams: Self::DEFAULT_MAX_STREAMS,
+            streams: Mutex::new(Streams {
+                tick: 0,
+                streams: BTreeMap::new(),
+            }),
+        }
+    }
+
+    /**
+    Convert cumulative metric samples into deltas.
+    */
+    pub const fn to_delta() -> Self {
+        Self::new(Temporality::Delta)
+    }
+
+    /**
+    Convert delta metric samples into cumulative ones.
+    */
+    pub const fn to_cumulative() -> Self {
+        Self::new(Temporality::Cumulative)
+    }
+
+    /**
+    Set the maximum number of streams to track.
+
+    Once the maximum is reached, the least recently used stream is forgotten to make room for new ones. The default maximum is [`Convert::DEFAULT_MAX_STREAMS`].
+    */
+    pub const fn with_max_streams(mut self, max_streams: usize) -> Self {
+        self.max_streams = max_streams;
+        self
+    }
+
+    /**
+    Get the temporality metric samples are converted into.
+    */
+    pub fn target(&self) -> Temporality {
+        self.target
+    }
+
+    // Returns `None` if the event should be passed through unchanged,
+    // or `Some(None)` if it should be discarded
+    fn convert(&self, evt: &Event<impl Props>) -> Option<Option<(Extent, Sample, bool)>> {
+        if evt.props().pull::<Kind, _>(KEY_EVT_KIND) != Some(Kind::Metric) {
+            return None;
+        }
+
+        let extent = evt.extent()?;
+        let sample = Sample::from_props(evt.props())?;
+
+        let key = stream_key(evt);
+
+        let mut streams = self.streams.lock().unwrap_or_else(|e| e.into_inner());
+        streams.tick += 1;
+        let tick = streams.tick;
+
+        match (self.target, extent.as_range()) {
+            // Cumulative to delta
+            (Temporality::Delta, None) => {
+                let ts = *extent.as_point();
+
+                let Some(stream) = streams.streams.get_mut(&key) else {
+                    streams.insert(key, Stream::new(tick, ts, sample), self.max_streams);
+
+                    // The first sample is used as a baseline
+                    return Some(None);
+                };
+
+                stream.last_used = tick;
+
+                if ts <= stream.ts {
+                    return Some(None);
+                }
+
+                let (delta, is_reset) = match sample.sub(&stream.sample) {
+                    Some(delta) => (delta, false),
+                    None => (sample.clone(), true),
+                };
+
+                let extent = Extent::range(stream.ts..ts);
+
+                stream.ts = ts;
+                stream.sample = sample;
+
+                Some(Some((extent, delta, is_reset)))
+            }
+            // Delta to cumulative
+            (Temporality::Cumulative, Some(range)) => {
+                let ts = range.end;
+
+                let total = match streams.streams.get_mut(&key) {
+                    Some(stream) => {
+                        stream.last_used = tick;
+                        stream.ts = ts;
+                        stream.sample.add(&sample);
+
+                        stream.sample.clone()
+                    }
+                    None => {
+                        streams.insert(
+                            key,
+                            Stream::new(tick, ts, sample.clone()),
+                            self.max_streams,
+                        );
+
+                        sample
+                    }
+                };
+
+                Some(Some((Extent::point(ts), total, true)))
+            }
+            // Already in the target temporality
+            _ => None,
+        }
+    }
+}
+
+impl Wrapping for Convert {
+    fn wrap<O: Emitter, E: ToEvent>(&self, output: O, evt: E) {
+        let evt = evt.to_event();
+
+        match self.convert(&evt) {
+            Some(Some((extent, sample, with_extrema))) => output.emit(Event::new(
+                evt.mdl().by_ref(),
+                evt.tpl().by_ref(),
+                extent,
+                ConvertedProps {
+                    sample,
+                    with_extrema,
+                    props: evt.props(),
+                },
+            )),
+            Some(None) => (),
+            None => output.emit(evt),
+        }
+    }
+}
+
+impl Streams {
+    fn insert(&mut self, key: StreamKey, stream: Stream, max_streams: usize) {
+        if max_streams == 0 {
+            return;
+        }
+
+        if self.streams.len() >= max_streams {
+            // Forget the least recently used stream to make room
+            if let Some(lru) = self
+                .streams
+                .iter()
+                .min_by_key(|(_, stream)| stream.last_used)
+                .map(|(key, _)| key.clone())
+            {
+                self.streams.remove(&lru);
+            }
+        }
+
+        self.streams.insert(key, stream);
+    }
+}
+
+impl Stream {
+    fn new(last_used: u64, ts: Timestamp, sample: Sample) -> Self {
+        Stream {
+            last_used,
+            ts,
+            sample,
+        }
+    }
+}
+
+fn is_sample_key(key: &str) -> bool {
+    matches!(
+        key,
+        KEY_EVT_KIND
+            | KEY_METRIC_VALUE
+            | KEY_DIST_COUNT
+            | KEY_DIST_SUM
+            | KEY_DIST_MIN
+            | KEY_DIST_MAX
+            | KEY_DIST_EXP_SCALE
+            | KEY_DIST_EXP_BUCKETS
+            | KEY_DIST_BUCKET_BOUNDS
+            | KEY_DIST_BUCKET_COUNTS
+    )
+}
+
+// Samples taken within a span carry its ids, which change between samples
+fn is_span_key(key: &str) -> bool {
+    matches!(key, KEY_TRACE_ID | KEY_SPAN_ID | KEY_SPAN_PARENT)
+}
+
+fn stream_key(evt: &Event<impl Props>) -> StreamKey {
+    let mut props = Vec::new();
+
+    let _ = evt.props().for_each(|k, v| {
+        if !is_sample_key(k.get()) && !is_span_key(k.get()) {
+            props.push((k.get().to_string(), v.to_string()));
+        }
+
+        ControlFlow::Continue(())
+    });
+
+    props.sort();
+    props.dedup_by(|a, b| a.0 == b.0);
+
+    (evt.mdl().to_string(), props.into_boxed_slice())
+}
+
+impl Sample {
+    fn from_props(props: impl Props) -> Option<Self> {
+        let is_count = match props.get(KEY_METRIC_AGG)?.to_cow_str()?.as_ref() {
+            METRIC_AGG_COUNT => true,
+            METRIC_AGG_SUM => false,
+            _ => return None,
+        };
+
+        let buckets = if let (Some(scale), Some(buckets)) = (
+            props.pull::<i32, _>(KEY_DIST_EXP_SCALE),
+            props.pull::<exp::BucketSet, _>(KEY_DIST_EXP_BUCKETS),
+        ) {
+            Some(Buckets::Exp { scale, buckets })
+        } else if let (Some(bounds), Some(counts)) = (
+            props
+                .get(KEY_DIST_BUCKET_BOUNDS)
+                .and_then(|bounds| bounds.to_f64_sequence()),
+            props
+                .get(KEY_DIST_BUCKET_COUNTS)
+                .and_then(|counts| counts.to_u64_sequence()),
+        ) {
+            Some(Buckets::Explicit { bounds, counts })
+        } else {
+            None
+        };
+
+        Some(Sample {
+            is_count,
+            value: Number::from_value(props.get(KEY_METRIC_VALUE)?)?,
+            count: props.pull(KEY_DIST_COUNT),
+            sum: props.get(KEY_DIST_SUM).and_then(Number::from_value),
+            min: props.pull(KEY_DIST_MIN),
+            max: props.pull(KEY_DIST_MAX),
+            buckets,
+        })
+    }
+
+    // Compute `self - prev`, returning `None` if the underlying counter has restarted
+    fn sub(&self, prev: &Sample) -> Option<Sample> {
+        let value = self.value.sub(prev.value);
+
+        if self.is_count && value.is_negative() {
+            return None;
+        }
+
+        let count = match (self.count, prev.count) {
+            (Some(count), Some(prev)) => Some(count.checked_sub(prev)?),
+            (count, _) => count,
+        };
+
+        let buckets = match (&self.buckets, &prev.buckets) {
+            (Some(buckets), Some(prev)) => Some(buckets.sub(prev)?),
+            (buckets, _) => buckets.clone(),
+        };
+
+        Some(Sample {
+            is_count: self.is_count,
+            value,
+            count,
+            sum: match (self.sum, prev.sum) {
+                (Some(sum), Some(prev)) => Some(sum.sub(prev)),
+                (sum, _) => sum,
+            },
+            min: None,
+            max: None,
+            buckets,
+        })
+    }
+
+    // Accumulate `next` into `self`
+    fn add(&mut self, next: &Sample) {
+        self.value = self.value.add(next.value);
+
+        self.count = match (self.count, next.count) {
+            (Some(count), Some(next)) => Some(count.saturating_add(next)),
+            (count, next) => count.or(next),
+        };
+        self.sum = match (self.sum, next.sum) {
+            (Some(sum), Some(next)) => Some(sum.add(next)),
+            (sum, next) => sum.or(next),
+        };
+        self.min = match (self.min, next.min) {
+            (Some(min), Some(next)) => Some(min.min(next)),
+            (min, next) => min.or(next),
+        };
+        self.max = match (self.max, next.max) {
+            (Some(max), Some(next)) => Some(max.max(next)),
+            (max, next) => max.or(next),
+        };
+
+        match (&mut self.buckets, &next.buckets) {
+            (Some(buckets), Some(next)) => buckets.add(next),
+            (buckets @ None, Some(next)) => *buckets = Some(next.clone()),
+            _ => (),
+        }
+    }
+}
+
+impl Buckets {
+    fn sub(&self, prev: &Buckets) -> Option<Buckets> {
+        match (self, prev) {
+            (
+                Buckets::Exp { scale, buckets },
+                Buckets::Exp {
+                    scale: prev_scale,
+                    buckets: prev_buckets,
+                },
+            ) => {
+                // Compare buckets at the smaller of the two scales
+                let scale = (*scale).min(*prev_scale);
+                let buckets = rescale(buckets, scale);
+                let prev = rescale(prev_buckets, scale);
+
+                let mut delta = exp::BucketSet::new();
+
+                for (point, prev_count) in &prev {
+                    buckets.get(point)?.checked_sub(prev_count)?;
+                }
+
+                for (point, count) in &buckets {
+                    let count = count.checked_sub(prev.get(point).unwrap_or(0))?;
+
+                    if count > 0 {
+                        delta.observe_all(point, count);
+                    }
+                }
+
+                Some(Buckets::Exp {
+                    scale,
+                    buckets: delta,
+                })
+            }
+            (
+                Buckets::Explicit { bounds, counts },
+                Buckets::Explicit {
+                    bounds: prev_bounds,
+                    counts: prev_counts,
+                },
+            ) if bounds == prev_bounds && counts.len() == prev_counts.len() => {
+                Some(Buckets::Explicit {
+                    bounds: bounds.clone(),
+                    counts: counts
+                        .iter()
+                        .zip(prev_counts)
+                        .map(|(count, prev)| count.checked_sub(*prev))
+                        .collect::<Option<_>>()?,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn add(&mut self, next: &Buckets) {
+        match (&mut *self, next) {
+            (
+                Buckets::Exp { scale, buckets },
+                Buckets::Exp {
+                    scale: next_scale,
+                    buckets: next_buckets,
+                },
+            ) => {
+                if *next_scale < *scale {
+                    *scale = *next_scale;
+                    *buckets = rescale(buckets, *scale);
+                }
+
+                for (point, count) in next_buckets {
+                    buckets.observe_all(exp::midpoint(point.get(), *scale), count);
+                }
+
+                while buckets.len() > exp::Distribution::DEFAULT_MAX_BUCKETS {
+                    *scale -= 1;
+                    *buckets = rescale(buckets, *scale);
+                }
+            }
+            (
+                Buckets::Explicit { bounds, counts },
+                Buckets::Explicit {
+                    bounds: next_bounds,
+                    counts: next_counts,
+                },
+            ) if bounds == next_bounds && counts.len() == next_counts.len() => {
+                for (count, next) in counts.iter_mut().zip(next_counts) {
+                    *count = count.saturating_add(*next);
+                }
+            }
+            // The buckets are incompatible, so start again from the new ones
+            _ => *self = next.clone(),
+        }
+    }
+}
+
+fn rescale(buckets: &exp::BucketSet, scale: i32) -> exp::BucketSet {
+    let mut rescaled = buckets.clone();
+    rescaled.remap(|point| exp::midpoint(point.get(), scale));
+
+    rescaled
+}
+
+impl Number {
+    fn from_value(value: Value) -> Option<Self> {
+        if let Some(value) = value.by_ref().cast::<i128>() {
+            return Some(Number::Int(value));
+        }
+
+        value.cast::<f64>().map(Number::Float)
+    }
+
+    fn is_negative(&self) -> bool {
+        match self {
+            Number::Int(value) => *value < 0,
+            Number::Float(value) => *value < 0.0,
+        }
+    }
+
+    fn as_f64(&self) -> f64 {
+        match self {
+            Number::Int(value) => *value as f64,
+            Number::Float(value) => *value,
+        }
+    }
+
+    fn sub(&self, other: Number) -> Number {
+        match (self, other) {
+            (Number::Int(a), Number::Int(b)) => a
+                .checked_sub(b)
+                .map(Number::Int)
+                .unwrap_or(Number::Float(*a as f64 - b as f64)),
+            (a, b) => Number::Float(a.as_f64() - b.as_f64()),
+        }
+    }
+
+    fn add(&self, other: Number) -> Number {
+        match (self, other) {
+            (Number::Int(a), Number::Int(b)) => a
+                .checked_add(b)
+                .map(Number::Int)
+                .unwrap_or(Number::Float(*a as f64 + b as f64)),
+            (a, b) => Number::Float(a.as_f64() + b.as_f64()),
+        }
+    }
+}
+
+impl ToValue for Number {
+    fn to_value(&self) -> Value<'_> {
+        match *self {
+            Number::Int(value) => {
+                if let Ok(value) = u64::try_from(value) {
+                    Value::from(value)
+                } else if let Ok(value) = i64::try_from(value) {
+                    Value::from(value)
+                } else {
+                    Value::from(value)
+                }
+            }
+            Number::Float(value) => Value::from(value),
+        }
+    }
+}
+
+struct ConvertedProps<P> {
+    sample: Sample,
+    with_extrema: bool,
+    props: P,
+}
+
+impl<P: Props> Props for ConvertedProps<P> {
+    fn for_each<'kv, F: FnMut(Str<'kv>, Value<'kv>) -> ControlFlow<()>>(
+        &'kv self,
+        mut for_each: F,
+    ) -> ControlFlow<()> {
+        let sample = &self.sample;
+
+        for_each(KEY_METRIC_VALUE.to_str(), sample.value.to_value())?;
+
+        if let Some(count) = sample.count {
+            for_each(KEY_DIST_COUNT.to_str(), count.into())?;
+        }
+        if let Some(sum) = &sample.sum {
+            for_each(KEY_DIST_SUM.to_str(), sum.to_value())?;
+        }
+        if self.with_extrema {
+            if let Some(min) = sample.min {
+                for_each(KEY_DIST_MIN.to_str(), min.into())?;
+            }
+            if let Some(max) = sample.max {
+                for_each(KEY_DIST_MAX.to_str(), max.into())?;
+            }
+        }
+
+        match &sample.buckets {
+            Some(Buckets::Exp { scale, buckets }) => {
+                for_each(KEY_DIST_EXP_SCALE.to_str(), (*scale).into())?;
+                for_each(KEY_DIST_EXP_BUCKETS.to_str(), buckets.to_value())?;
+            }
+            Some(Buckets::Explicit { bounds, counts }) => {
+                for_each(KEY_DIST_BUCKET_BOUNDS.to_str(), bounds.to_value())?;
+                for_each(KEY_DIST_BUCKET_COUNTS.to_str(), counts.to_value())?;
+            }
+            None => (),
+        }
+
+        self.props.for_each(|k, v| {
+            if is_sample_key(k.get()) && k.get() != KEY_EVT_KIND {
+                return ControlFlow::Continue(());
+            }
+
+            for_each(k, v)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    use emit_core::{emitter, path::Path};
+
+    use crate::{metric::Metric, testing::Capture};
+
+    fn ts(secs: u64) -> Timestamp {
+        Timestamp::from_unix(Duration::from_secs(secs)).unwrap()
+    }
+
+    fn emit(convert: &Convert, capture: &Capture, metric: Metric<impl Props>) {
+        emitter::wrap(capture, convert).emit(metric);
+    }
+
+    fn count(extent: impl emit_core::extent::ToExtent, value: u64) -> Metric<'static, impl Props> {
+        Metric::new(
+            Path::new_raw("test"),
+            extent,
+            [
+                ("metric_name", Value::from("test")),
+                ("metric_agg", Value::from("count")),
+                ("metric_value", Value::from(value)),
+            ],
+        )
+    }
+
+    fn values(capture: &Capture) -> Vec<(Option<Timestamp>, Timestamp, u64)> {
+        capture
+            .events()
+            .iter()
+            .map(|evt| {
+                let extent = evt.extent().unwrap();
+
+                (
+                    extent.as_range().map(|range| range.start),
+                    *extent.as_point(),
+                    evt.props().pull::<u64, _>(KEY_METRIC_VALUE).unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn cumulative_to_delta() {
+        let convert = Convert::to_delta();
+        let capture = Capture::new();
+
+        emit(&convert, &capture, count(ts(1), 5));
+        emit(&convert, &capture, count(ts(2), 8));
+        emit(&convert, &capture, count(ts(3), 8));
+        emit(&convert, &capture, count(ts(4), 12));
+
+        assert_eq!(
+            vec![
+                (Some(ts(1)), ts(2), 3),
+                (Some(ts(2)), ts(3), 0),
+                (Some(ts(3)), ts(4), 4),
+            ],
+            values(&capture)
+        );
+    }
+
+    #[test]
+    fn cumulative_to_delta_reset() {
+        let convert = Convert::to_delta();
+        let capture = Capture::new();
+
+        emit(&convert, &capture, count(ts(1), 5));
+        emit(&convert, &capture, count(ts(2), 8));
+        // The counter restarted
+        emit(&convert, &capture, count(ts(3), 2));
+        emit(&convert, &capture, count(ts(4), 3));
+
+        assert_eq!(
+            vec![
+                (Some(ts(1)), ts(2), 3),
+                (Some(ts(2)), ts(3), 2),
+                (Some(ts(3)), ts(4), 1),
+            ],
+            values(&capture)
+        );
+    }
+
+    #[test]
+    fn cumulative_to_delta_out_of_order() {
+        let convert = Convert::to_delta();
+        let capture = Capture::new();
+
+        emit(&convert, &capture, count(ts(2), 5));
+        emit(&convert, &capture, count(ts(1), 3));
+        emit(&convert, &capture, count(ts(3), 6));
+
+        assert_eq!(vec![(Some(ts(2)), ts(3), 1)], values(&capture));
+    }
+
+    #[test]
+    fn cumulative_to_delta_sum() {
+        let convert = Convert::to_delta();
+        let capture = Capture::new();
+
+        for (secs, value) in [(1, 5), (2, 3), (3, 4)] {
+            emit(
+                &convert,
+                &capture,
+                Metric::new(
+                    Path::new_raw("test"),
+                    ts(secs),
+                    [
+                        ("metric_name", Value::from("test")),
+                        ("metric_agg", Value::from("sum")),
+                        ("metric_value", Value::from(value)),
+                    ],
+                ),
+            );
+        }
+
+        let values = capture
+            .events()
+            .iter()
+            .map(|evt| evt.props().pull::<i64, _>(KEY_METRIC_VALUE).unwrap())
+            .collect::<Vec<_>>();
+
+        // Sums may decrease without restarting
+        assert_eq!(vec![-2, 1], values);
+    }
+
+    #[test]
+    fn delta_to_cumulative() {
+        let convert = Convert::to_cumulative();
+        let capture = Capture::new();
+
+        emit(&convert, &capture, count(ts(0)..ts(1), 5));
+        emit(&convert, &capture, count(ts(1)..ts(2), 3));
+        emit(&convert, &capture, count(ts(2)..ts(3), 0));
+
+        assert_eq!(
+            vec![(None, ts(1), 5), (None, ts(2), 8), (None, ts(3), 8),],
+            values(&capture)
+        );
+    }
+
+    #[test]
+    fn passthrough() {
+        let convert = Convert::to_delta();
+        let capture = Capture::new();
+
+        // Already a delta
+        emit(&convert, &capture, count(ts(0)..ts(1), 5));
+
+        // Not a count or sum
+        emit(
+            &convert,
+            &capture,
+            Metric::new(
+                Path::new_raw("test"),
+                ts(1),
+                [
+                    ("metric_name", Value::from("test")),
+                    ("metric_agg", Value::from("last")),
+                    ("metric_value", Value::from(3)),
+                ],
+            ),
+        );
+
+        // Not a metric
+        emitter::wrap(&capture, &convert).emit(Event::new(
+            Path::new_raw("test"),
+            emit_core::template::Template::literal("not a metric"),
+            ts(1),
+            [("metric_agg", "count")],
+        ));
+
+        assert_eq!(3, capture.events().len());
+    }
+
+    #[test]
+    fn streams_by_props() {
+        let convert = Convert::to_cumulative();
+        let capture = Capture::new();
+
+        for (route, value) in [("a", 1), ("b", 2), ("a", 3), ("b", 4)] {
+            emit(
+                &convert,
+                &capture,
+                count(ts(0)..ts(1), value)
+                    .map_props(|props| emit_core::props::Props::and_props(("route", route), props)),
+            );
+        }
+
+        let values = capture
+            .events()
+            .iter()
+            .map(|evt| {
+                (
+                    evt.props().get("route").unwrap().to_string(),
+                    evt.props().pull::<u64, _>(KEY_METRIC_VALUE).unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![
+                ("a".to_string(), 1),
+                ("b".to_string(), 2),
+                ("a".to_string(), 4),
+                ("b".to_string(), 6)
+            ],
+            values
+        );
+    }
+
+    #[test]
+    fn streams_ignore_span_ids() {
+        let convert = Convert::to_delta();
+        let capture = Capture::new();
+
+        // Each sample is taken within a different span in the same trace
+        for (span_id, extent, value) in [
+            ("00f067aa0ba902b7", ts(1), 5),
+            ("00f067aa0ba902b8", ts(2), 8),
+            ("00f067aa0ba902b9", ts(3), 12),
+        ] {
+            emit(
+                &convert,
+                &capture,
+                count(extent, value).map_props(|props| {
+                    emit_core::props::Props::and_props(
+                        [
+                            (KEY_TRACE_ID, "4bf92f3577b34da6a3ce929d0e0e4736"),
+                            (KEY_SPAN_ID, span_id),
+                            (KEY_SPAN_PARENT, "00f067aa0ba902b6"),
+                        ],
+                        props,
+                    )
+                }),
+            );
+        }
+
+        assert_eq!(
+            vec![(Some(ts(1)), ts(2), 3), (Some(ts(2)), ts(3), 4)],
+            values(&capture)
+        );
+
+        // The ids of the latest span are kept on each sample
+        assert_eq!(
+            "00f067aa0ba902b9",
+            capture.events()[1]
+                .props()
+                .get(KEY_SPAN_ID)
+                .unwrap()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn max_streams() {
+        let convert = Convert::to_delta().with_max_streams(1);
+        let capture = Capture::new();
+
+        let sample = |route: &'static str, secs, value| {
+            count(ts(secs), value)
+                .map_props(move |props| emit_core::props::Props::and_props(("route", route), props))
+        };
+
+        emit(&convert, &capture, sample("a", 1, 1));
+        emit(&convert, &capture, sample("b", 2, 1));
+
+        // Stream `a` was forgotten, so this is a new baseline
+        emit(&convert, &capture, sample("a", 3, 5));
+        emit(&convert, &capture, sample("a", 4, 6));
+
+        assert_eq!(vec![(Some(ts(3)), ts(4), 1)], values(&capture));
+        assert_eq!(1, convert.streams.lock().unwrap().streams.len());
+    }
+
+    #[test]
+    fn cumulative_to_delta_exp_distribution() {
+        let convert = Convert::to_delta();
+        let capture = Capture::new();
+
+        let mut distribution = exp::Distribution::new(4, 160);
+
+        for (secs, values) in [(1, &[1.0, 2.0][..]), (2, &[2.0, 30.0][..])] {
+            for value in values {
+                distribution.observe(*value);
+            }
+
+            emit(
+                &convert,
+                &capture,
+                Metric::new(
+                    Path::new_raw("test"),
+                    ts(secs),
+                    emit_core::props::Props::and_props(
+                        &[
+                            ("metric_name", Value::from("test")),
+                            ("metric_agg", Value::from("count")),
+                            ("metric_value", Value::from(distribution.count())),
+                        ],
+                        &distribution,
+                    ),
+                ),
+            );
+        }
+
+        let events = capture.events();
+        let evt = &events[0];
+
+        let buckets = evt
+            .props()
+            .pull::<exp::BucketSet, _>(KEY_DIST_EXP_BUCKETS)
+            .unwrap();
+
+        assert_eq!(2, evt.props().pull::<u64, _>(KEY_METRIC_VALUE).unwrap());
+        assert_eq!(2, evt.props().pull::<u64, _>(KEY_DIST_COUNT).unwrap());
+        assert_eq!(32.0, evt.props().pull::<f64, _>(KEY_DIST_SUM).unwrap());
+        assert_eq!(2, buckets.total());
+        assert_eq!(Some(1), buckets.get(exp::midpoint(2.0, 4)));
+        assert_eq!(Some(1), buckets.get(exp::midpoint(30.0, 4)));
+
+        // Extrema can't be converted
+        assert!(evt.props().get(KEY_DIST_MIN).is_none());
+        assert!(evt.props().get(KEY_DIST_MAX).is_none());
+    }
+
+    #[test]
+    fn delta_to_cumulative_exp_distribution() {
+        let convert = Convert::to_cumulative();
+        let capture = Capture::new();
+
+        let mut a = exp::Distribution::new(8, 160);
+        a.observe(1.0);
+        a.observe(3.0);
+
+        // A smaller scale
+        let mut b = exp::Distribution::new(2, 160);
+        b.observe(3.0);
+        b.observe(-3.0);
+
+        for (secs, distribution) in [(1, &a), (2, &b)] {
+            emit(
+                &convert,
+                &capture,
+                Metric::new(
+                    Path::new_raw("test"),
+                    ts(secs - 1)..ts(secs),
+                    emit_core::props::Props::and_props(
+                        &[
+                            ("metric_name", Value::from("test")),
+                            ("metric_agg", Value::from("count")),
+                            ("metric_value", Value::from(distribution.count())),
+                        ],
+                        distribution,
+                    ),
+                ),
+            );
+        }
+
+        let events = capture.events();
+        let evt = &events[1];
+
+        let buckets = evt
+            .props()
+            .pull::<exp::BucketSet, _>(KEY_DIST_EXP_BUCKETS)
+            .unwrap();
+
+        assert_eq!(4, evt.props().pull::<u64, _>(KEY_METRIC_VALUE).unwrap());
+        assert_eq!(2, evt.props().pull::<i32, _>(KEY_DIST_EXP_SCALE).unwrap());
+        assert_eq!(4, buckets.total());
+        assert_eq!(Some(2), buckets.get(exp::midpoint(3.0, 2)));
+        assert_eq!(-3.0, evt.props().pull::<f64, _>(KEY_DIST_MIN).unwrap());
+        assert_eq!(3.0, evt.props().pull::<f64, _>(KEY_DIST_MAX).unwrap());
+    }
+
+    #[test]
+    fn delta_to_cumulative_explicit_distribution() {
+        let convert = Convert::to_cumulative();
+        let capture = Capture::new();
+
+        let mut a = crate::metric::explicit::Distribution::new([1.0, 5.0]);
+        a.observe(0.5);
+
+        let mut b = crate::metric::explicit::Distribution::new([1.0, 5.0]);
+        b.observe(3.0);
+        b.observe(10.0);
+
+        for (secs, distribution) in [(1, &a), (2, &b)] {
+            emit(
+                &convert,
+                &capture,
+                Metric::new(
+                    Path::new_raw("test"),
+                    ts(secs - 1)..ts(secs),
+                    emit_core::props::Props::and_props(
+                        &[
+                            ("metric_name", Value::from("test")),
+                            ("metric_agg", Value::from("count")),
+                            ("metric_value", Value::from(distribution.count())),
+                        ],
+                        distribution,
+                    ),
+                ),
+            );
+        }
+
+        let events = capture.events();
+        let evt = &events[1];
+
+        assert_eq!(
+            Some(vec![1, 1, 1]),
+            evt.props()
+                .get(KEY_DIST_BUCKET_COUNTS)
+                .and_then(|counts| counts.to_u64_sequence())
+        );
+    }
+}
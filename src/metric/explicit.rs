@@ -0,0 +1,401 @@
+/*!
+Functions for working with explicit bucket histograms.
+
+An explicit bucket histogram divides values into a fixed set of buckets, described by their upper bounds. For bounds `[b0, b1, .., bn]` there are `n + 2` buckets:
+
+- `(-inf, b0]`
+- `(b0, b1]`
+- ...
+- `(bn, +inf)`
+
+Explicit bucket histograms are less precise than exponential ones, but are more widely supported by backends that store distributions. See [`crate::metric::exp`] for exponential histograms.
+*/
+
+use core::{cmp, ops::ControlFlow};
+
+use alloc::vec::Vec;
+
+use emit_core::{
+    props::Props,
+    str::{Str, ToStr},
+    value::{ToValue, Value},
+    well_known::{
+        KEY_DIST_BUCKET_BOUNDS, KEY_DIST_BUCKET_COUNTS, KEY_DIST_COUNT, KEY_DIST_MAX, KEY_DIST_MIN,
+        KEY_DIST_SUM,
+    },
+};
+
+use crate::metric::exp;
+
+/**
+The default upper bounds of buckets in a [`Distribution`].
+
+These are the same defaults used by OpenTelemetry SDKs.
+*/
+pub const DEFAULT_BOUNDS: &'static [f64] = &[
+    0.0, 5.0, 10.0, 25.0, 50.0, 75.0, 100.0, 250.0, 500.0, 750.0, 1000.0, 2500.0, 5000.0, 7500.0,
+    10000.0,
+];
+
+/**
+A container for approximating the distribution of a streaming data source using a fixed set of buckets.
+
+`Distribution`s aggregate statistics from raw samples that pass through them. They include:
+
+- `total`: The total number of observed values.
+- `sum`: The sum of all observed values.
+- `min`: The smallest observed value.
+- `max`: The largest observed value.
+- `bounds` and `counts`: An explicit bucket histogram.
+
+Call the [`Distribution::observe`] method on each raw value.
+
+Use the [`Props`] implementation on `Distribution` to include it on a metric sample.
+*/
+#[derive(Clone, Debug, PartialEq)]
+pub struct Distribution {
+    bounds: Vec<f64>,
+    counts: Vec<u64>,
+    sum: Option<f64>,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+impl Default for Distribution {
+    fn default() -> Self {
+        Self::new(DEFAULT_BOUNDS)
+    }
+}
+
+impl Distribution {
+    /**
+    Create a new `Distribution` with buckets for the given upper `bounds`.
+
+    The bounds will be sorted and deduplicated. Any `NaN` bounds are ignored.
+    */
+    pub fn new(bounds: impl Into<Vec<f64>>) -> Self {
+        let mut bounds = bounds.into();
+
+        bounds.retain(|bound| !bound.is_nan());
+        bounds.sort_by(|a, b| a.total_cmp(b));
+        bounds.dedup();
+
+        let counts = alloc::vec![0; bounds.len() + 1];
+
+        Distribution {
+            bounds,
+            counts,
+            sum: None,
+            min: None,
+            max: None,
+        }
+    }
+
+    /**
+    Create a new `Distribution` with buckets for the given upper `bounds` from an exponential one.
+
+    Each exponential bucket is assigned to the explicit bucket containing its midpoint, so the result is an approximation of the original. The count, sum, and extrema are carried over exactly.
+    */
+    pub fn from_exp(bounds: impl Into<Vec<f64>>, distribution: &exp::Distribution) -> Self {
+        let mut explicit = Distribution::new(bounds);
+
+        for (midpoint, count) in distribution.buckets() {
+            let index = explicit.index(midpoint.get());
+            explicit.counts[index] += count;
+        }
+
+        explicit.sum = distribution.sum();
+        explicit.min = distribution.min();
+        explicit.max = distribution.max();
+
+        explicit
+    }
+
+    /**
+    Observe a raw value.
+
+    The count for the bucket containing the value will be incremented by `1`.
+
+    # Panics
+
+    This method will panic if incrementing the count for the bucket would overflow.
+    */
+    pub fn observe(&mut self, raw_value: f64) {
+        self.observe_all(raw_value, 1)
+    }
+
+    /**
+    Observe a raw value `count` times.
+
+    The count for the bucket containing the value will be incremented by `count`. `NaN` values are counted in the last bucket.
+
+    # Panics
+
+    This method will panic if incrementing the count for the bucket would overflow.
+    */
+    pub fn observe_all(&mut self, raw_value: f64, count: u64) {
+        let index = self.index(raw_value);
+        self.counts[index] = self.counts[index]
+            .checked_add(count)
+            .expect("overflow incrementing bucket count");
+
+        // Track the extrema
+        self.min = self
+            .min
+            .map(|min| cmp::min_by(min, raw_value, |a, b| a.total_cmp(b)))
+            .or(Some(raw_value));
+        self.max = self
+            .max
+            .map(|max| cmp::max_by(max, raw_value, |a, b| a.total_cmp(b)))
+            .or(Some(raw_value));
+        self.sum = self
+            .sum
+            .map(|sum| sum + raw_value * count as f64)
+            .or(Some(raw_value * count as f64));
+    }
+
+    /**
+    Merge the observations from `other` into this distribution.
+
+    # Panics
+
+    This method will panic if `other` doesn't have the same bounds as this distribution, or if merging the counts for a bucket would overflow.
+    */
+    pub fn merge(&mut self, other: &Distribution) {
+        assert_eq!(
+            self.bounds, other.bounds,
+            "only distributions with the same bounds can be merged"
+        );
+
+        for (count, other_count) in self.counts.iter_mut().zip(&other.counts) {
+            *count = count
+                .checked_add(*other_count)
+                .expect("overflow merging bucket count");
+        }
+
+        // Merge the extrema
+        if let Some(other_min) = other.min {
+            self.min = self
+                .min
+                .map(|min| cmp::min_by(min, other_min, |a, b| a.total_cmp(b)))
+                .or(Some(other_min));
+        }
+        if let Some(other_max) = other.max {
+            self.max = self
+                .max
+                .map(|max| cmp::max_by(max, other_max, |a, b| a.total_cmp(b)))
+                .or(Some(other_max));
+        }
+        if let Some(other_sum) = other.sum {
+            self.sum = self.sum.map(|sum| sum + other_sum).or(Some(other_sum));
+        }
+    }
+
+    /**
+    Clear the distribution of any data so it can be re-used.
+
+    The bounds of the distribution are kept.
+    */
+    pub fn reset(&mut self) {
+        for count in &mut self.counts {
+            *count = 0;
+        }
+
+        self.min = None;
+        self.max = None;
+        self.sum = None;
+    }
+
+    /**
+    Get the total count of observed values across all buckets.
+
+    This method returns `0` if no values have been seen.
+    */
+    pub fn count(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /**
+    Get the minimum observed value.
+
+    This method returns `None` if no values have been seen.
+    */
+    pub fn min(&self) -> Option<f64> {
+        self.min
+    }
+
+    /**
+    Get the maximum observed value.
+
+    This method returns `None` if no values have been seen.
+    */
+    pub fn max(&self) -> Option<f64> {
+        self.max
+    }
+
+    /**
+    Get the sum of all observed values.
+
+    This method returns `None` if no values have been seen.
+    */
+    pub fn sum(&self) -> Option<f64> {
+        self.sum
+    }
+
+    /**
+    Get the upper bounds of each bucket, except the last.
+    */
+    pub fn bounds(&self) -> &[f64] {
+        &self.bounds
+    }
+
+    /**
+    Get the counts of each bucket.
+
+    There is always one more count than there are bounds.
+    */
+    pub fn counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    fn index(&self, value: f64) -> usize {
+        if value.is_nan() {
+            return self.bounds.len();
+        }
+
+        self.bounds.partition_point(|bound| *bound < value)
+    }
+}
+
+impl Props for Distribution {
+    fn for_each<'kv, F: FnMut(Str<'kv>, Value<'kv>) -> ControlFlow<()>>(
+        &'kv self,
+        mut for_each: F,
+    ) -> ControlFlow<()> {
+        for_each(KEY_DIST_BUCKET_BOUNDS.to_str(), self.bounds.to_value())?;
+        for_each(KEY_DIST_BUCKET_COUNTS.to_str(), self.counts.to_value())?;
+
+        for_each(KEY_DIST_COUNT.to_str(), self.count().into())?;
+
+        if let Some(sum) = self.sum() {
+            for_each(KEY_DIST_SUM.to_str(), sum.into())?;
+        }
+        if let Some(min) = self.min() {
+            for_each(KEY_DIST_MIN.to_str(), min.into())?;
+        }
+        if let Some(max) = self.max() {
+            for_each(KEY_DIST_MAX.to_str(), max.into())?;
+        }
+
+        ControlFlow::Continue(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distribution_new() {
+        let distribution = Distribution::new([10.0, f64::NAN, 1.0, 5.0, 1.0]);
+
+        assert_eq!(&[1.0, 5.0, 10.0], distribution.bounds());
+        assert_eq!(&[0, 0, 0, 0], distribution.counts());
+        assert_eq!(0, distribution.count());
+    }
+
+    #[test]
+    fn distribution_observe() {
+        let mut distribution = Distribution::new([1.0, 5.0, 10.0]);
+
+        for value in [0.0, 1.0, 1.5, 5.0, 7.0, 10.0, 11.0, f64::NAN] {
+            distribution.observe(value);
+        }
+
+        assert_eq!(&[2, 2, 2, 2], distribution.counts());
+        assert_eq!(8, distribution.count());
+        assert_eq!(Some(0.0), distribution.min());
+
+        distribution.reset();
+
+        assert_eq!(&[1.0, 5.0, 10.0], distribution.bounds());
+        assert_eq!(&[0, 0, 0, 0], distribution.counts());
+        assert_eq!(None, distribution.min());
+        assert_eq!(None, distribution.max());
+        assert_eq!(None, distribution.sum());
+    }
+
+    #[test]
+    fn distribution_observe_all() {
+        let mut distribution = Distribution::new([1.0]);
+
+        distribution.observe_all(2.0, 3);
+
+        assert_eq!(&[0, 3], distribution.counts());
+        assert_eq!(Some(6.0), distribution.sum());
+    }
+
+    #[test]
+    fn distribution_merge() {
+        let mut a = Distribution::new([1.0, 5.0]);
+        let mut b = Distribution::new([1.0, 5.0]);
+
+        a.observe(0.5);
+        b.observe(2.0);
+        b.observe(6.0);
+
+        a.merge(&b);
+
+        assert_eq!(&[1, 1, 1], a.counts());
+        assert_eq!(Some(0.5), a.min());
+        assert_eq!(Some(6.0), a.max());
+        assert_eq!(Some(8.5), a.sum());
+    }
+
+    #[test]
+    #[should_panic]
+    fn distribution_merge_different_bounds() {
+        let mut a = Distribution::new([1.0, 5.0]);
+        let b = Distribution::new([1.0, 10.0]);
+
+        a.merge(&b);
+    }
+
+    #[test]
+    fn distribution_from_exp() {
+        let mut exp = exp::Distribution::default();
+
+        for value in [0.0, 0.5, 2.0, 3.0, 100.0, -1.0] {
+            exp.observe(value);
+        }
+
+        let explicit = Distribution::from_exp([1.0, 10.0], &exp);
+
+        assert_eq!(&[3, 2, 1], explicit.counts());
+        assert_eq!(exp.count(), explicit.count());
+        assert_eq!(exp.sum(), explicit.sum());
+        assert_eq!(exp.min(), explicit.min());
+        assert_eq!(exp.max(), explicit.max());
+    }
+
+    #[test]
+    fn distribution_props() {
+        let mut distribution = Distribution::new([1.0, 5.0]);
+
+        distribution.observe(2.0);
+
+        assert_eq!(
+            Some(vec![1.0, 5.0]),
+            distribution
+                .get(KEY_DIST_BUCKET_BOUNDS)
+                .and_then(|bounds| bounds.to_f64_sequence())
+        );
+        assert_eq!(
+            Some(vec![0, 1, 0]),
+            distribution
+                .get(KEY_DIST_BUCKET_COUNTS)
+                .and_then(|counts| counts.to_u64_sequence())
+        );
+        assert_eq!(1, distribution.pull::<u64, _>(KEY_DIST_COUNT).unwrap());
+    }
+}
@@ -0,0 +1,455 @@
+/*!
+The [`Family`] type.
+
+A family is a set of instruments that share a name, but are partitioned by a small set of labels:
+
+```
+static REQUESTS: emit::metric::Family<emit::metric::Counter> = emit::metric::Family::new(
+    emit::metric::Counter::new(emit::mdl!(), "requests"),
+    &["route", "status"],
+);
+
+fn handle_request(route: &str, status: u16) {
+    REQUESTS
+        .get(emit::props! {
+            route,
+            status,
+        })
+        .increment();
+}
+```
+
+Each distinct set of label values is a separate series. When a family is sampled, each series is reported as its own [`crate::metric::Metric`] with its labels as properties.
+
+Label values are compared by their formatted value, and whether or not they're strings. That means `200` and `"200"` are separate series, but non-string values of different types that format the same, like `200i32` and `200u16`, share a series.
+
+# Cardinality
+
+The number of series in a family is capped by [`Family::with_max_series`]. Once the cap is reached, any new sets of label values are accumulated into a single overflow series instead. The overflow series doesn't carry any labels, and has the [`KEY_OTEL_METRIC_OVERFLOW`] property set to `true`.
+
+Series can be expired when they haven't been used for a number of reporting intervals with [`Family::with_expire_after`]. A series is used whenever it's returned from [`Family::get`]. A series won't expire while any handles returned by [`Family::get`] are still alive.
+*/
+
+use std::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    sync::{
+        Arc, OnceLock, RwLock,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    vec::Vec,
+};
+
+use emit_core::{props::Props, value::OwnedValue};
+
+use crate::metric::{Counter, instrument::Instrument, sampler::Sampler, source::Source};
+
+/**
+The property set on the overflow series of a [`Family`].
+*/
+pub const KEY_OTEL_METRIC_OVERFLOW: &'static str = "otel.metric.overflow";
+
+/**
+A set of instruments partitioned by labels.
+
+See the [`mod@crate::metric::family`] module for details.
+*/
+pub struct Family<I> {
+    instrument: I,
+    labels: &'static [&'static str],
+    max_series: usize,
+    expire_after: Option<usize>,
+    series: RwLock<BTreeMap<SeriesKey, Series<I>>>,
+    overflow: OnceLock<Arc<I>>,
+}
+
+/**
+The limits on the number of series in a [`Family`].
+
+This is used by sources that create their own families, so they can expose the same limits as [`Family`] itself.
+*/
+#[derive(Clone, Copy)]
+pub(crate) struct Limits {
+    max_series: usize,
+    expire_after: Option<usize>,
+}
+
+// The rendered values of each label in a series, along with whether they're strings
+type SeriesKey = Box<[Option<(bool, String)>]>;
+
+struct Series<I> {
+    instrument: Arc<I>,
+    labels: Arc<[(&'static str, OwnedValue)]>,
+    touched: AtomicBool,
+    idle: AtomicUsize,
+}
+
+impl<I: Instrument> Family<I> {
+    /**
+    The default maximum number of series in a family.
+    */
+    pub const DEFAULT_MAX_SERIES: usize = 2000;
+
+    /**
+    Create a new family of instruments partitioned by `labels`.
+
+    The `instrument` is used as a template for each series, which will share its name, unit, description, and temporality. The template itself is never sampled.
+    */
+    pub const fn new(instrument: I, labels: &'static [&'static str]) -> Self {
+        Family {
+            instrument,
+            labels,
+            max_series: Self::DEFAULT_MAX_SERIES,
+            expire_after: None,
+            series: RwLock::new(BTreeMap::new()),
+            overflow: OnceLock::new(),
+        }
+    }
+
+    /**
+    Set the maximum number of series in the family.
+
+    Once the maximum is reached, new sets of label values are accumulated into an overflow series. The default maximum is [`Family::DEFAULT_MAX_SERIES`].
+    */
+    pub const fn with_max_series(mut self, max_series: usize) -> Self {
+        self.max_series = max_series;
+        self
+    }
+
+    /**
+    Expire series after they've been idle for `intervals` samples of the family.
+
+    A series that's used between two samples is never expired by the second, so an `intervals` of `0` is treated as `1`. By default, series never expire.
+    */
+    pub const fn with_expire_after(mut self, intervals: usize) -> Self {
+        self.expire_after = Some(if intervals == 0 { 1 } else { intervals });
+        self
+    }
+
+    /**
+    Get the instrument for the series matching the labels in `props`.
+
+    Any properties in `props` that aren't labels of the family are ignored. Labels that aren't present in `props` are omitted from the series.
+
+    If the family has reached its maximum number of series then the overflow series is returned.
+    */
+    pub fn get(&self, props: impl Props) -> Arc<I> {
+        let key = self
+            .labels
+            .iter()
+            .map(|label| {
+                props.get(*label).map(|value| match value.to_cow_str() {
+                    Some(value) => (true, value.into_owned()),
+                    None => (false, value.to_string()),
+                })
+            })
+            .collect::<SeriesKey>();
+
+        if let Some(series) = self
+            .series
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&key)
+        {
+            series.touched.store(true, Ordering::Relaxed);
+
+            return series.instrument.clone();
+        }
+
+        let mut series = self.series.write().unwrap_or_else(|e| e.into_inner());
+
+        // Another thread may have added the series while we were waiting for the lock
+        if let Some(series) = series.get(&key) {
+            series.touched.store(true, Ordering::Relaxed);
+
+            return series.instrument.clone();
+        }
+
+        if series.len() >= self.max_series {
+            return self
+                .overflow
+                .get_or_init(|| Arc::new(self.instrument.fork()))
+                .clone();
+        }
+
+        let instrument = Arc::new(self.instrument.fork());
+
+        series.insert(
+            key,
+            Series {
+                instrument: instrument.clone(),
+                labels: self
+                    .labels
+                    .iter()
+                    .filter_map(|label| Some((*label, props.get(*label)?.to_owned())))
+                    .collect(),
+                touched: AtomicBool::new(true),
+                idle: AtomicUsize::new(0),
+            },
+        );
+
+        instrument
+    }
+}
+
+impl Limits {
+    pub(crate) const fn new() -> Self {
+        Limits {
+            max_series: Family::<Counter>::DEFAULT_MAX_SERIES,
+            expire_after: None,
+        }
+    }
+
+    pub(crate) const fn with_max_series(mut self, max_series: usize) -> Self {
+        self.max_series = max_series;
+        self
+    }
+
+    pub(crate) const fn with_expire_after(mut self, intervals: usize) -> Self {
+        self.expire_after = Some(intervals);
+        self
+    }
+
+    pub(crate) fn family<I: Instrument>(
+        &self,
+        instrument: I,
+        labels: &'static [&'static str],
+    ) -> Family<I> {
+        let family = Family::new(instrument, labels).with_max_series(self.max_series);
+
+        match self.expire_after {
+            Some(intervals) => family.with_expire_after(intervals),
+            None => family,
+        }
+    }
+}
+
+impl<I: Instrument> Source for Family<I> {
+    fn sample_metrics<S: Sampler>(&self, sampler: S) {
+        let mut sampled = Vec::new();
+        let mut expired = Vec::new();
+
+        {
+            let series = self.series.read().unwrap_or_else(|e| e.into_inner());
+
+            for (key, series) in series.iter() {
+                sampled.push((series.instrument.clone(), series.labels.clone()));
+
+                let idle = if series.touched.swap(false, Ordering::Relaxed) {
+                    series.idle.store(0, Ordering::Relaxed);
+                    0
+                } else {
+                    series.idle.fetch_add(1, Ordering::Relaxed) + 1
+                };
+
+                if self
+                    .expire_after
+                    .is_some_and(|expire_after| idle >= expire_after)
+                {
+                    expired.push(key.clone());
+                }
+            }
+        }
+
+        // The lock is released before sampling, so samplers can call `get` on this family
+        for (instrument, labels) in sampled {
+            instrument.sample_with(&sampler, &*labels);
+        }
+
+        if let Some(overflow) = self.overflow.get() {
+            overflow.sample_with(&sampler, (KEY_OTEL_METRIC_OVERFLOW, true));
+        }
+
+        if !expired.is_empty() {
+            let mut series = self.series.write().unwrap_or_else(|e| e.into_inner());
+
+            for key in expired {
+                // Only remove the series if it's still idle and there are no live handles to it
+                if series.get(&key).is_some_and(|series| {
+                    !series.touched.load(Ordering::Relaxed)
+                        && Arc::strong_count(&series.instrument) == 1
+                }) {
+                    series.remove(&key);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::{cell::RefCell, time::Duration};
+
+    use emit_core::{path::Path, timestamp::Timestamp};
+
+    use crate::metric::{Counter, Histogram, Temporality, sampler};
+
+    fn sample(source: impl Source) -> Vec<String> {
+        let sampled = RefCell::new(Vec::new());
+
+        source.sample_metrics(
+            sampler::from_fn(|metric| {
+                let mut labels = Vec::new();
+
+                let _ = metric.props().for_each(|k, v| {
+                    if !k.get().starts_with("metric_") && !k.get().starts_with("dist_") {
+                        labels.push(format!("{k}={v}"));
+                    }
+
+                    std::ops::ControlFlow::Continue(())
+                });
+
+                sampled.borrow_mut().push(format!(
+                    "{} {}",
+                    labels.join(","),
+                    metric.value().unwrap()
+                ));
+            })
+            .with_sampled_at(Timestamp::from_unix(Duration::from_secs(1))),
+        );
+
+        let mut sampled = sampled.into_inner();
+        sampled.sort();
+
+        sampled
+    }
+
+    #[test]
+    fn family_series() {
+        let family = Family::new(Counter::new(Path::new_raw("test"), "counter"), &["a", "b"]);
+
+        family.get([("a", 1), ("b", 2)]).increment();
+        family.get([("b", 2), ("a", 1), ("c", 3)]).increment();
+        family.get([("a", 1)]).increment();
+        family.get([("a", 2), ("b", 2)]).add(5);
+
+        assert_eq!(vec!["a=1 1", "a=1,b=2 2", "a=2,b=2 5"], sample(&family));
+    }
+
+    #[test]
+    fn family_inherits_template() {
+        let family = Family::new(
+            Counter::new(Path::new_raw("test"), "counter")
+                .with_unit("requests")
+                .with_temporality(Temporality::Delta),
+            &["a"],
+        );
+
+        family.get(("a", 1)).increment();
+
+        family.sample_metrics(sampler::from_fn(|metric| {
+            assert_eq!("test", metric.mdl());
+            assert_eq!("counter", metric.name().unwrap());
+            assert_eq!("requests", metric.unit().unwrap());
+        }));
+
+        assert_eq!(0, family.get(("a", 1)).get());
+    }
+
+    #[test]
+    fn family_histogram() {
+        let family = Family::new(Histogram::new(Path::new_raw("test"), "histogram"), &["a"]);
+
+        family.get(("a", 1)).observe(1.0);
+        family.get(("a", 1)).observe(2.0);
+        family.get(("a", 2)).observe(3.0);
+
+        assert_eq!(vec!["a=1 2", "a=2 1"], sample(&family));
+    }
+
+    #[test]
+    fn family_overflow() {
+        let family =
+            Family::new(Counter::new(Path::new_raw("test"), "counter"), &["a"]).with_max_series(2);
+
+        for i in 0..5 {
+            family.get(("a", i)).increment();
+        }
+
+        // Existing series are still used after the cap is reached
+        family.get(("a", 0)).increment();
+
+        assert_eq!(
+            vec!["a=0 2", "a=1 1", "otel.metric.overflow=true 3"],
+            sample(&family)
+        );
+    }
+
+    #[test]
+    fn family_expire() {
+        let family = Family::new(Counter::new(Path::new_raw("test"), "counter"), &["a"])
+            .with_expire_after(2);
+
+        family.get(("a", 1)).increment();
+        family.get(("a", 2)).increment();
+
+        let held = family.get(("a", 3));
+
+        assert_eq!(3, sample(&family).len());
+
+        family.get(("a", 2)).increment();
+
+        // Series 1 has been idle for 1 interval
+        assert_eq!(3, sample(&family).len());
+
+        // Series 1 has been idle for 2 intervals, so it's sampled one last time and expired
+        // Series 3 has also been idle for 2 intervals, but has a live handle
+        assert_eq!(vec!["a=1 1", "a=2 2", "a=3 0"], sample(&family));
+
+        // Series 2 has now been idle for 2 intervals
+        assert_eq!(vec!["a=2 2", "a=3 0"], sample(&family));
+
+        drop(held);
+
+        // Series 3 no longer has a live handle
+        assert_eq!(vec!["a=3 0"], sample(&family));
+        assert!(sample(&family).is_empty());
+
+        // Expired series start again from scratch
+        family.get(("a", 1)).increment();
+
+        assert_eq!(vec!["a=1 1"], sample(&family));
+    }
+
+    #[test]
+    fn family_get_while_sampling() {
+        let family = Family::new(Counter::new(Path::new_raw("test"), "counter"), &["a"]);
+
+        family.get(("a", 1)).increment();
+
+        // Samplers can add new series to the family they're sampling
+        family.sample_metrics(sampler::from_fn(|metric| {
+            let a = metric.props().pull::<i32, _>("a").unwrap();
+
+            family.get(("a", a + 1)).increment();
+        }));
+
+        assert_eq!(vec!["a=1 1", "a=2 1"], sample(&family));
+    }
+
+    #[test]
+    fn family_series_by_kind() {
+        let family = Family::new(Counter::new(Path::new_raw("test"), "counter"), &["a"]);
+
+        family.get(("a", 200)).increment();
+        family.get(("a", "200")).increment();
+        family.get(("a", 200u16)).increment();
+
+        assert_eq!(vec!["a=200 1", "a=200 2"], sample(&family));
+    }
+
+    #[test]
+    fn family_expire_after_zero() {
+        let family = Family::new(Counter::new(Path::new_raw("test"), "counter"), &["a"])
+            .with_expire_after(0);
+
+        family.get(("a", 1)).increment();
+
+        // Series aren't expired in the same interval they're used in
+        assert_eq!(vec!["a=1 1"], sample(&family));
+        assert_eq!(vec!["a=1 1"], sample(&family));
+        assert!(sample(&family).is_empty());
+    }
+}
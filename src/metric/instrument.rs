@@ -0,0 +1,941 @@
+/*!
+Built-in instruments for accumulating metrics in-process.
+
+Instruments are [`Source`]s that are cheap to update from hot paths. Counters and gauges are updated atomically, without locking. Histograms lock one of a set of striped distributions, so concurrent threads rarely contend with each other. Instruments can be defined as `static`s and shared throughout a program:
+
+```
+static REQUESTS: emit::metric::Counter = emit::metric::Counter::new(emit::mdl!(), "requests")
+    .with_description("The number of requests handled");
+
+fn handle_request() {
+    REQUESTS.increment();
+
+    // Your code goes here
+}
+```
+
+Instruments are sampled like any other [`Source`], either directly or through a [`crate::metric::Reporter`]:
+
+```
+# static REQUESTS: emit::metric::Counter = emit::metric::Counter::new(emit::mdl!(), "requests");
+let mut reporter = emit::metric::Reporter::new();
+
+reporter.add_source(&REQUESTS);
+```
+
+The following instruments are available:
+
+- [`Counter`]: A monotonic count of events, sampled with the `count` aggregation.
+- [`UpDownCounter`]: A count that can increase or decrease, sampled with the `sum` aggregation.
+- [`Gauge`]: The last observed value, sampled with the `last` aggregation.
+- [`Histogram`]: A distribution of observed values, sampled with the `count` aggregation along with an [`exp::Distribution`].
+
+# Temporality
+
+Counters and histograms can be sampled as either cumulative or delta metrics, depending on their [`Temporality`]. Cumulative instruments report their total value since they were created on each sample, using a point extent. Delta instruments report the value accumulated since the last time they were sampled, and reset it, using a range extent.
+
+The extent of a sample is based on [`Sampler::sampled_at`]. The first sample of a delta instrument has an empty range, because the instrument doesn't know when it started accumulating.
+*/
+
+use core::ops::ControlFlow;
+use std::sync::{
+    Mutex,
+    atomic::{AtomicI64, AtomicU64, AtomicUsize, Ordering},
+};
+
+use emit_core::{
+    and::And,
+    empty::Empty,
+    extent::{Extent, ToExtent},
+    path::Path,
+    props::Props,
+    str::{Str, ToStr},
+    timestamp::Timestamp,
+    value::{ToValue, Value},
+    well_known::{
+        KEY_METRIC_AGG, KEY_METRIC_DESCRIPTION, KEY_METRIC_NAME, KEY_METRIC_UNIT, KEY_METRIC_VALUE,
+        METRIC_AGG_COUNT, METRIC_AGG_LAST, METRIC_AGG_SUM,
+    },
+};
+
+use crate::metric::{Metric, exp, sampler::Sampler, source::Source};
+
+use self::internal::SealedInstrument as _;
+
+/**
+How an instrument accumulates its value between samples.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Temporality {
+    /**
+    Each sample reports the total value since the instrument was created.
+    */
+    #[default]
+    Cumulative,
+    /**
+    Each sample reports the value accumulated since the previous sample, resetting it.
+    */
+    Delta,
+}
+
+/**
+An instrument that can be used in a [`crate::metric::Family`].
+
+This trait is implemented by [`Counter`], [`UpDownCounter`], [`Gauge`], and [`Histogram`]. It can't be implemented outside of `emit`.
+*/
+pub trait Instrument: internal::SealedInstrument {}
+
+pub(super) mod internal {
+    use super::*;
+
+    pub trait SealedInstrument: Source + Sized {
+        fn fork(&self) -> Self;
+
+        fn sample_with<S: Sampler, P: Props>(&self, sampler: S, props: P);
+    }
+}
+
+/**
+A monotonic count of events.
+
+Counters are sampled with the `count` aggregation. Updating a counter is a single atomic operation.
+
+See the [`mod@crate::metric::instrument`] module for details.
+*/
+pub struct Counter {
+    meta: Meta,
+    value: AtomicU64,
+}
+
+impl Counter {
+    /**
+    Create a new counter with the given module and name.
+
+    The counter is cumulative by default.
+    */
+    pub const fn new(mdl: Path<'static>, name: &'static str) -> Self {
+        Counter {
+            meta: Meta::new(mdl, name),
+            value: AtomicU64::new(0),
+        }
+    }
+
+    /**
+    Set the unit of the counter's samples.
+    */
+    pub const fn with_unit(mut self, unit: &'static str) -> Self {
+        self.meta.unit = Some(unit);
+        self
+    }
+
+    /**
+    Set a description of the counter.
+    */
+    pub const fn with_description(mut self, description: &'static str) -> Self {
+        self.meta.description = Some(description);
+        self
+    }
+
+    /**
+    Set whether the counter is sampled as a cumulative or delta metric.
+    */
+    pub const fn with_temporality(mut self, temporality: Temporality) -> Self {
+        self.meta.temporality = temporality;
+        self
+    }
+
+    /**
+    Add `1` to the counter.
+    */
+    pub fn increment(&self) {
+        self.add(1)
+    }
+
+    /**
+    Add `value` to the counter.
+    */
+    pub fn add(&self, value: u64) {
+        self.value.fetch_add(value, Ordering::Relaxed);
+    }
+
+    /**
+    Get the current value of the counter.
+
+    For delta counters, this is the value accumulated since the last sample.
+    */
+    pub fn get(&self) -> u64 {
+        self.value.load(Ordering::Relaxed)
+    }
+
+    /**
+    Get the temporality of the counter.
+    */
+    pub fn temporality(&self) -> Temporality {
+        self.meta.temporality
+    }
+}
+
+impl Source for Counter {
+    fn sample_metrics<S: Sampler>(&self, sampler: S) {
+        self.sample_with(sampler, Empty)
+    }
+}
+
+impl Instrument for Counter {}
+
+impl internal::SealedInstrument for Counter {
+    fn fork(&self) -> Self {
+        Counter {
+            meta: self.meta.fork(),
+            value: AtomicU64::new(0),
+        }
+    }
+
+    fn sample_with<S: Sampler, P: Props>(&self, sampler: S, props: P) {
+        let (extent, value) =
+            self.meta
+                .advance(sampler.sampled_at(), |temporality| match temporality {
+                    Temporality::Cumulative => self.value.load(Ordering::Relaxed),
+                    Temporality::Delta => self.value.swap(0, Ordering::Relaxed),
+                });
+
+        sampler.metric(self.meta.metric(extent, METRIC_AGG_COUNT, value, props));
+    }
+}
+
+/**
+A count of events that can increase or decrease.
+
+Up-down counters are sampled with the `sum` aggregation. Updating an up-down counter is a single atomic operation.
+
+See the [`mod@crate::metric::instrument`] module for details.
+*/
+pub struct UpDownCounter {
+    meta: Meta,
+    value: AtomicI64,
+}
+
+impl UpDownCounter {
+    /**
+    Create a new up-down counter with the given module and name.
+
+    The counter is cumulative by default.
+    */
+    pub const fn new(mdl: Path<'static>, name: &'static str) -> Self {
+        UpDownCounter {
+            meta: Meta::new(mdl, name),
+            value: AtomicI64::new(0),
+        }
+    }
+
+    /**
+    Set the unit of the counter's samples.
+    */
+    pub const fn with_unit(mut self, unit: &'static str) -> Self {
+        self.meta.unit = Some(unit);
+        self
+    }
+
+    /**
+    Set a description of the counter.
+    */
+    pub const fn with_description(mut self, description: &'static str) -> Self {
+        self.meta.description = Some(description);
+        self
+    }
+
+    /**
+    Set whether the counter is sampled as a cumulative or delta metric.
+    */
+    pub const fn with_temporality(mut self, temporality: Temporality) -> Self {
+        self.meta.temporality = temporality;
+        self
+    }
+
+    /**
+    Add `1` to the counter.
+    */
+    pub fn increment(&self) {
+        self.add(1)
+    }
+
+    /**
+    Subtract `1` from the counter.
+    */
+    pub fn decrement(&self) {
+        self.add(-1)
+    }
+
+    /**
+    Add `value` to the counter.
+
+    The value may be negative.
+    */
+    pub fn add(&self, value: i64) {
+        self.value.fetch_add(value, Ordering::Relaxed);
+    }
+
+    /**
+    Get the current value of the counter.
+
+    For delta counters, this is the value accumulated since the last sample.
+    */
+    pub fn get(&self) -> i64 {
+        self.value.load(Ordering::Relaxed)
+    }
+
+    /**
+    Get the temporality of the counter.
+    */
+    pub fn temporality(&self) -> Temporality {
+        self.meta.temporality
+    }
+}
+
+impl Source for UpDownCounter {
+    fn sample_metrics<S: Sampler>(&self, sampler: S) {
+        self.sample_with(sampler, Empty)
+    }
+}
+
+impl Instrument for UpDownCounter {}
+
+impl internal::SealedInstrument for UpDownCounter {
+    fn fork(&self) -> Self {
+        UpDownCounter {
+            meta: self.meta.fork(),
+            value: AtomicI64::new(0),
+        }
+    }
+
+    fn sample_with<S: Sampler, P: Props>(&self, sampler: S, props: P) {
+        let (extent, value) =
+            self.meta
+                .advance(sampler.sampled_at(), |temporality| match temporality {
+                    Temporality::Cumulative => self.value.load(Ordering::Relaxed),
+                    Temporality::Delta => self.value.swap(0, Ordering::Relaxed),
+                });
+
+        sampler.metric(self.meta.metric(extent, METRIC_AGG_SUM, value, props));
+    }
+}
+
+/**
+The last observed value of some quantity.
+
+Gauges are sampled with the `last` aggregation. They don't have a [`Temporality`]; each sample reports the most recently set value. Updating a gauge is a single atomic operation.
+
+See the [`mod@crate::metric::instrument`] module for details.
+*/
+pub struct Gauge {
+    meta: Meta,
+    value: AtomicU64,
+}
+
+impl Gauge {
+    /**
+    Create a new gauge with the given module and name.
+
+    The gauge has an initial value of `0`.
+    */
+    pub const fn new(mdl: Path<'static>, name: &'static str) -> Self {
+        Gauge {
+            meta: Meta::new(mdl, name),
+            value: AtomicU64::new(0f64.to_bits()),
+        }
+    }
+
+    /**
+    Set the unit of the gauge's samples.
+    */
+    pub const fn with_unit(mut self, unit: &'static str) -> Self {
+        self.meta.unit = Some(unit);
+        self
+    }
+
+    /**
+    Set a description of the gauge.
+    */
+    pub const fn with_description(mut self, description: &'static str) -> Self {
+        self.meta.description = Some(description);
+        self
+    }
+
+    /**
+    Set the value of the gauge.
+    */
+    pub fn set(&self, value: f64) {
+        self.value.store(value.to_bits(), Ordering::Relaxed);
+    }
+
+    /**
+    Get the current value of the gauge.
+    */
+    pub fn get(&self) -> f64 {
+        f64::from_bits(self.value.load(Ordering::Relaxed))
+    }
+}
+
+impl Source for Gauge {
+    fn sample_metrics<S: Sampler>(&self, sampler: S) {
+        self.sample_with(sampler, Empty)
+    }
+}
+
+impl Instrument for Gauge {}
+
+impl internal::SealedInstrument for Gauge {
+    fn fork(&self) -> Self {
+        Gauge {
+            meta: self.meta.fork(),
+            value: AtomicU64::new(0f64.to_bits()),
+        }
+    }
+
+    fn sample_with<S: Sampler, P: Props>(&self, sampler: S, props: P) {
+        sampler.metric(self.meta.metric(
+            sampler.sampled_at().to_extent(),
+            METRIC_AGG_LAST,
+            self.get(),
+            props,
+        ));
+    }
+}
+
+/**
+A distribution of observed values.
+
+Histograms are sampled with the `count` aggregation, along with the properties of an [`exp::Distribution`] of the values observed.
+
+Observed values are accumulated in a set of striped distributions, each behind its own lock. Each thread observes into its own stripe, so threads don't contend with each other unless there are more of them than stripes. The stripes are merged when the histogram is sampled.
+
+See the [`mod@crate::metric::instrument`] module for details.
+*/
+pub struct Histogram {
+    meta: Meta,
+    stripes: [Stripe; STRIPES],
+}
+
+const STRIPES: usize = 8;
+
+// Pad stripes to avoid false sharing between them
+#[repr(align(128))]
+struct Stripe(Mutex<exp::Distribution>);
+
+impl Histogram {
+    /**
+    Create a new histogram with the given module and name.
+
+    The histogram is cumulative by default.
+    */
+    pub const fn new(mdl: Path<'static>, name: &'static str) -> Self {
+        Histogram {
+            meta: Meta::new(mdl, name),
+            stripes: [const {
+                Stripe(Mutex::new(exp::Distribution::new(
+                    exp::Distribution::DEFAULT_MAX_SCALE,
+                    exp::Distribution::DEFAULT_MAX_BUCKETS,
+                )))
+            }; STRIPES],
+        }
+    }
+
+    /**
+    Set the unit of the histogram's observed values.
+    */
+    pub const fn with_unit(mut self, unit: &'static str) -> Self {
+        self.meta.unit = Some(unit);
+        self
+    }
+
+    /**
+    Set a description of the histogram.
+    */
+    pub const fn with_description(mut self, description: &'static str) -> Self {
+        self.meta.description = Some(description);
+        self
+    }
+
+    /**
+    Set whether the histogram is sampled as a cumulative or delta metric.
+    */
+    pub const fn with_temporality(mut self, temporality: Temporality) -> Self {
+        self.meta.temporality = temporality;
+        self
+    }
+
+    /**
+    Observe a value.
+    */
+    pub fn observe(&self, value: f64) {
+        self.stripes[stripe()]
+            .0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .observe(value);
+    }
+
+    /**
+    Get a merged snapshot of the values observed by the histogram.
+
+    For delta histograms, this is the distribution accumulated since the last sample.
+    */
+    pub fn get(&self) -> exp::Distribution {
+        self.merge(false)
+    }
+
+    /**
+    Get the temporality of the histogram.
+    */
+    pub fn temporality(&self) -> Temporality {
+        self.meta.temporality
+    }
+
+    fn merge(&self, reset: bool) -> exp::Distribution {
+        let mut merged = exp::Distribution::default();
+
+        for stripe in &self.stripes {
+            let mut stripe = stripe.0.lock().unwrap_or_else(|e| e.into_inner());
+
+            merged.merge(&stripe);
+
+            if reset {
+                stripe.reset();
+            }
+        }
+
+        merged
+    }
+}
+
+impl Source for Histogram {
+    fn sample_metrics<S: Sampler>(&self, sampler: S) {
+        self.sample_with(sampler, Empty)
+    }
+}
+
+impl Instrument for Histogram {}
+
+impl internal::SealedInstrument for Histogram {
+    fn fork(&self) -> Self {
+        Histogram {
+            meta: self.meta.fork(),
+            ..Histogram::new(self.meta.mdl.clone(), self.meta.name)
+        }
+    }
+
+    fn sample_with<S: Sampler, P: Props>(&self, sampler: S, props: P) {
+        let (extent, distribution) =
+            self.meta
+                .advance(sampler.sampled_at(), |temporality| match temporality {
+                    Temporality::Cumulative => self.merge(false),
+                    Temporality::Delta => self.merge(true),
+                });
+
+        sampler.metric(self.meta.metric(
+            extent,
+            METRIC_AGG_COUNT,
+            distribution.count(),
+            And::new(&distribution, props),
+        ));
+    }
+}
+
+// Assign threads to stripes in round-robin order
+fn stripe() -> usize {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+
+    std::thread_local! {
+        static STRIPE: usize = NEXT.fetch_add(1, Ordering::Relaxed) % STRIPES;
+    }
+
+    STRIPE.try_with(|stripe| *stripe).unwrap_or(0)
+}
+
+struct Meta {
+    mdl: Path<'static>,
+    name: &'static str,
+    unit: Option<&'static str>,
+    description: Option<&'static str>,
+    temporality: Temporality,
+    last_sampled_at: Mutex<Option<Timestamp>>,
+}
+
+impl Meta {
+    const fn new(mdl: Path<'static>, name: &'static str) -> Self {
+        Meta {
+            mdl,
+            name,
+            unit: None,
+            description: None,
+            temporality: Temporality::Cumulative,
+            last_sampled_at: Mutex::new(None),
+        }
+    }
+
+    fn fork(&self) -> Self {
+        Meta {
+            mdl: self.mdl.clone(),
+            name: self.name,
+            unit: self.unit,
+            description: self.description,
+            temporality: self.temporality,
+            last_sampled_at: Mutex::new(None),
+        }
+    }
+
+    fn advance<T>(
+        &self,
+        now: Option<Timestamp>,
+        take: impl FnOnce(Temporality) -> T,
+    ) -> (Option<Extent>, T) {
+        match self.temporality {
+            Temporality::Cumulative => (now.to_extent(), take(Temporality::Cumulative)),
+            Temporality::Delta => {
+                // Hold the lock while taking the value so concurrent samples
+                // don't produce overlapping extents
+                let mut last_sampled_at = self
+                    .last_sampled_at
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner());
+
+                let value = take(Temporality::Delta);
+
+                let extent = now.map(|now| {
+                    let start = last_sampled_at.replace(now).unwrap_or(now);
+
+                    Extent::range(start..now)
+                });
+
+                (extent, value)
+            }
+        }
+    }
+
+    fn metric<'a, V: ToValue, P: Props>(
+        &'a self,
+        extent: Option<Extent>,
+        agg: &'static str,
+        value: V,
+        props: P,
+    ) -> Metric<'a, SampleProps<'a, V, P>> {
+        Metric::new(
+            self.mdl.by_ref(),
+            extent,
+            SampleProps {
+                meta: self,
+                agg,
+                value,
+                props,
+            },
+        )
+    }
+}
+
+struct SampleProps<'a, V, P> {
+    meta: &'a Meta,
+    agg: &'static str,
+    value: V,
+    props: P,
+}
+
+impl<'a, V: ToValue, P: Props> Props for SampleProps<'a, V, P> {
+    fn for_each<'kv, F: FnMut(Str<'kv>, Value<'kv>) -> ControlFlow<()>>(
+        &'kv self,
+        mut for_each: F,
+    ) -> ControlFlow<()> {
+        for_each(KEY_METRIC_NAME.to_str(), self.meta.name.to_value())?;
+        for_each(KEY_METRIC_AGG.to_str(), self.agg.to_value())?;
+        for_each(KEY_METRIC_VALUE.to_str(), self.value.to_value())?;
+
+        if let Some(unit) = self.meta.unit {
+            for_each(KEY_METRIC_UNIT.to_str(), unit.to_value())?;
+        }
+
+        if let Some(description) = self.meta.description {
+            for_each(KEY_METRIC_DESCRIPTION.to_str(), description.to_value())?;
+        }
+
+        self.props.for_each(for_each)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::{cell::Cell, sync::Arc, thread, time::Duration};
+
+    use crate::metric::sampler;
+
+    fn ts(secs: u64) -> Option<Timestamp> {
+        Timestamp::from_unix(Duration::from_secs(secs))
+    }
+
+    fn sample(source: impl Source, now: Option<Timestamp>) -> (Option<Extent>, String, String) {
+        let sampled = Cell::new(None);
+
+        source.sample_metrics(
+            sampler::from_fn(|metric| {
+                assert!(
+                    sampled
+                        .replace(Some((
+                            metric.extent().cloned(),
+                            metric.agg().unwrap().to_string(),
+                            metric.value().unwrap().to_string(),
+                        )))
+                        .is_none()
+                );
+            })
+            .with_sampled_at(now),
+        );
+
+        sampled.into_inner().unwrap()
+    }
+
+    #[test]
+    fn instruments_are_static() {
+        static COUNTER: Counter = Counter::new(Path::new_raw("test"), "counter")
+            .with_unit("requests")
+            .with_description("a counter")
+            .with_temporality(Temporality::Delta);
+        static UP_DOWN_COUNTER: UpDownCounter =
+            UpDownCounter::new(Path::new_raw("test"), "up_down_counter");
+        static GAUGE: Gauge = Gauge::new(Path::new_raw("test"), "gauge");
+        static HISTOGRAM: Histogram = Histogram::new(Path::new_raw("test"), "histogram");
+
+        COUNTER.increment();
+        UP_DOWN_COUNTER.decrement();
+        GAUGE.set(1.5);
+        HISTOGRAM.observe(1.5);
+
+        assert_eq!(1, COUNTER.get());
+        assert_eq!(-1, UP_DOWN_COUNTER.get());
+        assert_eq!(1.5, GAUGE.get());
+        assert_eq!(1, HISTOGRAM.get().count());
+    }
+
+    #[test]
+    fn counter_cumulative() {
+        let counter = Counter::new(Path::new_raw("test"), "counter");
+
+        counter.increment();
+        counter.add(2);
+
+        let (extent, agg, value) = sample(&counter, ts(1));
+
+        let extent = extent.unwrap();
+
+        assert_eq!(ts(1).unwrap(), *extent.as_point());
+        assert!(extent.is_point());
+        assert_eq!("count", agg);
+        assert_eq!("3", value);
+
+        counter.increment();
+
+        let (_, _, value) = sample(&counter, ts(2));
+
+        assert_eq!("4", value);
+    }
+
+    #[test]
+    fn counter_delta() {
+        let counter =
+            Counter::new(Path::new_raw("test"), "counter").with_temporality(Temporality::Delta);
+
+        counter.add(3);
+
+        let (extent, _, value) = sample(&counter, ts(1));
+
+        assert_eq!(
+            ts(1).unwrap()..ts(1).unwrap(),
+            *extent.unwrap().as_range().unwrap()
+        );
+        assert_eq!("3", value);
+
+        counter.add(2);
+
+        let (extent, _, value) = sample(&counter, ts(3));
+
+        assert_eq!(
+            ts(1).unwrap()..ts(3).unwrap(),
+            *extent.unwrap().as_range().unwrap()
+        );
+        assert_eq!("2", value);
+        assert_eq!(0, counter.get());
+    }
+
+    #[test]
+    fn counter_metadata() {
+        let counter = Counter::new(Path::new_raw("test"), "counter")
+            .with_unit("requests")
+            .with_description("a counter");
+
+        counter.sample_metrics(sampler::from_fn(|metric| {
+            assert_eq!("test", metric.mdl());
+            assert_eq!("counter", metric.name().unwrap());
+            assert_eq!("requests", metric.unit().unwrap());
+            assert_eq!("a counter", metric.description().unwrap());
+            assert!(metric.extent().is_none());
+        }));
+    }
+
+    #[test]
+    fn up_down_counter_cumulative() {
+        let counter = UpDownCounter::new(Path::new_raw("test"), "counter");
+
+        counter.increment();
+        counter.add(-3);
+
+        let (extent, agg, value) = sample(&counter, ts(1));
+
+        assert!(extent.unwrap().is_point());
+        assert_eq!("sum", agg);
+        assert_eq!("-2", value);
+    }
+
+    #[test]
+    fn up_down_counter_delta() {
+        let counter = UpDownCounter::new(Path::new_raw("test"), "counter")
+            .with_temporality(Temporality::Delta);
+
+        counter.decrement();
+
+        let (_, _, value) = sample(&counter, ts(1));
+
+        assert_eq!("-1", value);
+
+        counter.increment();
+        counter.increment();
+
+        let (extent, _, value) = sample(&counter, ts(2));
+
+        assert_eq!(
+            ts(1).unwrap()..ts(2).unwrap(),
+            *extent.unwrap().as_range().unwrap()
+        );
+        assert_eq!("2", value);
+    }
+
+    #[test]
+    fn gauge_last() {
+        let gauge = Gauge::new(Path::new_raw("test"), "gauge");
+
+        let (_, agg, value) = sample(&gauge, ts(1));
+
+        assert_eq!("last", agg);
+        assert_eq!("0", value);
+
+        gauge.set(1.5);
+        gauge.set(-3.25);
+
+        let (extent, _, value) = sample(&gauge, ts(2));
+
+        assert_eq!(ts(2).unwrap(), *extent.unwrap().as_point());
+        assert_eq!("-3.25", value);
+
+        let (_, _, value) = sample(&gauge, ts(3));
+
+        assert_eq!("-3.25", value);
+    }
+
+    #[test]
+    fn histogram_cumulative() {
+        let histogram = Histogram::new(Path::new_raw("test"), "histogram");
+
+        for i in 0..10 {
+            histogram.observe(i as f64);
+        }
+
+        histogram.sample_metrics(
+            sampler::from_fn(|metric| {
+                assert!(metric.extent().unwrap().is_point());
+                assert_eq!("count", metric.agg().unwrap());
+                assert_eq!(10, metric.value().unwrap().cast::<u64>().unwrap());
+                assert_eq!(10, metric.dist_count().unwrap().cast::<u64>().unwrap());
+                assert_eq!(45.0, metric.dist_sum().unwrap().cast::<f64>().unwrap());
+                assert_eq!(0.0, metric.dist_min().unwrap().cast::<f64>().unwrap());
+                assert_eq!(9.0, metric.dist_max().unwrap().cast::<f64>().unwrap());
+                assert_eq!(10, metric.dist_exp_buckets().unwrap().total());
+            })
+            .with_sampled_at(ts(1)),
+        );
+
+        assert_eq!(10, histogram.get().count());
+    }
+
+    #[test]
+    fn histogram_delta() {
+        let histogram =
+            Histogram::new(Path::new_raw("test"), "histogram").with_temporality(Temporality::Delta);
+
+        histogram.observe(1.0);
+        histogram.observe(2.0);
+
+        let (_, _, value) = sample(&histogram, ts(1));
+
+        assert_eq!("2", value);
+
+        histogram.observe(3.0);
+
+        let (extent, _, value) = sample(&histogram, ts(2));
+
+        assert_eq!(
+            ts(1).unwrap()..ts(2).unwrap(),
+            *extent.unwrap().as_range().unwrap()
+        );
+        assert_eq!("1", value);
+        assert_eq!(0, histogram.get().count());
+    }
+
+    #[test]
+    fn histogram_contended() {
+        let histogram = Arc::new(Histogram::new(Path::new_raw("test"), "histogram"));
+
+        let threads = (0..STRIPES * 2)
+            .map(|_| {
+                let histogram = histogram.clone();
+
+                thread::spawn(move || {
+                    for i in 0..1000 {
+                        histogram.observe(i as f64);
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        let distribution = histogram.get();
+
+        assert_eq!(STRIPES as u64 * 2 * 1000, distribution.count());
+        assert_eq!(Some(0.0), distribution.min());
+        assert_eq!(Some(999.0), distribution.max());
+        assert!(distribution.buckets().len() <= distribution.max_buckets());
+    }
+
+    #[test]
+    fn counter_contended() {
+        let counter = Arc::new(Counter::new(Path::new_raw("test"), "counter"));
+
+        let threads = (0..8)
+            .map(|_| {
+                let counter = counter.clone();
+
+                thread::spawn(move || {
+                    for _ in 0..1000 {
+                        counter.increment();
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        assert_eq!(8000, counter.get());
+    }
+}
@@ -0,0 +1,393 @@
+/*!
+The [`SpanMetrics`] type.
+
+[`SpanMetrics`] derives request rate, error, and duration metrics from completed spans. It's a [`crate::emitter::wrapping::Wrapping`] that observes spans as they're emitted, and a [`Source`] that reports what it's observed:
+
+```
+static SPANS: emit::metric::SpanMetrics = emit::metric::SpanMetrics::new(emit::mdl!());
+
+fn main() {
+    let rt = emit::setup()
+        .emit_to(emit::emitter::from_fn(|evt| println!("{evt:?}")))
+        .map_emitter(|emitter| emit::emitter::wrap(emitter, &SPANS))
+        .init();
+
+    let mut reporter = emit::metric::Reporter::new();
+    reporter.add_source(&SPANS);
+
+    // Your app code goes here
+
+    reporter.emit_metrics(rt.emitter());
+
+    rt.blocking_flush(std::time::Duration::from_secs(5));
+}
+```
+
+# Metrics
+
+The following metrics are reported:
+
+| `metric_name` | `metric_agg` | Description |
+| ------------- | ------------ | ----------- |
+| `span_calls` | `count` | The number of completed spans. |
+| `span_errors` | `count` | The number of completed spans that failed. A span has failed if it has a `lvl` of `error`, or an `err` property. |
+| `span_duration` | `count` | An [`crate::metric::exp::Distribution`] of the durations of completed spans, in seconds. |
+
+Each metric is partitioned by the labels of [`SpanMetrics::DEFAULT_LABELS`] by default. These are the module the span was emitted from, as [`KEY_SPAN_MDL`], and its `span_name` and `span_kind`. Use [`SpanMetrics::with_labels`] to partition spans by other properties. See [`crate::metric::Family`] for details on how the number of series is bounded.
+
+Metrics are cumulative by default. Use [`SpanMetrics::with_temporality`] to report them as deltas instead.
+*/
+
+use std::sync::OnceLock;
+
+use emit_core::{
+    and::And,
+    emitter::{Emitter, wrapping::Wrapping},
+    event::ToEvent,
+    path::Path,
+    props::Props,
+    well_known::{KEY_ERR, KEY_EVT_KIND, KEY_LVL, KEY_SPAN_KIND, KEY_SPAN_NAME},
+};
+
+use crate::{
+    kind::Kind,
+    level::Level,
+    metric::{
+        Counter, Family, Histogram, Temporality, family::Limits, instrument::Instrument,
+        sampler::Sampler, source::Source,
+    },
+};
+
+/**
+The property used to label metrics with the module of the span they were derived from.
+*/
+pub const KEY_SPAN_MDL: &'static str = "span_mdl";
+
+/**
+Rate, error, and duration metrics derived from spans.
+
+See the [`mod@crate::metric::span`] module for details.
+*/
+pub struct SpanMetrics {
+    mdl: Path<'static>,
+    labels: &'static [&'static str],
+    temporality: Temporality,
+    limits: Limits,
+    families: OnceLock<Families>,
+}
+
+struct Families {
+    calls: Family<Counter>,
+    errors: Family<Counter>,
+    duration: Family<Histogram>,
+}
+
+impl SpanMetrics {
+    /**
+    The default labels to partition metrics by.
+    */
+    pub const DEFAULT_LABELS: &'static [&'static str] =
+        &[KEY_SPAN_MDL, KEY_SPAN_NAME, KEY_SPAN_KIND];
+
+    /**
+    Create a new set of span metrics, reported from the given module.
+    */
+    pub const fn new(mdl: Path<'static>) -> Self {
+        SpanMetrics {
+            mdl,
+            labels: Self::DEFAULT_LABELS,
+            temporality: Temporality::Cumulative,
+            limits: Limits::new(),
+            families: OnceLock::new(),
+        }
+    }
+
+    /**
+    Set the labels to partition metrics by.
+
+    Labels are the keys of properties on spans, or [`KEY_SPAN_MDL`] for the module the span was emitted from. These labels replace [`SpanMetrics::DEFAULT_LABELS`], so to add a label to the defaults they need to be included too:
+
+    ```
+    static SPANS: emit::metric::SpanMetrics = emit::metric::SpanMetrics::new(emit::mdl!())
+        .with_labels(&["span_mdl", "span_name", "span_kind", "http.route"]);
+    ```
+    */
+    pub const fn with_labels(mut self, labels: &'static [&'static str]) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /**
+    Set how metrics accumulate between samples.
+    */
+    pub const fn with_temporality(mut self, temporality: Temporality) -> Self {
+        self.temporality = temporality;
+        self
+    }
+
+    /**
+    Set the maximum number of series for each metric.
+
+    See [`Family::with_max_series`] for details.
+    */
+    pub const fn with_max_series(mut self, max_series: usize) -> Self {
+        self.limits = self.limits.with_max_series(max_series);
+        self
+    }
+
+    /**
+    Expire series after they've been idle for `intervals` samples.
+
+    See [`Family::with_expire_after`] for details.
+    */
+    pub const fn with_expire_after(mut self, intervals: usize) -> Self {
+        self.limits = self.limits.with_expire_after(intervals);
+        self
+    }
+
+    /**
+    Observe an event.
+
+    If the event is a span then it's counted as a call, and its duration is observed. Any other events are ignored.
+    */
+    pub fn observe(&self, evt: impl ToEvent) {
+        let evt = evt.to_event();
+
+        if evt.props().pull::<Kind, _>(KEY_EVT_KIND) != Some(Kind::Span) {
+            return;
+        }
+
+        let Some(duration) = evt.extent().and_then(|extent| extent.len()) else {
+            return;
+        };
+
+        let families = self.families();
+        let labels = And::new((KEY_SPAN_MDL, evt.mdl()), evt.props());
+
+        families.calls.get(&labels).increment();
+
+        if evt.props().pull::<Level, _>(KEY_LVL) == Some(Level::Error)
+            || evt.props().get(KEY_ERR).is_some()
+        {
+            families.errors.get(&labels).increment();
+        }
+
+        families
+            .duration
+            .get(&labels)
+            .observe(duration.as_secs_f64());
+    }
+
+    fn families(&self) -> &Families {
+        self.families.get_or_init(|| Families {
+            calls: self.family(
+                Counter::new(self.mdl.clone(), "span_calls")
+                    .with_description("The number of completed spans")
+                    .with_temporality(self.temporality),
+            ),
+            errors: self.family(
+                Counter::new(self.mdl.clone(), "span_errors")
+                    .with_description("The number of completed spans that failed")
+                    .with_temporality(self.temporality),
+            ),
+            duration: self.family(
+                Histogram::new(self.mdl.clone(), "span_duration")
+                    .with_unit("s")
+                    .with_description("The duration of completed spans")
+                    .with_temporality(self.temporality),
+            ),
+        })
+    }
+
+    fn family<I: Instrument>(&self, instrument: I) -> Family<I> {
+        self.limits.family(instrument, self.labels)
+    }
+}
+
+impl Wrapping for SpanMetrics {
+    fn wrap<O: Emitter, E: ToEvent>(&self, output: O, evt: E) {
+        let evt = evt.to_event();
+
+        self.observe(&evt);
+
+        output.emit(evt);
+    }
+}
+
+impl Source for SpanMetrics {
+    fn sample_metrics<S: Sampler>(&self, sampler: S) {
+        let Some(families) = self.families.get() else {
+            return;
+        };
+
+        families.calls.sample_metrics(&sampler);
+        families.errors.sample_metrics(&sampler);
+        families.duration.sample_metrics(&sampler);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::{cell::RefCell, time::Duration};
+
+    use emit_core::{
+        event::Event, template::Template, timestamp::Timestamp, value::Value,
+        well_known::KEY_METRIC_NAME,
+    };
+
+    use crate::{metric::sampler, testing::Capture};
+
+    fn ts(millis: u64) -> Timestamp {
+        Timestamp::from_unix(Duration::from_millis(millis)).unwrap()
+    }
+
+    fn span(name: &'static str, millis: u64, props: impl Props) -> Event<'static, impl Props> {
+        Event::new(
+            Path::new_raw("test"),
+            Template::literal(name),
+            ts(0)..ts(millis),
+            And::new(
+                [
+                    (KEY_EVT_KIND, Value::from("span")),
+                    (KEY_SPAN_NAME, Value::from(name)),
+                ],
+                props,
+            ),
+        )
+    }
+
+    fn sample(spans: &SpanMetrics) -> Vec<(String, Option<String>, Option<String>, String)> {
+        let samples = RefCell::new(Vec::new());
+
+        spans.sample_metrics(sampler::from_fn(|metric| {
+            samples.borrow_mut().push((
+                metric.props().pull::<String, _>(KEY_METRIC_NAME).unwrap(),
+                metric.props().pull::<String, _>(KEY_SPAN_MDL),
+                metric.props().pull::<String, _>(KEY_SPAN_NAME),
+                metric.value().unwrap().to_string(),
+            ));
+        }));
+
+        let mut samples = samples.into_inner();
+        samples.sort();
+
+        samples
+    }
+
+    #[test]
+    fn observe_spans() {
+        let spans = SpanMetrics::new(Path::new_raw("spans"));
+        let capture = Capture::new();
+
+        let emitter = emit_core::emitter::wrap(&capture, &spans);
+
+        emitter.emit(span("a", 10, emit_core::empty::Empty));
+        emitter.emit(span("a", 30, (KEY_LVL, Level::Error)));
+        emitter.emit(span("b", 20, (KEY_ERR, "failed")));
+
+        // Events are passed through to the wrapped emitter
+        assert_eq!(3, capture.events().len());
+
+        let samples = sample(&spans);
+
+        let expected = |name: &str, span_name: &str, value: &str| {
+            (
+                name.to_string(),
+                Some("test".to_string()),
+                Some(span_name.to_string()),
+                value.to_string(),
+            )
+        };
+
+        assert_eq!(
+            vec![
+                expected("span_calls", "a", "2"),
+                expected("span_calls", "b", "1"),
+                expected("span_duration", "a", "2"),
+                expected("span_duration", "b", "1"),
+                expected("span_errors", "a", "1"),
+                expected("span_errors", "b", "1"),
+            ],
+            samples
+        );
+    }
+
+    #[test]
+    fn observe_span_durations() {
+        let spans = SpanMetrics::new(Path::new_raw("spans"));
+
+        spans.observe(span("a", 10, emit_core::empty::Empty));
+        spans.observe(span("a", 30, emit_core::empty::Empty));
+
+        let distribution = RefCell::new(None);
+        spans.sample_metrics(sampler::from_fn(|metric| {
+            if metric.name().unwrap() == "span_duration" {
+                assert_eq!(Some("s"), metric.unit().as_ref().map(|unit| unit.get()));
+                assert_eq!(Some(Path::new_raw("spans")), Some(metric.mdl().clone()));
+
+                *distribution.borrow_mut() = Some((
+                    metric.props().pull::<f64, _>("dist_min").unwrap(),
+                    metric.props().pull::<f64, _>("dist_max").unwrap(),
+                ));
+            }
+        }));
+
+        assert_eq!(Some((0.01, 0.03)), distribution.into_inner());
+    }
+
+    #[test]
+    fn observe_ignores_non_spans() {
+        let spans = SpanMetrics::new(Path::new_raw("spans"));
+
+        // Not a span
+        spans.observe(Event::new(
+            Path::new_raw("test"),
+            Template::literal("log"),
+            ts(0)..ts(10),
+            (KEY_SPAN_NAME, "a"),
+        ));
+
+        // No duration
+        spans.observe(Event::new(
+            Path::new_raw("test"),
+            Template::literal("span"),
+            ts(0),
+            [(KEY_EVT_KIND, "span"), (KEY_SPAN_NAME, "a")],
+        ));
+
+        assert!(sample(&spans).is_empty());
+    }
+
+    #[test]
+    fn observe_with_labels() {
+        let spans = SpanMetrics::new(Path::new_raw("spans")).with_labels(&["route"]);
+
+        spans.observe(span("a", 10, ("route", "/a")));
+        spans.observe(span("b", 10, ("route", "/a")));
+        spans.observe(span("c", 10, ("route", "/b")));
+
+        let calls = RefCell::new(Vec::new());
+        spans.sample_metrics(sampler::from_fn(|metric| {
+            if metric.name().unwrap() == "span_calls" {
+                assert!(metric.props().get(KEY_SPAN_NAME).is_none());
+
+                calls.borrow_mut().push((
+                    metric.props().pull::<String, _>("route").unwrap(),
+                    metric.value().unwrap().to_string(),
+                ));
+            }
+        }));
+        let mut calls = calls.into_inner();
+        calls.sort();
+
+        assert_eq!(
+            vec![
+                ("/a".to_string(), "2".to_string()),
+                ("/b".to_string(), "1".to_string())
+            ],
+            calls
+        );
+    }
+}
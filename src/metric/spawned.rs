@@ -0,0 +1,581 @@
+use core::time::Duration;
+use std::{
+    boxed::Box,
+    sync::{Arc, Condvar, Mutex},
+    time::Instant,
+};
+
+use emit_core::{
+    emitter::{Emitter, ErasedEmitter},
+    event::ToEvent,
+    path::Path,
+    timestamp::Timestamp,
+};
+
+use crate::metric::{Gauge, Reporter, sampler, sampler::Sampler as _, source::Source as _};
+
+impl Reporter {
+    /**
+    Spawn a background thread that samples the reporter at a fixed `interval`, emitting metrics through [`crate::runtime::shared`].
+
+    Samples are aligned to multiples of `interval` since the Unix epoch. A sample taken every 30 seconds will happen at `:00` and `:30` past each minute, and use those timestamps for the extents of its metrics.
+
+    The returned [`ReporterHandle`] can be used to take a final sample before the application exits. See [`ReporterHandle`] for details.
+
+    # Panics
+
+    This method will panic if `interval` is zero.
+    */
+    #[cfg(feature = "implicit_rt")]
+    pub fn spawn(self, interval: Duration) -> ReporterHandle {
+        self.spawn_to(interval, crate::runtime::shared())
+    }
+
+    /**
+    Spawn a background thread that samples the reporter at a fixed `interval`, emitting metrics through the given `emitter`.
+
+    See [`Reporter::spawn`] for details.
+
+    # Panics
+
+    This method will panic if `interval` is zero.
+    */
+    pub fn spawn_to(
+        self,
+        interval: Duration,
+        emitter: impl Emitter + Send + Sync + 'static,
+    ) -> ReporterHandle {
+        let handle = ReporterHandle::new(self, interval, emitter);
+
+        let shared = handle.shared.clone();
+        std::thread::Builder::new()
+            .name("emit_metric_reporter".into())
+            .spawn(move || shared.run_thread())
+            .expect("failed to spawn metric reporter thread");
+
+        handle
+    }
+
+    /**
+    Spawn a `tokio` task that samples the reporter at a fixed `interval`, emitting metrics through [`crate::runtime::shared`].
+
+    See [`Reporter::spawn`] for details.
+
+    # Panics
+
+    This method will panic if `interval` is zero, or if it's called outside of a `tokio` runtime.
+    */
+    #[cfg(all(feature = "implicit_rt", feature = "tokio"))]
+    pub fn spawn_tokio(self, interval: Duration) -> ReporterHandle {
+        self.spawn_tokio_to(interval, crate::runtime::shared())
+    }
+
+    /**
+    Spawn a `tokio` task that samples the reporter at a fixed `interval`, emitting metrics through the given `emitter`.
+
+    See [`Reporter::spawn`] for details.
+
+    # Panics
+
+    This method will panic if `interval` is zero, or if it's called outside of a `tokio` runtime.
+    */
+    #[cfg(feature = "tokio")]
+    pub fn spawn_tokio_to(
+        self,
+        interval: Duration,
+        emitter: impl Emitter + Send + Sync + 'static,
+    ) -> ReporterHandle {
+        let handle = ReporterHandle::new(self, interval, emitter);
+
+        tokio::spawn(handle.shared.clone().run_tokio());
+
+        handle
+    }
+}
+
+/**
+A handle to a [`Reporter`] that's sampled in the background.
+
+This type is returned by [`Reporter::spawn`] or [`Reporter::spawn_tokio`]. Dropping the handle doesn't stop the background sampling. Call [`ReporterHandle::shutdown`] to stop it.
+
+Along with the metrics from its sources, the reporter will also sample:
+
+- `reporter_sample_skew`: The time in seconds between when a sample was scheduled and when it actually started.
+- `reporter_sample_duration`: The time in seconds it took to sample the reporter's sources.
+
+# Final samples
+
+Call [`ReporterHandle::emit_metrics`] before your application exits to take a final sample. Alternatively, pass the handle to [`crate::Setup::with_reporter`], so a final sample is taken whenever the runtime is flushed:
+
+```
+fn main() {
+    let mut reporter = emit::metric::Reporter::new();
+
+    // Add sources to the reporter
+
+    let reporter = reporter.spawn(std::time::Duration::from_secs(30));
+
+    let rt = emit::setup()
+        .emit_to(emit::emitter::from_fn(|evt| println!("{evt:?}")))
+        .with_reporter(reporter)
+        .init();
+
+    // Your app code goes here
+
+    // Takes a final sample from the reporter before flushing
+    rt.blocking_flush(std::time::Duration::from_secs(5));
+}
+```
+*/
+#[derive(Clone)]
+pub struct ReporterHandle {
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    reporter: Reporter,
+    emitter: Box<dyn ErasedEmitter + Send + Sync>,
+    interval: Duration,
+    skew: Gauge,
+    duration: Gauge,
+    // Held while sampling so scheduled and final samples don't overlap
+    sampling: Mutex<()>,
+    is_shutdown: Mutex<bool>,
+    shutdown_thread: Condvar,
+    #[cfg(feature = "tokio")]
+    shutdown_task: tokio::sync::Notify,
+}
+
+impl ReporterHandle {
+    fn new(
+        reporter: Reporter,
+        interval: Duration,
+        emitter: impl Emitter + Send + Sync + 'static,
+    ) -> Self {
+        assert!(
+            !interval.is_zero(),
+            "the metric reporter interval must be non-zero"
+        );
+
+        ReporterHandle {
+            shared: Arc::new(Shared {
+                reporter,
+                emitter: Box::new(emitter),
+                interval,
+                skew: Gauge::new(Path::new_raw("emit::metric"), "reporter_sample_skew")
+                    .with_unit("s"),
+                duration: Gauge::new(Path::new_raw("emit::metric"), "reporter_sample_duration")
+                    .with_unit("s"),
+                sampling: Mutex::new(()),
+                is_shutdown: Mutex::new(false),
+                shutdown_thread: Condvar::new(),
+                #[cfg(feature = "tokio")]
+                shutdown_task: tokio::sync::Notify::new(),
+            }),
+        }
+    }
+
+    /**
+    Get the interval the reporter is sampled at.
+    */
+    pub fn interval(&self) -> Duration {
+        self.shared.interval
+    }
+
+    /**
+    Sample the reporter immediately, emitting its metrics.
+
+    This method doesn't flush the emitter the reporter emits its metrics to.
+    */
+    pub fn emit_metrics(&self) {
+        self.shared.sample(self.shared.reporter.now());
+    }
+
+    /**
+    Sample the reporter immediately, then flush the emitter it emits its metrics to.
+
+    This method forwards to [`Emitter::blocking_flush`], which has details on how the timeout is handled.
+    */
+    pub fn blocking_flush(&self, timeout: Duration) -> bool {
+        self.emit_metrics();
+
+        self.shared.emitter.blocking_flush(timeout)
+    }
+
+    /**
+    Stop sampling the reporter in the background, taking a final sample.
+
+    Calling this method more than once has no effect.
+    */
+    pub fn shutdown(&self) {
+        {
+            let mut is_shutdown = self
+                .shared
+                .is_shutdown
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+
+            if *is_shutdown {
+                return;
+            }
+
+            *is_shutdown = true;
+        }
+
+        self.shared.shutdown_thread.notify_all();
+
+        #[cfg(feature = "tokio")]
+        self.shared.shutdown_task.notify_one();
+
+        self.emit_metrics();
+    }
+
+    /**
+    Whether [`ReporterHandle::shutdown`] has been called.
+    */
+    pub fn is_shutdown(&self) -> bool {
+        *self
+            .shared
+            .is_shutdown
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Shared {
+    fn run_thread(&self) {
+        loop {
+            let (wait, at) = self.next_sample();
+            let deadline = Instant::now() + wait;
+
+            let mut is_shutdown = self.is_shutdown.lock().unwrap_or_else(|e| e.into_inner());
+
+            loop {
+                if *is_shutdown {
+                    return;
+                }
+
+                let Some(timeout) = deadline.checked_duration_since(Instant::now()) else {
+                    break;
+                };
+
+                is_shutdown = self
+                    .shutdown_thread
+                    .wait_timeout(is_shutdown, timeout)
+                    .unwrap_or_else(|e| e.into_inner())
+                    .0;
+            }
+
+            drop(is_shutdown);
+
+            self.sample(at);
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    async fn run_tokio(self: Arc<Self>) {
+        loop {
+            if *self.is_shutdown.lock().unwrap_or_else(|e| e.into_inner()) {
+                return;
+            }
+
+            let (wait, at) = self.next_sample();
+
+            if tokio::time::timeout(wait, self.shutdown_task.notified())
+                .await
+                .is_ok()
+            {
+                return;
+            }
+
+            self.sample(at);
+        }
+    }
+
+    // Get the time to wait until the next sample, along with its timestamp
+    fn next_sample(&self) -> (Duration, Option<Timestamp>) {
+        let Some(now) = self.reporter.now() else {
+            return (self.interval, None);
+        };
+
+        let now = now.to_unix();
+        let interval = self.interval.as_nanos();
+
+        // Align the next sample to a multiple of the interval
+        // If that can't be represented then just wait for the interval
+        let Some(next) = (now.as_nanos() / interval + 1)
+            .checked_mul(interval)
+            .and_then(|next| u64::try_from(next).ok())
+            .map(Duration::from_nanos)
+        else {
+            return (
+                self.interval,
+                now.checked_add(self.interval)
+                    .and_then(Timestamp::from_unix),
+            );
+        };
+
+        (next.saturating_sub(now), Timestamp::from_unix(next))
+    }
+
+    fn sample(&self, at: Option<Timestamp>) {
+        let _sampling = self.sampling.lock().unwrap_or_else(|e| e.into_inner());
+
+        let start = Instant::now();
+
+        let skew = at
+            .zip(self.reporter.now())
+            .and_then(|(at, now)| now.duration_since(at))
+            .unwrap_or_default();
+
+        self.reporter
+            .sample_metrics_at(at, sampler::from_emitter(&self.emitter));
+
+        self.skew.set(skew.as_secs_f64());
+        self.duration.set(start.elapsed().as_secs_f64());
+
+        let sampler = sampler::from_emitter(&self.emitter).with_sampled_at(at);
+
+        self.skew.sample_metrics(&sampler);
+        self.duration.sample_metrics(&sampler);
+    }
+}
+
+/**
+An [`Emitter`] that takes a final sample from a [`ReporterHandle`] before flushing.
+
+This type is created by [`crate::Setup::with_reporter`].
+*/
+pub struct ReportOnFlush<E> {
+    emitter: E,
+    reporter: ReporterHandle,
+}
+
+impl<E> ReportOnFlush<E> {
+    /**
+    Wrap the given `emitter`, taking a final sample from `reporter` before it's flushed.
+    */
+    pub fn new(emitter: E, reporter: ReporterHandle) -> Self {
+        ReportOnFlush { emitter, reporter }
+    }
+}
+
+impl<E: Emitter> Emitter for ReportOnFlush<E> {
+    fn emit<T: ToEvent>(&self, evt: T) {
+        self.emitter.emit(evt)
+    }
+
+    fn blocking_flush(&self, timeout: Duration) -> bool {
+        self.reporter.emit_metrics();
+
+        self.emitter.blocking_flush(timeout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        thread,
+    };
+
+    use emit_core::props::Props as _;
+
+    use crate::{
+        metric::{Counter, Temporality},
+        testing::Capture,
+    };
+
+    static REPORTED: Counter = Counter::new(Path::new_raw("test"), "reported");
+
+    fn reporter() -> Reporter {
+        let mut reporter = Reporter::new();
+        reporter.add_source(&REPORTED);
+
+        reporter
+    }
+
+    fn wait_for(capture: &Capture, name: &str, count: usize) {
+        let start = Instant::now();
+
+        while count_samples(capture, name) < count {
+            assert!(
+                start.elapsed() < Duration::from_secs(10),
+                "timed out waiting for samples"
+            );
+
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    fn count_samples(capture: &Capture, name: &str) -> usize {
+        capture
+            .events()
+            .iter()
+            .filter(|evt| {
+                evt.props()
+                    .get("metric_name")
+                    .is_some_and(|metric_name| metric_name.to_string() == name)
+            })
+            .count()
+    }
+
+    #[test]
+    #[should_panic]
+    fn spawn_zero_interval() {
+        let _ = reporter().spawn_to(Duration::ZERO, crate::Empty);
+    }
+
+    #[test]
+    fn next_sample_unrepresentable() {
+        // Nanoseconds since the Unix epoch this far out don't fit in a `u64`
+        let now = Timestamp::from_unix(Duration::from_secs(253_402_300_000)).unwrap();
+
+        let mut reporter = reporter();
+        reporter.normalize_with_clock(crate::testing::ManualClock::new(now));
+
+        let handle = ReporterHandle::new(reporter, Duration::from_secs(30), crate::Empty);
+
+        assert_eq!(
+            (
+                Duration::from_secs(30),
+                Timestamp::from_unix(Duration::from_secs(253_402_300_030))
+            ),
+            handle.shared.next_sample()
+        );
+    }
+
+    #[test]
+    fn spawn_samples_aligned() {
+        let capture = Capture::new();
+
+        let handle = reporter().spawn_to(Duration::from_millis(20), capture.clone());
+
+        wait_for(&capture, "reported", 2);
+
+        let events = capture.events();
+
+        handle.shutdown();
+
+        for evt in events {
+            let ts = evt.extent().unwrap().as_point().to_unix();
+
+            if evt.props().get("metric_name").unwrap().to_string() == "reported" {
+                assert_eq!(0, ts.as_nanos() % Duration::from_millis(20).as_nanos());
+            }
+        }
+
+        assert!(count_samples(&capture, "reporter_sample_skew") >= 2);
+        assert!(count_samples(&capture, "reporter_sample_duration") >= 2);
+    }
+
+    #[test]
+    fn shutdown_takes_final_sample() {
+        let capture = Capture::new();
+
+        let handle = reporter().spawn_to(Duration::from_secs(60 * 60), capture.clone());
+
+        assert!(!handle.is_shutdown());
+
+        handle.shutdown();
+
+        assert!(handle.is_shutdown());
+        assert_eq!(1, count_samples(&capture, "reported"));
+
+        // Shutting down again doesn't sample
+        handle.shutdown();
+
+        assert_eq!(1, count_samples(&capture, "reported"));
+    }
+
+    #[test]
+    fn blocking_flush_takes_final_sample() {
+        struct Flushes {
+            capture: Capture,
+            flushes: AtomicUsize,
+        }
+
+        impl Emitter for Flushes {
+            fn emit<E: ToEvent>(&self, evt: E) {
+                self.capture.emit(evt)
+            }
+
+            fn blocking_flush(&self, _: Duration) -> bool {
+                self.flushes.fetch_add(1, Ordering::Relaxed);
+
+                true
+            }
+        }
+
+        let emitter = Arc::new(Flushes {
+            capture: Capture::new(),
+            flushes: AtomicUsize::new(0),
+        });
+
+        let handle = reporter().spawn_to(Duration::from_secs(60 * 60), emitter.clone());
+
+        assert!(handle.blocking_flush(Duration::from_secs(1)));
+
+        assert_eq!(1, count_samples(&emitter.capture, "reported"));
+        assert_eq!(1, emitter.flushes.load(Ordering::Relaxed));
+
+        handle.shutdown();
+    }
+
+    #[test]
+    fn report_on_flush() {
+        let delta = Arc::new(
+            Counter::new(Path::new_raw("test"), "delta").with_temporality(Temporality::Delta),
+        );
+
+        let mut reporter = Reporter::new();
+        reporter.add_source(delta.clone());
+
+        let capture = Capture::new();
+        let handle = reporter.spawn_to(Duration::from_secs(60 * 60), capture.clone());
+
+        let emitter = ReportOnFlush::new(crate::Empty, handle.clone());
+
+        delta.add(3);
+
+        assert!(emitter.blocking_flush(Duration::from_secs(1)));
+
+        let events = capture.events();
+        let evt = events
+            .iter()
+            .find(|evt| evt.props().get("metric_name").unwrap().to_string() == "delta")
+            .unwrap();
+
+        assert_eq!(3, evt.props().pull::<u64, _>("metric_value").unwrap());
+
+        handle.shutdown();
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn spawn_tokio_samples() {
+        let capture = Capture::new();
+
+        let handle = reporter().spawn_tokio_to(Duration::from_millis(20), capture.clone());
+
+        let start = Instant::now();
+        while count_samples(&capture, "reported") < 2 {
+            assert!(
+                start.elapsed() < Duration::from_secs(10),
+                "timed out waiting for samples"
+            );
+
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+
+        handle.shutdown();
+
+        let sampled = count_samples(&capture, "reported");
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert_eq!(sampled, count_samples(&capture, "reported"));
+    }
+}
@@ -0,0 +1,412 @@
+/*!
+The [`RuntimeMetrics`] type.
+
+[`RuntimeMetrics`] is a [`Source`] that reports the metrics of a `tokio` runtime, like the number of alive tasks and how long its workers have been busy for. It can be sampled along with any other sources through a [`crate::metric::Reporter`]:
+
+```
+# #[tokio::main] async fn main() {
+let mut reporter = emit::metric::Reporter::new();
+
+reporter.add_source(emit::metric::tokio::RuntimeMetrics::current());
+# }
+```
+
+# Metrics
+
+The following metrics are always reported:
+
+| `metric_name` | `metric_agg` | Description |
+| ------------- | ------------ | ----------- |
+| `num_workers` | `last` | The number of worker threads used by the runtime. |
+| `num_alive_tasks` | `last` | The number of tasks currently alive in the runtime. |
+| `global_queue_depth` | `last` | The number of tasks currently scheduled in the runtime's global queue. |
+
+On platforms with 64-bit atomics, the following metrics are also reported for each worker, with a `worker` property for the index of the worker:
+
+| `metric_name` | `metric_agg` | Description |
+| ------------- | ------------ | ----------- |
+| `worker_busy_duration` | `count` | The amount of time in seconds the worker has been busy for. |
+| `worker_park_count` | `count` | The number of times the worker has parked. |
+
+When compiling with `--cfg tokio_unstable`, the following metrics are also reported:
+
+| `metric_name` | `metric_agg` | Description |
+| ------------- | ------------ | ----------- |
+| `num_blocking_threads` | `last` | The number of threads in the runtime's blocking pool. |
+| `num_idle_blocking_threads` | `last` | The number of idle threads in the runtime's blocking pool. |
+| `blocking_queue_depth` | `last` | The number of tasks currently scheduled in the runtime's blocking pool. |
+| `spawned_tasks_count` | `count` | The number of tasks spawned in the runtime. |
+
+# Temporality
+
+Metrics with the `count` aggregation are cumulative by default, reporting their total since the runtime was created. Use [`RuntimeMetrics::with_temporality`] to report them as deltas instead. Metrics with the `last` aggregation always use a point extent.
+
+See [`crate::metric::instrument`] for details on how the extents of samples are determined.
+*/
+
+#[cfg(target_has_atomic = "64")]
+use std::{sync::Mutex, time::Duration};
+
+use emit_core::{
+    empty::Empty,
+    extent::{Extent, ToExtent},
+    path::Path,
+    timestamp::Timestamp,
+    value::Value,
+    well_known::{METRIC_AGG_COUNT, METRIC_AGG_LAST},
+};
+
+use ::tokio::runtime::Handle;
+
+use crate::metric::{Metric, Temporality, sampler::Sampler, source::Source};
+
+/**
+A [`Source`] for the metrics of a `tokio` runtime.
+
+See the [`mod@crate::metric::tokio`] module for details.
+*/
+pub struct RuntimeMetrics {
+    handle: Handle,
+    mdl: Path<'static>,
+    temporality: Temporality,
+    #[cfg(target_has_atomic = "64")]
+    last: Mutex<Totals>,
+}
+
+#[cfg(target_has_atomic = "64")]
+#[derive(Default)]
+struct Totals {
+    sampled_at: Option<Timestamp>,
+    workers: Vec<WorkerTotals>,
+    #[cfg(all(tokio_unstable, target_has_atomic = "64"))]
+    spawned_tasks: u64,
+}
+
+#[cfg(target_has_atomic = "64")]
+#[derive(Default, Clone, Copy)]
+struct WorkerTotals {
+    busy: Duration,
+    parks: u64,
+}
+
+impl RuntimeMetrics {
+    /**
+    Report the metrics of the runtime for the given `handle`.
+
+    Metric samples use `tokio` as their module by default.
+    */
+    pub fn new(handle: Handle) -> Self {
+        RuntimeMetrics {
+            handle,
+            mdl: Path::new_raw("tokio"),
+            temporality: Temporality::Cumulative,
+            #[cfg(target_has_atomic = "64")]
+            last: Mutex::new(Totals::default()),
+        }
+    }
+
+    /**
+    Report the metrics of the current runtime.
+
+    # Panics
+
+    This method will panic if it's called outside of a `tokio` runtime.
+    */
+    pub fn current() -> Self {
+        Self::new(Handle::current())
+    }
+
+    /**
+    Set the module to use for metric samples.
+    */
+    pub fn with_mdl(mut self, mdl: impl Into<Path<'static>>) -> Self {
+        self.mdl = mdl.into();
+        self
+    }
+
+    /**
+    Set how metrics with the `count` aggregation accumulate between samples.
+    */
+    pub fn with_temporality(mut self, temporality: Temporality) -> Self {
+        self.temporality = temporality;
+        self
+    }
+
+    /**
+    Get the handle to the runtime being reported.
+    */
+    pub fn handle(&self) -> &Handle {
+        &self.handle
+    }
+
+    fn sample_last<S: Sampler>(
+        &self,
+        sampler: &S,
+        now: Option<Timestamp>,
+        name: &'static str,
+        value: usize,
+    ) {
+        sampler.metric(
+            Metric::new(self.mdl.by_ref(), now, Empty)
+                .with_name(name)
+                .with_agg(METRIC_AGG_LAST)
+                .with_value(value),
+        );
+    }
+
+    #[cfg(target_has_atomic = "64")]
+    fn sample_counts<S: Sampler>(
+        &self,
+        sampler: &S,
+        now: Option<Timestamp>,
+        metrics: &::tokio::runtime::RuntimeMetrics,
+    ) {
+        let workers = (0..metrics.num_workers())
+            .map(|worker| WorkerTotals {
+                busy: metrics.worker_total_busy_duration(worker),
+                parks: metrics.worker_park_count(worker),
+            })
+            .collect::<Vec<_>>();
+
+        #[cfg(tokio_unstable)]
+        let spawned_tasks = metrics.spawned_tasks_count();
+
+        let (extent, workers, spawned_tasks) = match self.temporality {
+            Temporality::Cumulative => {
+                #[cfg(tokio_unstable)]
+                let spawned_tasks = Some(spawned_tasks);
+                #[cfg(not(tokio_unstable))]
+                let spawned_tasks = None::<u64>;
+
+                (now.to_extent(), workers, spawned_tasks)
+            }
+            Temporality::Delta => {
+                let mut last = self.last.lock().unwrap_or_else(|e| e.into_inner());
+
+                let deltas = workers
+                    .iter()
+                    .enumerate()
+                    .map(|(worker, totals)| {
+                        let last = last.workers.get(worker).copied().unwrap_or_default();
+
+                        WorkerTotals {
+                            busy: totals.busy.saturating_sub(last.busy),
+                            parks: totals.parks.saturating_sub(last.parks),
+                        }
+                    })
+                    .collect();
+
+                last.workers = workers;
+
+                #[cfg(tokio_unstable)]
+                let spawned_tasks = Some(
+                    spawned_tasks
+                        .saturating_sub(core::mem::replace(&mut last.spawned_tasks, spawned_tasks)),
+                );
+                #[cfg(not(tokio_unstable))]
+                let spawned_tasks = None::<u64>;
+
+                let extent = now.map(|now| {
+                    let start = last.sampled_at.replace(now).unwrap_or(now);
+
+                    Extent::range(start..now)
+                });
+
+                (extent, deltas, spawned_tasks)
+            }
+        };
+
+        let sample_count = |name: &'static str,
+                            unit: Option<&'static str>,
+                            value: Value,
+                            worker: Option<usize>| {
+            let metric = Metric::new(
+                self.mdl.by_ref(),
+                extent.clone(),
+                worker.map(|worker| ("worker", worker)),
+            )
+            .with_name(name)
+            .with_agg(METRIC_AGG_COUNT)
+            .with_value(value);
+
+            match unit {
+                Some(unit) => sampler.metric(metric.with_unit(unit)),
+                None => sampler.metric(metric),
+            }
+        };
+
+        for (worker, totals) in workers.iter().enumerate() {
+            sample_count(
+                "worker_busy_duration",
+                Some("s"),
+                totals.busy.as_secs_f64().into(),
+                Some(worker),
+            );
+            sample_count("worker_park_count", None, totals.parks.into(), Some(worker));
+        }
+
+        if let Some(spawned_tasks) = spawned_tasks {
+            sample_count("spawned_tasks_count", None, spawned_tasks.into(), None);
+        }
+    }
+}
+
+impl Source for RuntimeMetrics {
+    fn sample_metrics<S: Sampler>(&self, sampler: S) {
+        let metrics = self.handle.metrics();
+        let now = sampler.sampled_at();
+
+        self.sample_last(&sampler, now, "num_workers", metrics.num_workers());
+        self.sample_last(&sampler, now, "num_alive_tasks", metrics.num_alive_tasks());
+        self.sample_last(
+            &sampler,
+            now,
+            "global_queue_depth",
+            metrics.global_queue_depth(),
+        );
+
+        #[cfg(tokio_unstable)]
+        {
+            self.sample_last(
+                &sampler,
+                now,
+                "num_blocking_threads",
+                metrics.num_blocking_threads(),
+            );
+            self.sample_last(
+                &sampler,
+                now,
+                "num_idle_blocking_threads",
+                metrics.num_idle_blocking_threads(),
+            );
+            self.sample_last(
+                &sampler,
+                now,
+                "blocking_queue_depth",
+                metrics.blocking_queue_depth(),
+            );
+        }
+
+        #[cfg(target_has_atomic = "64")]
+        {
+            self.sample_counts(&sampler, now, &metrics);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::{cell::RefCell, time::Duration};
+
+    use emit_core::props::Props as _;
+
+    use crate::metric::sampler;
+
+    type Sample = (String, Option<usize>, Option<Extent>, f64);
+
+    fn sample(source: &RuntimeMetrics, now: Option<Timestamp>) -> Vec<Sample> {
+        let samples = RefCell::new(Vec::new());
+
+        source.sample_metrics(
+            sampler::from_fn(|metric| {
+                samples.borrow_mut().push((
+                    metric.name().unwrap().to_string(),
+                    metric.props().pull::<usize, _>("worker"),
+                    metric.extent().cloned(),
+                    metric.value().unwrap().cast::<f64>().unwrap(),
+                ));
+            })
+            .with_sampled_at(now),
+        );
+
+        samples.into_inner()
+    }
+
+    fn find<'a>(samples: &'a [Sample], name: &str) -> impl Iterator<Item = &'a Sample> {
+        samples.iter().filter(move |(n, ..)| n == name)
+    }
+
+    fn ts(secs: u64) -> Timestamp {
+        Timestamp::from_unix(Duration::from_secs(secs)).unwrap()
+    }
+
+    fn runtime() -> ::tokio::runtime::Runtime {
+        ::tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn sample_runtime() {
+        let rt = runtime();
+        let source = RuntimeMetrics::new(rt.handle().clone());
+
+        let task = rt.spawn(std::future::pending::<()>());
+
+        let samples = sample(&source, Some(ts(1)));
+
+        assert_eq!(2.0, find(&samples, "num_workers").next().unwrap().3);
+        assert_eq!(1.0, find(&samples, "num_alive_tasks").next().unwrap().3);
+        assert!(find(&samples, "global_queue_depth").next().is_some());
+
+        #[cfg(target_has_atomic = "64")]
+        {
+            let busy = find(&samples, "worker_busy_duration").collect::<Vec<_>>();
+
+            assert_eq!(2, busy.len());
+            assert_eq!(Some(0), busy[0].1);
+            assert_eq!(Some(1), busy[1].1);
+            assert!(busy[0].2.as_ref().unwrap().is_point());
+
+            assert_eq!(2, find(&samples, "worker_park_count").count());
+        }
+
+        task.abort();
+    }
+
+    #[test]
+    #[cfg(target_has_atomic = "64")]
+    fn sample_runtime_delta() {
+        let rt = runtime();
+        let source = RuntimeMetrics::new(rt.handle().clone()).with_temporality(Temporality::Delta);
+
+        rt.block_on(async {
+            ::tokio::task::yield_now().await;
+        });
+
+        let first = sample(&source, Some(ts(1)));
+        let second = sample(&source, Some(ts(2)));
+
+        for samples in [&first, &second] {
+            for (_, _, extent, value) in find(samples, "worker_park_count") {
+                assert!(extent.as_ref().unwrap().is_range());
+                assert!(*value >= 0.0);
+            }
+        }
+
+        let (_, _, extent, _) = find(&second, "worker_busy_duration").next().unwrap();
+        let range = extent.as_ref().unwrap().as_range().unwrap();
+
+        assert_eq!(ts(1)..ts(2), *range);
+
+        // Gauges are always points
+        let (_, _, extent, _) = find(&second, "num_workers").next().unwrap();
+        assert!(extent.as_ref().unwrap().is_point());
+    }
+
+    #[test]
+    fn sample_current_thread_runtime() {
+        let rt = ::tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+
+        let source = rt.block_on(async { RuntimeMetrics::current() });
+
+        let samples = sample(&source, None);
+
+        assert_eq!(1.0, find(&samples, "num_workers").next().unwrap().3);
+    }
+}
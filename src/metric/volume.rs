@@ -0,0 +1,489 @@
+/*!
+The [`EventVolume`] type.
+
+[`EventVolume`] counts the events passing through an emitter, partitioned by their level, module, and kind. It's a [`crate::emitter::wrapping::Wrapping`] that observes events as they're emitted, and a [`Source`] that reports what it's observed:
+
+```
+static VOLUME: emit::metric::EventVolume = emit::metric::EventVolume::new(emit::mdl!());
+
+fn main() {
+    let rt = emit::setup()
+        .emit_to(emit::emitter::from_fn(|evt| println!("{evt:?}")))
+        .map_emitter(|emitter| emit::emitter::wrap(emitter, &VOLUME))
+        .init();
+
+    let mut reporter = emit::metric::Reporter::new();
+    reporter.add_source(&VOLUME);
+
+    // Your app code goes here
+
+    // Report through the emitter wrapped by `VOLUME`
+    reporter.emit_metrics(rt.emitter().emitter());
+
+    rt.blocking_flush(std::time::Duration::from_secs(5));
+}
+```
+
+# Dropped events
+
+Events that are rejected by the runtime's filter never reach its emitter, so they aren't observed by the wrapping. Use [`EventVolume::count_dropped`] to wrap the filter, and count the events it rejects:
+
+```
+static VOLUME: emit::metric::EventVolume = emit::metric::EventVolume::new(emit::mdl!());
+
+fn main() {
+    let rt = emit::setup()
+        .emit_to(emit::emitter::from_fn(|evt| println!("{evt:?}")))
+        .map_emitter(|emitter| emit::emitter::wrap(emitter, &VOLUME))
+        .emit_when(VOLUME.count_dropped(emit::level::min_filter(emit::Level::Warn)))
+        .init();
+
+    // Your app code goes here
+
+    rt.blocking_flush(std::time::Duration::from_secs(5));
+}
+```
+
+# Metrics
+
+The following metrics are reported:
+
+| `metric_name` | `metric_agg` | Description |
+| ------------- | ------------ | ----------- |
+| `events` | `count` | The number of events emitted. |
+| `events_dropped` | `count` | The number of events rejected by a filter wrapped in [`EventVolume::count_dropped`]. |
+
+Each metric is partitioned by the `lvl` and `evt_kind` of events, and their module, as [`KEY_EVENT_MDL`]. Modules can be truncated to a number of segments with [`EventVolume::with_mdl_depth`] to keep the number of series small.
+
+Metric samples, events with an `evt_kind` of `metric`, aren't counted by default. Use [`EventVolume::with_count_metrics`] to count them too.
+
+Metrics are deltas by default. Use [`EventVolume::with_temporality`] to report them as cumulative instead.
+*/
+
+use std::{sync::OnceLock, vec::Vec};
+
+use emit_core::{
+    and::And,
+    emitter::{Emitter, wrapping::Wrapping},
+    event::ToEvent,
+    filter::Filter,
+    path::Path,
+    props::Props,
+    well_known::{KEY_EVT_KIND, KEY_LVL},
+};
+
+use crate::{
+    kind::Kind,
+    metric::{
+        Counter, Family, Temporality, family::Limits, instrument::Instrument, sampler::Sampler,
+        source::Source,
+    },
+};
+
+/**
+The property used to label metrics with the module of the events they were derived from.
+*/
+pub const KEY_EVENT_MDL: &'static str = "event_mdl";
+
+/**
+Counts of events by their level, module, and kind.
+
+See the [`mod@crate::metric::volume`] module for details.
+*/
+pub struct EventVolume {
+    mdl: Path<'static>,
+    mdl_depth: Option<usize>,
+    temporality: Temporality,
+    count_metrics: bool,
+    limits: Limits,
+    families: OnceLock<Families>,
+}
+
+struct Families {
+    events: Family<Counter>,
+    dropped: Family<Counter>,
+}
+
+/**
+A [`Filter`] that counts the events rejected by another.
+
+This type is returned by [`EventVolume::count_dropped`].
+*/
+pub struct CountDropped<'a, F> {
+    volume: &'a EventVolume,
+    filter: F,
+}
+
+impl EventVolume {
+    const LABELS: &'static [&'static str] = &[KEY_LVL, KEY_EVENT_MDL, KEY_EVT_KIND];
+
+    /**
+    Create a new set of event counts, reported from the given module.
+    */
+    pub const fn new(mdl: Path<'static>) -> Self {
+        EventVolume {
+            mdl,
+            mdl_depth: None,
+            temporality: Temporality::Delta,
+            count_metrics: false,
+            limits: Limits::new(),
+            families: OnceLock::new(),
+        }
+    }
+
+    /**
+    Truncate the modules of events to their first `depth` segments.
+
+    With a `depth` of `2`, events from `my_app::api::users` are counted under `my_app::api`. By default, modules aren't truncated.
+    */
+    pub const fn with_mdl_depth(mut self, depth: usize) -> Self {
+        self.mdl_depth = Some(depth);
+        self
+    }
+
+    /**
+    Count metric samples along with other events.
+
+    By default, events with an `evt_kind` of `metric` aren't counted, so reporting the counts of an [`EventVolume`] through the emitter it wraps doesn't inflate them.
+    */
+    pub const fn with_count_metrics(mut self, count_metrics: bool) -> Self {
+        self.count_metrics = count_metrics;
+        self
+    }
+
+    /**
+    Set how metrics accumulate between samples.
+    */
+    pub const fn with_temporality(mut self, temporality: Temporality) -> Self {
+        self.temporality = temporality;
+        self
+    }
+
+    /**
+    Set the maximum number of series for each metric.
+
+    See [`Family::with_max_series`] for details.
+    */
+    pub const fn with_max_series(mut self, max_series: usize) -> Self {
+        self.limits = self.limits.with_max_series(max_series);
+        self
+    }
+
+    /**
+    Expire series after they've been idle for `intervals` samples.
+
+    See [`Family::with_expire_after`] for details.
+    */
+    pub const fn with_expire_after(mut self, intervals: usize) -> Self {
+        self.limits = self.limits.with_expire_after(intervals);
+        self
+    }
+
+    /**
+    Wrap a `filter`, counting the events it rejects.
+    */
+    pub const fn count_dropped<F: Filter>(&self, filter: F) -> CountDropped<'_, F> {
+        CountDropped {
+            volume: self,
+            filter,
+        }
+    }
+
+    /**
+    Count an event as emitted.
+    */
+    pub fn observe(&self, evt: impl ToEvent) {
+        self.observe_into(&self.families().events, evt);
+    }
+
+    /**
+    Count an event as dropped.
+    */
+    pub fn observe_dropped(&self, evt: impl ToEvent) {
+        self.observe_into(&self.families().dropped, evt);
+    }
+
+    fn observe_into(&self, family: &Family<Counter>, evt: impl ToEvent) {
+        let evt = evt.to_event();
+
+        if !self.count_metrics && evt.props().pull::<Kind, _>(KEY_EVT_KIND) == Some(Kind::Metric) {
+            return;
+        }
+
+        let counter = match self.mdl_depth {
+            Some(depth) => {
+                let mdl = evt
+                    .mdl()
+                    .segments()
+                    .take(depth)
+                    .collect::<Vec<_>>()
+                    .join("::");
+
+                family.get(And::new((KEY_EVENT_MDL, &*mdl), evt.props()))
+            }
+            None => family.get(And::new((KEY_EVENT_MDL, evt.mdl()), evt.props())),
+        };
+
+        counter.increment();
+    }
+
+    fn families(&self) -> &Families {
+        self.families.get_or_init(|| Families {
+            events: self.family(
+                Counter::new(self.mdl.clone(), "events")
+                    .with_description("The number of events emitted")
+                    .with_temporality(self.temporality),
+            ),
+            dropped: self.family(
+                Counter::new(self.mdl.clone(), "events_dropped")
+                    .with_description("The number of events rejected by a filter")
+                    .with_temporality(self.temporality),
+            ),
+        })
+    }
+
+    fn family<I: Instrument>(&self, instrument: I) -> Family<I> {
+        self.limits.family(instrument, Self::LABELS)
+    }
+}
+
+impl Wrapping for EventVolume {
+    fn wrap<O: Emitter, E: ToEvent>(&self, output: O, evt: E) {
+        let evt = evt.to_event();
+
+        self.observe(&evt);
+
+        output.emit(evt);
+    }
+}
+
+impl Source for EventVolume {
+    fn sample_metrics<S: Sampler>(&self, sampler: S) {
+        let Some(families) = self.families.get() else {
+            return;
+        };
+
+        families.events.sample_metrics(&sampler);
+        families.dropped.sample_metrics(&sampler);
+    }
+}
+
+impl<'a, F: Filter> Filter for CountDropped<'a, F> {
+    fn matches<E: ToEvent>(&self, evt: E) -> bool {
+        let evt = evt.to_event();
+
+        if self.filter.matches(&evt) {
+            return true;
+        }
+
+        self.volume.observe_dropped(&evt);
+
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::{
+        cell::RefCell,
+        string::{String, ToString},
+    };
+
+    use emit_core::{event::Event, template::Template, value::Value, well_known::KEY_METRIC_NAME};
+
+    use crate::{Level, metric::sampler, testing::Capture};
+
+    fn event(mdl: &'static str, lvl: Level) -> Event<'static, impl emit_core::props::Props> {
+        Event::new(
+            Path::new_raw(mdl),
+            Template::literal("event"),
+            emit_core::empty::Empty,
+            (KEY_LVL, lvl),
+        )
+    }
+
+    fn sample(volume: &EventVolume) -> Vec<(String, Option<String>, Option<String>, u64)> {
+        let samples = RefCell::new(Vec::new());
+
+        volume.sample_metrics(sampler::from_fn(|metric| {
+            samples.borrow_mut().push((
+                metric.props().pull::<String, _>(KEY_METRIC_NAME).unwrap(),
+                metric.props().get(KEY_LVL).map(|lvl| lvl.to_string()),
+                metric.props().pull::<String, _>(KEY_EVENT_MDL),
+                metric.value().unwrap().cast::<u64>().unwrap(),
+            ));
+        }));
+
+        let mut samples = samples.into_inner();
+        samples.sort();
+
+        samples
+    }
+
+    fn expected(
+        name: &str,
+        lvl: &str,
+        mdl: &str,
+        value: u64,
+    ) -> (String, Option<String>, Option<String>, u64) {
+        (
+            name.to_string(),
+            Some(lvl.to_string()),
+            Some(mdl.to_string()),
+            value,
+        )
+    }
+
+    #[test]
+    fn observe_events() {
+        let volume = EventVolume::new(Path::new_raw("volume"));
+        let capture = Capture::new();
+
+        let emitter = emit_core::emitter::wrap(&capture, &volume);
+
+        emitter.emit(event("a", Level::Info));
+        emitter.emit(event("a", Level::Info));
+        emitter.emit(event("a", Level::Error));
+        emitter.emit(event("b", Level::Error));
+
+        // Events are passed through to the wrapped emitter
+        assert_eq!(4, capture.events().len());
+
+        assert_eq!(
+            vec![
+                expected("events", "error", "a", 1),
+                expected("events", "error", "b", 1),
+                expected("events", "info", "a", 2),
+            ],
+            sample(&volume)
+        );
+
+        // Counts are deltas by default
+        assert_eq!(
+            vec![
+                expected("events", "error", "a", 0),
+                expected("events", "error", "b", 0),
+                expected("events", "info", "a", 0),
+            ],
+            sample(&volume)
+        );
+    }
+
+    #[test]
+    fn observe_evt_kind() {
+        let volume = EventVolume::new(Path::new_raw("volume"));
+
+        volume.observe(Event::new(
+            Path::new_raw("a"),
+            Template::literal("span"),
+            emit_core::empty::Empty,
+            [(KEY_EVT_KIND, Value::from("span"))],
+        ));
+
+        let kinds = RefCell::new(Vec::new());
+        volume.sample_metrics(sampler::from_fn(|metric| {
+            kinds
+                .borrow_mut()
+                .push(metric.props().pull::<String, _>(KEY_EVT_KIND));
+        }));
+
+        assert_eq!(vec![Some("span".to_string())], kinds.into_inner());
+    }
+
+    #[test]
+    fn observe_mdl_depth() {
+        let volume = EventVolume::new(Path::new_raw("volume")).with_mdl_depth(2);
+
+        volume.observe(event("a::b::c", Level::Info));
+        volume.observe(event("a::b::d", Level::Info));
+        volume.observe(event("a", Level::Info));
+
+        assert_eq!(
+            vec![
+                expected("events", "info", "a", 1),
+                expected("events", "info", "a::b", 2),
+            ],
+            sample(&volume)
+        );
+    }
+
+    #[test]
+    fn observe_ignores_metrics() {
+        let metric = || {
+            Event::new(
+                Path::new_raw("a"),
+                Template::literal("metric"),
+                emit_core::empty::Empty,
+                And::new((KEY_LVL, Level::Info), (KEY_EVT_KIND, "metric")),
+            )
+        };
+
+        let volume = EventVolume::new(Path::new_raw("volume"));
+
+        volume.observe(metric());
+        volume.observe(event("a", Level::Info));
+
+        assert_eq!(vec![expected("events", "info", "a", 1)], sample(&volume));
+
+        let volume = EventVolume::new(Path::new_raw("volume")).with_count_metrics(true);
+
+        volume.observe(metric());
+        volume.observe(event("a", Level::Info));
+
+        // Metric samples are counted in their own series by `evt_kind`
+        assert_eq!(
+            vec![
+                expected("events", "info", "a", 1),
+                expected("events", "info", "a", 1),
+            ],
+            sample(&volume)
+        );
+    }
+
+    #[test]
+    fn count_metrics_through_wrapped_emitter() {
+        static VOLUME: EventVolume =
+            EventVolume::new(Path::new_raw("volume")).with_count_metrics(true);
+
+        let capture = Capture::new();
+        let emitter = emit_core::emitter::wrap(&capture, &VOLUME);
+
+        emitter.emit(event("a", Level::Info));
+
+        let mut reporter = crate::metric::Reporter::new();
+        reporter.add_source(&VOLUME);
+
+        // Samples are counted by `VOLUME` as they're emitted through it
+        reporter.emit_metrics(&emitter);
+
+        assert_eq!(2, capture.events().len());
+
+        assert_eq!(
+            vec![
+                expected("events", "info", "a", 0),
+                expected("events", "info", "volume", 1),
+            ],
+            sample(&VOLUME)
+        );
+    }
+
+    #[test]
+    fn count_dropped() {
+        let volume = EventVolume::new(Path::new_raw("volume"));
+        let filter = volume.count_dropped(crate::level::min_filter(Level::Warn));
+
+        assert!(!filter.matches(event("a", Level::Info)));
+        assert!(!filter.matches(event("a", Level::Debug)));
+        assert!(filter.matches(event("a", Level::Error)));
+
+        assert_eq!(
+            vec![
+                expected("events_dropped", "debug", "a", 1),
+                expected("events_dropped", "info", "a", 1),
+            ],
+            sample(&volume)
+        );
+    }
+}
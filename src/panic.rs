@@ -0,0 +1,315 @@
+/*!
+Emitting panics as diagnostic events.
+
+[`install_hook`] sets a [`std::panic::set_hook`] that emits an event at the error level whenever a thread panics:
+
+```
+fn main() {
+    let rt = emit::setup()
+        .emit_to(emit::emitter::from_fn(|evt| println!("{evt:?}")))
+        .init();
+
+    emit::panic::install_hook();
+
+    // Your app code goes here
+
+    rt.blocking_flush(std::time::Duration::from_secs(5));
+}
+```
+
+The event includes:
+
+- [`crate::well_known::KEY_ERR`]: The panic message, captured as an [`crate::err::Chain`], along with a backtrace if they're enabled through the `RUST_BACKTRACE` environment variable.
+- [`KEY_FILE`] and [`KEY_LINE`]: The source location where the panic happened.
+- [`KEY_THREAD`]: The name of the thread that panicked, if it has one.
+
+The event is emitted through the runtime's [`crate::Ctxt`], so a panic within a span will be correlated with it.
+
+Any panic hook that was installed before the `emit` one is still called after the event is emitted. If the crate is compiled with `panic = "abort"` then the hook will also flush the runtime, since the process won't get a chance to before it exits.
+
+Use [`Hook`] to install a panic hook for a runtime besides [`crate::runtime::shared`], or to configure how long to flush for.
+*/
+
+use core::{fmt, ops::ControlFlow, time::Duration};
+use std::{error::Error, panic::PanicHookInfo};
+
+use emit_core::{
+    event::Event,
+    path::Path,
+    props::Props,
+    runtime::AmbientSlot,
+    str::Str,
+    template::{self, Template},
+    value::{ToValue, Value},
+    well_known::{KEY_ERR, KEY_LVL},
+};
+
+use crate::{Level, err::Chain};
+
+/**
+The property for the file the panic happened in.
+*/
+pub const KEY_FILE: &'static str = "file";
+
+/**
+The property for the line the panic happened on.
+*/
+pub const KEY_LINE: &'static str = "line";
+
+/**
+The property for the name of the thread that panicked.
+*/
+pub const KEY_THREAD: &'static str = "thread";
+
+/**
+Install a panic hook that emits panics as events through [`crate::runtime::shared`].
+
+This method is a shorthand for [`hook`] followed by [`Hook::install`].
+*/
+#[cfg(feature = "implicit_rt")]
+pub fn install_hook() {
+    hook().install()
+}
+
+/**
+Get a panic hook for [`crate::runtime::shared`] that can be configured before it's installed.
+*/
+#[cfg(feature = "implicit_rt")]
+pub fn hook() -> Hook {
+    Hook::new(emit_core::runtime::shared_slot())
+}
+
+/**
+A panic hook that emits panics as events.
+
+The hook is installed by calling [`Hook::install`].
+*/
+pub struct Hook {
+    slot: &'static AmbientSlot,
+    mdl: Path<'static>,
+    flush_on_abort: Option<Duration>,
+}
+
+impl Hook {
+    /**
+    Create a panic hook that emits events through the runtime in the given `slot`.
+
+    The runtime doesn't need to be initialized yet. Panics are emitted through whatever runtime is in the slot at the time they happen.
+    */
+    pub fn new(slot: &'static AmbientSlot) -> Self {
+        Hook {
+            slot,
+            mdl: crate::mdl!(),
+            flush_on_abort: Some(Duration::from_secs(5)),
+        }
+    }
+
+    /**
+    Set the module that panic events will be emitted from.
+
+    The default module is `emit::panic`.
+    */
+    pub fn with_mdl(mut self, mdl: impl Into<Path<'static>>) -> Self {
+        self.mdl = mdl.into();
+        self
+    }
+
+    /**
+    Set the maximum amount of time to wait for the runtime to flush when the panic will abort the process.
+
+    The default timeout is 5 seconds.
+    */
+    pub fn flush_on_abort(mut self, timeout: Duration) -> Self {
+        self.flush_on_abort = Some(timeout);
+        self
+    }
+
+    /**
+    Don't flush the runtime when the panic will abort the process.
+    */
+    pub fn no_flush_on_abort(mut self) -> Self {
+        self.flush_on_abort = None;
+        self
+    }
+
+    /**
+    Install the panic hook.
+
+    The previously installed panic hook is called after the panic is emitted.
+    */
+    pub fn install(self) {
+        let prev = std::panic::take_hook();
+
+        std::panic::set_hook(Box::new(move |info| {
+            self.emit(info);
+
+            prev(info);
+        }))
+    }
+
+    fn emit(&self, info: &PanicHookInfo) {
+        let rt = self.slot.get();
+
+        let thread = std::thread::current();
+
+        let props = PanicProps {
+            err: crate::err::chain_dyn(&Payload(
+                info.payload_as_str().unwrap_or("Box<dyn Any>").to_owned(),
+            )),
+            location: info
+                .location()
+                .map(|location| (location.file(), location.line())),
+            thread: thread.name(),
+        };
+
+        rt.emit(Event::new(
+            self.mdl.by_ref(),
+            if props.location.is_some() {
+                LOCATION_TEMPLATE.by_ref()
+            } else {
+                TEMPLATE.by_ref()
+            },
+            crate::Empty,
+            props,
+        ));
+
+        if cfg!(panic = "abort")
+            && let Some(timeout) = self.flush_on_abort
+        {
+            rt.blocking_flush(timeout);
+        }
+    }
+}
+
+struct PanicProps<'a> {
+    err: Chain,
+    location: Option<(&'a str, u32)>,
+    thread: Option<&'a str>,
+}
+
+impl<'a> Props for PanicProps<'a> {
+    fn for_each<'kv, F: FnMut(Str<'kv>, Value<'kv>) -> ControlFlow<()>>(
+        &'kv self,
+        mut for_each: F,
+    ) -> ControlFlow<()> {
+        for_each(Str::new(KEY_LVL), Level::Error.to_value())?;
+        for_each(Str::new(KEY_ERR), self.err.to_value())?;
+
+        if let Some((file, line)) = &self.location {
+            for_each(Str::new(KEY_FILE), file.to_value())?;
+            for_each(Str::new(KEY_LINE), line.to_value())?;
+        }
+
+        if let Some(thread) = self.thread {
+            for_each(Str::new(KEY_THREAD), thread.to_value())?;
+        }
+
+        ControlFlow::Continue(())
+    }
+}
+
+#[derive(Debug)]
+struct Payload(String);
+
+impl fmt::Display for Payload {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl Error for Payload {}
+
+// "panicked at {file}:{line}: {err}"
+const LOCATION_TEMPLATE_PARTS: &'static [template::Part<'static>] = &[
+    template::Part::text("panicked at "),
+    template::Part::hole(KEY_FILE),
+    template::Part::text(":"),
+    template::Part::hole(KEY_LINE),
+    template::Part::text(": "),
+    template::Part::hole(KEY_ERR),
+];
+
+static LOCATION_TEMPLATE: Template<'static> = Template::new(LOCATION_TEMPLATE_PARTS);
+
+// "panicked: {err}"
+const TEMPLATE_PARTS: &'static [template::Part<'static>] = &[
+    template::Part::text("panicked: "),
+    template::Part::hole(KEY_ERR),
+];
+
+static TEMPLATE: Template<'static> = Template::new(TEMPLATE_PARTS);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Mutex;
+
+    struct Captured {
+        msg: String,
+        lvl: Option<Level>,
+        file: Option<String>,
+        line: Option<u32>,
+        thread: Option<String>,
+        chain: Option<String>,
+    }
+
+    #[test]
+    fn hook_emits_panic() {
+        static SLOT: AmbientSlot = AmbientSlot::new();
+        static EVENTS: Mutex<Vec<Captured>> = Mutex::new(Vec::new());
+
+        let _ = crate::setup()
+            .emit_to(crate::emitter::from_fn(|evt| {
+                let props = evt.props();
+
+                EVENTS.lock().unwrap().push(Captured {
+                    msg: evt.msg().to_string(),
+                    lvl: props.pull(KEY_LVL),
+                    file: props.get(KEY_FILE).map(|file| file.to_string()),
+                    line: props.pull(KEY_LINE),
+                    thread: props.get(KEY_THREAD).map(|thread| thread.to_string()),
+                    chain: props.get(KEY_ERR).and_then(|err| {
+                        err.downcast_ref::<Chain>()
+                            .map(|chain| chain.causes().next().unwrap().message().to_owned())
+                    }),
+                })
+            }))
+            .init_slot(&SLOT);
+
+        // Panic hooks are global, so the previous one is restored once the test has panicked
+        let prev = std::panic::take_hook();
+        Hook::new(&SLOT).install();
+
+        let line = line!() + 3;
+        let panicked = std::thread::Builder::new()
+            .name("panic_test".into())
+            .spawn(|| panic!("the emit panic hook test"))
+            .unwrap()
+            .join();
+
+        std::panic::set_hook(prev);
+
+        panicked.unwrap_err();
+
+        let events = EVENTS.lock().unwrap();
+
+        let evt = events
+            .iter()
+            .find(|evt| evt.msg.ends_with("the emit panic hook test"))
+            .unwrap();
+
+        assert_eq!(
+            format!("panicked at {}:{line}: the emit panic hook test", file!()),
+            evt.msg
+        );
+
+        assert_eq!(Some(Level::Error), evt.lvl);
+        assert_eq!(Some(file!()), evt.file.as_deref());
+        assert_eq!(Some(line), evt.line);
+        assert_eq!(Some("panic_test"), evt.thread.as_deref());
+        assert_eq!(Some("the emit panic hook test"), evt.chain.as_deref());
+
+        SLOT.get().blocking_flush(Duration::from_secs(1));
+    }
+}
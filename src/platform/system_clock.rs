@@ -2,10 +2,15 @@
 The [`SystemClock`] type.
 */
 
+use core::time::Duration;
+use std::{sync::OnceLock, time::Instant};
+
 use emit_core::{clock::Clock, runtime::InternalClock, timestamp::Timestamp};
 
 /**
 A [`Clock`] based on the standard library's [`std::time::SystemTime`].
+
+Monotonic readings are based on the standard library's [`std::time::Instant`].
 */
 #[derive(Default, Debug, Clone, Copy)]
 pub struct SystemClock {}
@@ -23,6 +28,14 @@ impl Clock for SystemClock {
     fn now(&self) -> Option<Timestamp> {
         Timestamp::from_unix(std::time::UNIX_EPOCH.elapsed().ok()?)
     }
+
+    fn now_monotonic(&self) -> Option<Duration> {
+        static ORIGIN: OnceLock<Instant> = OnceLock::new();
+
+        let origin = *ORIGIN.get_or_init(Instant::now);
+
+        Some(Instant::now().duration_since(origin))
+    }
 }
 
 impl InternalClock for SystemClock {}
@@ -37,4 +50,15 @@ mod tests {
     fn now() {
         assert!(SystemClock::new().now().is_some())
     }
+
+    #[test]
+    #[cfg(not(miri))]
+    fn now_monotonic() {
+        let clock = SystemClock::new();
+
+        let start = clock.now_monotonic().unwrap();
+        let end = clock.now_monotonic().unwrap();
+
+        assert!(end >= start);
+    }
 }
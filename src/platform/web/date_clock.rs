@@ -10,6 +10,8 @@ use emit_core::{clock::Clock, runtime::InternalClock, timestamp::Timestamp};
 
 /**
 A clock based on the [Date type](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date).
+
+Monotonic readings are based on [`performance.now()`](https://developer.mozilla.org/en-US/docs/Web/API/Performance/now), where it's available.
 */
 #[derive(Default, Debug, Clone, Copy)]
 pub struct DateClock {}
@@ -27,6 +29,10 @@ impl Clock for DateClock {
     fn now(&self) -> Option<Timestamp> {
         Timestamp::from_unix(date_now())
     }
+
+    fn now_monotonic(&self) -> Option<Duration> {
+        performance_now()
+    }
 }
 
 impl InternalClock for DateClock {}
@@ -42,6 +48,26 @@ fn date_now() -> Duration {
     Duration::new(timestamp_secs, timestamp_subsec_nanos)
 }
 
+fn performance_now() -> Option<Duration> {
+    let millis = performance::now().ok()?;
+
+    if !millis.is_finite() || millis < 0.0 {
+        return None;
+    }
+
+    Some(Duration::from_nanos((millis * 1_000_000.0) as u64))
+}
+
+mod performance {
+    use wasm_bindgen::prelude::*;
+
+    #[wasm_bindgen]
+    extern "C" {
+        #[wasm_bindgen(catch, js_namespace = performance, js_name = now)]
+        pub fn now() -> Result<f64, JsValue>;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -52,4 +78,15 @@ mod tests {
     fn date_clock_produces_timestamps() {
         assert_ne!(Timestamp::MIN, DateClock::new().now().unwrap());
     }
+
+    #[wasm_bindgen_test]
+    #[test]
+    fn date_clock_produces_monotonic_readings() {
+        let clock = DateClock::new();
+
+        let start = clock.now_monotonic().unwrap();
+        let end = clock.now_monotonic().unwrap();
+
+        assert!(end >= start);
+    }
 }
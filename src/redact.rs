@@ -0,0 +1,653 @@
+/*!
+Redacting sensitive properties before they're emitted.
+
+Properties can contain personally identifiable information, credentials, or other values that shouldn't be written to diagnostic backends. A [`Redact`] is a set of rules that match properties by their key, the type of their value, or whether they were captured with the [`macro@crate::sensitive`] attribute, and an [`Action`] to apply to them:
+
+- [`Action::drop`]: Remove the property.
+- [`Action::hash`]: Replace the value with a stable, non-cryptographic hash of it, so it can still be correlated across events.
+- [`Action::mask`]: Replace the value with `***`.
+- [`Action::truncate`]: Keep a prefix of the value, and drop the rest.
+
+Rules are checked in the order they're added, and the first one that matches a property is applied.
+
+A [`Redact`] needs to be applied both to emitters and to the ambient context, so that properties pushed by spans are redacted too. It's a [`crate::emitter::Wrapping`] that can be applied to an emitter with [`crate::emitter::wrap`], and a [`Ctxt`] can be wrapped with [`ctxt`]:
+
+```
+fn main() {
+    let redact = emit::redact::Redact::new()
+        .key("password", emit::redact::Action::drop())
+        .key_glob("*email*", emit::redact::Action::hash())
+        .sensitive(emit::redact::Action::mask());
+
+    let rt = emit::setup()
+        .emit_to(emit::emitter::from_fn(|evt| println!("{evt:?}")))
+        .map_emitter(|emitter| emit::emitter::wrap(emitter, redact.clone()))
+        .map_ctxt(|ctxt| emit::redact::ctxt(ctxt, redact))
+        .init();
+
+    // Your app code goes here
+
+    rt.blocking_flush(std::time::Duration::from_secs(5));
+}
+```
+
+Redacted values are also used when rendering the message of an event, so any holes in its template will show the redacted value. Holes for properties that are dropped will show their label instead.
+*/
+
+use core::{fmt, ops::ControlFlow};
+
+use emit_core::{
+    ctxt::Ctxt,
+    emitter::{Emitter, wrapping::Wrapping},
+    event::ToEvent,
+    props::Props,
+    str::Str,
+    value::{OwnedValue, ToValue, Value},
+};
+
+/**
+A set of rules for redacting properties.
+
+See the [module docs](self) for details.
+*/
+#[derive(Clone, Default)]
+pub struct Redact {
+    rules: Vec<Rule>,
+}
+
+#[derive(Clone)]
+struct Rule {
+    matcher: Matcher,
+    action: Action,
+}
+
+#[derive(Clone)]
+enum Matcher {
+    Key(String),
+    KeyGlob(String),
+    #[cfg(feature = "regex")]
+    KeyRegex(regex::Regex),
+    ValueType(fn(&Value) -> bool),
+    Sensitive,
+}
+
+/**
+An action to apply to a property matched by a [`Redact`] rule.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Action(ActionKind);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ActionKind {
+    Drop,
+    Hash,
+    Mask,
+    Truncate(usize),
+}
+
+impl Action {
+    /**
+    Remove the property entirely.
+    */
+    pub const fn drop() -> Self {
+        Action(ActionKind::Drop)
+    }
+
+    /**
+    Replace the value with a hash of its textual representation, like `#9f86d081884c7d65`.
+
+    The hash is stable, so the same value will always produce the same hash. It's not cryptographically secure though, so it shouldn't be used for values with low entropy that could be recovered by brute force.
+
+    Values that are already hashes aren't hashed again.
+    */
+    pub const fn hash() -> Self {
+        Action(ActionKind::Hash)
+    }
+
+    /**
+    Replace the value with `***`.
+    */
+    pub const fn mask() -> Self {
+        Action(ActionKind::Mask)
+    }
+
+    /**
+    Keep the first `len` characters of the textual representation of the value, replacing the rest with `…`.
+    */
+    pub const fn truncate(len: usize) -> Self {
+        Action(ActionKind::Truncate(len))
+    }
+}
+
+impl Redact {
+    /**
+    Create a new set of rules that doesn't redact anything.
+    */
+    pub fn new() -> Self {
+        Redact::default()
+    }
+
+    /**
+    Apply an action to properties with exactly the given key.
+    */
+    pub fn key(self, key: impl Into<String>, action: Action) -> Self {
+        self.rule(Matcher::Key(key.into()), action)
+    }
+
+    /**
+    Apply an action to properties with a key that matches a glob pattern.
+
+    In the pattern, `*` matches any sequence of characters, and `?` matches any single character. Any other characters must match exactly. For example, `*token*` matches `token`, `access_token`, and `tokens`.
+    */
+    pub fn key_glob(self, pattern: impl Into<String>, action: Action) -> Self {
+        self.rule(Matcher::KeyGlob(pattern.into()), action)
+    }
+
+    /**
+    Apply an action to properties with a key that matches a regular expression.
+    */
+    #[cfg(feature = "regex")]
+    pub fn key_regex(self, regex: regex::Regex, action: Action) -> Self {
+        self.rule(Matcher::KeyRegex(regex), action)
+    }
+
+    /**
+    Apply an action to properties with a value of the type `T`.
+
+    The type of a value can only be determined if it was captured with its type information, such as through [`Value::capture_display`], or the `#[emit::as_display(inspect: true)]` attribute.
+    */
+    pub fn value_type<T: 'static>(self, action: Action) -> Self {
+        self.rule(
+            Matcher::ValueType(|value| value.downcast_ref::<T>().is_some()),
+            action,
+        )
+    }
+
+    /**
+    Apply an action to properties captured with the [`macro@crate::sensitive`] attribute, or wrapped in [`Sensitive`].
+    */
+    pub fn sensitive(self, action: Action) -> Self {
+        self.rule(Matcher::Sensitive, action)
+    }
+
+    fn rule(mut self, matcher: Matcher, action: Action) -> Self {
+        self.rules.push(Rule { matcher, action });
+        self
+    }
+
+    /**
+    Redact the given properties.
+
+    Only the properties that are redacted are stored in the result.
+    */
+    fn redact_props(&self, props: impl Props) -> Vec<(Str<'static>, Option<String>)> {
+        let mut redacted = Vec::new();
+
+        if self.rules.is_empty() {
+            return redacted;
+        }
+
+        let _ = props.for_each(|k, v| {
+            if let Some(action) = self.action(&k, &v) {
+                redacted.push((k.to_owned(), redact_value(action, &v)));
+            }
+
+            ControlFlow::Continue(())
+        });
+
+        redacted
+    }
+
+    fn action(&self, key: &Str, value: &Value) -> Option<Action> {
+        self.rules.iter().find_map(|rule| {
+            let matched = match rule.matcher {
+                Matcher::Key(ref k) => key.get() == k,
+                Matcher::KeyGlob(ref pattern) => glob_matches(pattern, key.get()),
+                #[cfg(feature = "regex")]
+                Matcher::KeyRegex(ref regex) => regex.is_match(key.get()),
+                Matcher::ValueType(matches) => matches(value),
+                Matcher::Sensitive => is_sensitive(value),
+            };
+
+            if matched { Some(rule.action) } else { None }
+        })
+    }
+}
+
+impl fmt::Debug for Redact {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Redact").finish_non_exhaustive()
+    }
+}
+
+impl Wrapping for Redact {
+    fn wrap<O: Emitter, E: ToEvent>(&self, output: O, evt: E) {
+        let evt = evt.to_event();
+
+        let redacted = self.redact_props(evt.props());
+
+        if redacted.is_empty() {
+            output.emit(evt);
+        } else {
+            output.emit(evt.map_props(|props| Redacted { props, redacted }));
+        }
+    }
+}
+
+/**
+Wrap a [`Ctxt`] so any properties pushed to it are redacted by the given [`Redact`].
+*/
+pub fn ctxt<C: Ctxt>(ctxt: C, redact: Redact) -> RedactCtxt<C> {
+    RedactCtxt { ctxt, redact }
+}
+
+/**
+A [`Ctxt`] that redacts properties pushed to it.
+
+This type is returned by [`ctxt`].
+*/
+#[derive(Debug)]
+pub struct RedactCtxt<C> {
+    ctxt: C,
+    redact: Redact,
+}
+
+impl<C> RedactCtxt<C> {
+    /**
+    Get a reference to the underlying [`Ctxt`].
+    */
+    pub const fn inner(&self) -> &C {
+        &self.ctxt
+    }
+}
+
+impl<C: Ctxt> Ctxt for RedactCtxt<C> {
+    type Current = C::Current;
+    type Frame = C::Frame;
+
+    fn open_root<P: Props>(&self, props: P) -> Self::Frame {
+        let redacted = self.redact.redact_props(&props);
+
+        self.ctxt.open_root(Redacted { props, redacted })
+    }
+
+    fn open_push<P: Props>(&self, props: P) -> Self::Frame {
+        let redacted = self.redact.redact_props(&props);
+
+        self.ctxt.open_push(Redacted { props, redacted })
+    }
+
+    fn open_disabled<P: Props>(&self, props: P) -> Self::Frame {
+        let redacted = self.redact.redact_props(&props);
+
+        self.ctxt.open_disabled(Redacted { props, redacted })
+    }
+
+    fn enter(&self, frame: &mut Self::Frame) {
+        self.ctxt.enter(frame)
+    }
+
+    fn with_current<R, F: FnOnce(&Self::Current) -> R>(&self, with: F) -> R {
+        self.ctxt.with_current(with)
+    }
+
+    fn exit(&self, frame: &mut Self::Frame) {
+        self.ctxt.exit(frame)
+    }
+
+    fn close(&self, frame: Self::Frame) {
+        self.ctxt.close(frame)
+    }
+}
+
+struct Redacted<P> {
+    props: P,
+    redacted: Vec<(Str<'static>, Option<String>)>,
+}
+
+impl<P: Props> Props for Redacted<P> {
+    fn for_each<'kv, F: FnMut(Str<'kv>, Value<'kv>) -> ControlFlow<()>>(
+        &'kv self,
+        mut for_each: F,
+    ) -> ControlFlow<()> {
+        self.props
+            .for_each(|k, v| match self.redacted.iter().find(|(rk, _)| *rk == k) {
+                Some((_, Some(redacted))) => for_each(k, redacted.to_value()),
+                Some((_, None)) => ControlFlow::Continue(()),
+                None => for_each(k, v),
+            })
+    }
+
+    fn is_unique(&self) -> bool {
+        self.props.is_unique()
+    }
+}
+
+fn redact_value(action: Action, value: &Value) -> Option<String> {
+    match action.0 {
+        ActionKind::Drop => None,
+        ActionKind::Hash => {
+            let value = value.to_string();
+
+            if is_hash(&value) {
+                return Some(value);
+            }
+
+            Some(format!("#{:016x}", fnv1a(value.as_bytes())))
+        }
+        ActionKind::Mask => Some("***".to_owned()),
+        ActionKind::Truncate(len) => {
+            let mut value = value.to_string();
+
+            if let Some((i, _)) = value.char_indices().nth(len) {
+                // Don't truncate values that have already been truncated
+                if value[i..] != *"…" {
+                    value.truncate(i);
+                    value.push('…');
+                }
+            }
+
+            Some(value)
+        }
+    }
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash = 0xcbf29ce484222325u64;
+
+    for b in bytes {
+        hash ^= *b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+
+    hash
+}
+
+fn is_hash(value: &str) -> bool {
+    value.len() == 17
+        && value.starts_with('#')
+        && value[1..]
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+}
+
+fn glob_matches(pattern: &str, input: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let input = input.chars().collect::<Vec<_>>();
+
+    let (mut p, mut i) = (0, 0);
+
+    // The position of the last `*` in the pattern, and the input it was matched at
+    let mut backtrack = None;
+
+    while i < input.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, i));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == input[i] => {
+                p += 1;
+                i += 1;
+            }
+            _ => match backtrack {
+                // Let the last `*` consume another character
+                Some((star, matched)) => {
+                    backtrack = Some((star, matched + 1));
+                    p = star + 1;
+                    i = matched + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/**
+A value that's marked as sensitive.
+
+Sensitive values are matched by [`Redact::sensitive`]. They're otherwise formatted the same as the value they were created from. The [`macro@crate::sensitive`] attribute captures values using this type.
+
+Values are type-erased when they're captured, so a sensitive value is converted into an owned value up-front. That lets it be captured as a `Sensitive` itself, which can still be detected after it's been converted into a [`Value`].
+*/
+#[derive(Clone)]
+pub struct Sensitive(OwnedValue);
+
+impl Sensitive {
+    /**
+    Mark a value as sensitive.
+    */
+    pub fn new(value: impl ToValue) -> Self {
+        Sensitive(value.to_value().to_owned())
+    }
+
+    /**
+    Get the inner value.
+    */
+    pub fn inner(&self) -> Value<'_> {
+        self.0.by_ref()
+    }
+}
+
+impl fmt::Display for Sensitive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Debug for Sensitive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl ToValue for Sensitive {
+    fn to_value(&self) -> Value<'_> {
+        Value::capture_display(self)
+    }
+}
+
+fn is_sensitive(value: &Value) -> bool {
+    value.downcast_ref::<Sensitive>().is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{Event, Path, Template};
+
+    fn redact_to_string(redact: &Redact, evt: Event<impl Props>) -> String {
+        let emitted = std::sync::Mutex::new(String::new());
+
+        redact.wrap(
+            crate::emitter::from_fn(|evt| {
+                let mut props = Vec::new();
+                let _ = evt.props().for_each(|k, v| {
+                    props.push(format!("{k}={v}"));
+                    ControlFlow::Continue(())
+                });
+
+                *emitted.lock().unwrap() = format!("{} ({})", evt.msg(), props.join(", "));
+            }),
+            evt,
+        );
+
+        emitted.into_inner().unwrap()
+    }
+
+    #[test]
+    fn redact_actions() {
+        let redact = Redact::new()
+            .key("password", Action::drop())
+            .key("email", Action::hash())
+            .key("token", Action::mask())
+            .key("name", Action::truncate(3));
+
+        let parts = [
+            crate::template::Part::hole("email"),
+            crate::template::Part::text(" "),
+            crate::template::Part::hole("password"),
+        ];
+        let tpl = Template::new_ref(&parts);
+
+        assert_eq!(
+            format!(
+                "#{:016x} {{password}} (email=#{:016x}, token=***, name=Rus…, other=true)",
+                fnv1a(b"user@example.com"),
+                fnv1a(b"user@example.com")
+            ),
+            redact_to_string(
+                &redact,
+                Event::new(
+                    Path::new_raw("test"),
+                    tpl,
+                    crate::Empty,
+                    [
+                        ("email", Value::from("user@example.com")),
+                        ("password", Value::from("hunter2")),
+                        ("token", Value::from("abc")),
+                        ("name", Value::from("Rustacean")),
+                        ("other", Value::from(true)),
+                    ],
+                ),
+            )
+        );
+    }
+
+    #[test]
+    fn redact_is_idempotent() {
+        for action in [Action::hash(), Action::mask(), Action::truncate(3)] {
+            let once = redact_value(action, &Value::from("user@example.com")).unwrap();
+            let twice = redact_value(action, &Value::from(&*once)).unwrap();
+
+            assert_eq!(once, twice, "{action:?}");
+        }
+    }
+
+    #[test]
+    fn redact_first_matching_rule() {
+        let redact = Redact::new()
+            .key("user_id", Action::truncate(2))
+            .key_glob("user_*", Action::drop());
+
+        assert_eq!(
+            " (user_id=12…)",
+            redact_to_string(
+                &redact,
+                Event::new(
+                    Path::new_raw("test"),
+                    Template::literal(""),
+                    crate::Empty,
+                    [
+                        ("user_id", Value::from(1234)),
+                        ("user_name", Value::from("a"))
+                    ],
+                ),
+            )
+        );
+    }
+
+    #[test]
+    fn redact_value_type() {
+        struct Email(&'static str);
+
+        impl fmt::Display for Email {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.0)
+            }
+        }
+
+        let email = Email("user@example.com");
+
+        let redact = Redact::new().value_type::<Email>(Action::mask());
+
+        assert_eq!(
+            " (a=***, b=user@example.com)",
+            redact_to_string(
+                &redact,
+                Event::new(
+                    Path::new_raw("test"),
+                    Template::literal(""),
+                    crate::Empty,
+                    [
+                        ("a", Value::capture_display(&email)),
+                        ("b", Value::from_display(&email)),
+                    ],
+                ),
+            )
+        );
+    }
+
+    #[test]
+    fn redact_sensitive() {
+        let redact = Redact::new().sensitive(Action::mask());
+
+        let email = "user@example.com";
+
+        assert_eq!(
+            " (a=***, b=user@example.com)",
+            redact_to_string(
+                &redact,
+                Event::new(
+                    Path::new_raw("test"),
+                    Template::literal(""),
+                    crate::Empty,
+                    [
+                        ("a", Sensitive::new(email).to_value()),
+                        ("b", email.to_value()),
+                    ],
+                ),
+            )
+        );
+
+        // Sensitive values are formatted normally outside of redaction
+        assert_eq!(email, Sensitive::new(email).to_string());
+        assert!(!is_sensitive(&Value::from(email)));
+    }
+
+    #[test]
+    fn redact_ctxt() {
+        let redact = Redact::new().key("email", Action::mask());
+
+        let ctxt = ctxt(
+            crate::platform::thread_local_ctxt::ThreadLocalCtxt::new(),
+            redact,
+        );
+
+        let mut frame = ctxt.open_push([("email", "user@example.com"), ("user", "Rust")]);
+        ctxt.enter(&mut frame);
+
+        ctxt.with_current(|props| {
+            assert_eq!("***", props.get("email").unwrap().to_string());
+            assert_eq!("Rust", props.get("user").unwrap().to_string());
+        });
+
+        ctxt.exit(&mut frame);
+        ctxt.close(frame);
+    }
+
+    #[test]
+    fn glob() {
+        for (pattern, input, expected) in [
+            ("", "", true),
+            ("a", "a", true),
+            ("a", "b", false),
+            ("*", "", true),
+            ("*", "abc", true),
+            ("a*", "abc", true),
+            ("*c", "abc", true),
+            ("*b*", "abc", true),
+            ("*b*", "ac", false),
+            ("a?c", "abc", true),
+            ("a?c", "ac", false),
+            ("*token*", "access_token_id", true),
+            ("a*b*c", "aXbYbZc", true),
+            ("a*b*c", "aXbYbZ", false),
+        ] {
+            assert_eq!(expected, glob_matches(pattern, input), "{pattern} {input}");
+        }
+    }
+}
@@ -314,6 +314,28 @@ impl<TEmitter: Emitter, TFilter: Filter, TCtxt: Ctxt, TClock: Clock, TRng: Rng>
         }
     }
 
+    /**
+    Take a final sample from a background metric [`crate::metric::Reporter`] whenever the runtime is flushed.
+
+    The `reporter` is sampled before the [`Emitter`] is flushed, so its metrics are included in the flush.
+    */
+    #[cfg(feature = "std")]
+    pub fn with_reporter(
+        self,
+        reporter: crate::metric::ReporterHandle,
+    ) -> Setup<crate::metric::ReportOnFlush<TEmitter>, TFilter, TCtxt, TClock, TRng> {
+        Setup {
+            emitter: SetupCell {
+                value: crate::metric::ReportOnFlush::new(self.emitter.value, reporter),
+                set: self.emitter.set,
+            },
+            filter: self.filter,
+            ctxt: self.ctxt,
+            clock: self.clock,
+            rng: self.rng,
+        }
+    }
+
     /**
     Initialize a standalone runtime.
     */
@@ -0,0 +1,832 @@
+/*!
+Utilities for testing instrumented code.
+
+The [`Harness`] type is a self-contained runtime that captures events into memory instead of emitting them. It uses a [`FixedClock`] and [`FixedRng`] so the timestamps and ids assigned to events are the same each time a test runs:
+
+```
+# #[cfg(not(feature = "implicit_rt"))] fn main() {}
+# #[cfg(feature = "implicit_rt")]
+fn main() {
+    let harness = emit::testing::Harness::new();
+    let rt = harness.rt();
+
+    let (mut outer, frame) = emit::span_guard!(rt, "outer");
+    frame.call(move || {
+        outer.start();
+
+        let (mut inner, frame) = emit::span_guard!(rt, "inner");
+        frame.call(move || {
+            inner.start();
+
+            emit::error!(rt, "failed to connect to {host}", host: "localhost");
+        });
+    });
+
+    harness.assert_span_with_parent("inner", "outer");
+    harness.assert_event(emit::Level::Error, [("host", "localhost")]);
+}
+```
+
+The runtime is initialized through [`crate::Setup::init_slot`], so it's independent of [`crate::runtime::shared`], and of any other harness. Events need to be emitted through [`Harness::rt`] to be captured, such as with the `rt` control parameter on `emit`'s macros.
+
+The [`Capture`] type is the emitter used by [`Harness`], and can also be used on its own with any runtime. Captured events can be inspected directly through [`Capture::events`], checked with assertion helpers like [`Capture::assert_span`], or rendered to text with [`Capture::render`] for comparing against a snapshot.
+*/
+
+use core::{
+    fmt::{self, Write as _},
+    ops::{ControlFlow, Deref},
+    time::Duration,
+};
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Instant,
+};
+
+use emit_core::{
+    clock::Clock,
+    emitter::Emitter,
+    event::{Event, ToEvent},
+    props::{OwnedProps, Props},
+    rng::Rng,
+    runtime::{AmbientRuntime, AmbientSlot},
+    timestamp::Timestamp,
+    well_known::{
+        KEY_EVT_KIND, KEY_LVL, KEY_SPAN_ID, KEY_SPAN_NAME, KEY_SPAN_PARENT, KEY_TRACE_ID,
+    },
+};
+
+use crate::{Kind, Level, SpanId, TraceId, platform::thread_local_ctxt::ThreadLocalCtxt};
+
+/**
+A self-contained runtime for tests that captures events into memory.
+
+The harness dereferences to its [`Capture`], so assertion helpers can be called on it directly.
+*/
+pub struct Harness {
+    slot: AmbientSlot,
+    capture: Capture,
+}
+
+impl Harness {
+    /**
+    Create a new harness.
+
+    The runtime uses a [`Capture`] as its emitter, a [`FixedClock`] starting at [`Timestamp::MIN`], a [`FixedRng`], and its own [`ThreadLocalCtxt`].
+    */
+    pub fn new() -> Self {
+        Self::with(FixedClock::default(), FixedRng::default())
+    }
+
+    /**
+    Create a new harness with the given clock and source of randomness.
+    */
+    pub fn with(
+        clock: impl Clock + Send + Sync + 'static,
+        rng: impl Rng + Send + Sync + 'static,
+    ) -> Self {
+        let slot = AmbientSlot::new();
+        let capture = Capture::new();
+
+        let _ = crate::setup()
+            .emit_to(capture.clone())
+            .with_ctxt(ThreadLocalCtxt::new())
+            .with_clock(clock)
+            .with_rng(rng)
+            .init_slot(&slot);
+
+        Harness { slot, capture }
+    }
+
+    /**
+    Get the runtime to emit events through.
+    */
+    pub fn rt(&self) -> &AmbientRuntime<'_> {
+        self.slot.get()
+    }
+
+    /**
+    Get the events captured by the runtime.
+    */
+    pub fn capture(&self) -> &Capture {
+        &self.capture
+    }
+}
+
+impl Default for Harness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for Harness {
+    type Target = Capture;
+
+    fn deref(&self) -> &Capture {
+        &self.capture
+    }
+}
+
+/**
+An [`Emitter`] that captures events into memory.
+
+Clones of a `Capture` share the same set of events.
+*/
+#[derive(Clone, Default)]
+pub struct Capture {
+    events: Arc<Mutex<Vec<Event<'static, OwnedProps>>>>,
+}
+
+impl Capture {
+    /**
+    Create a new, empty capture.
+    */
+    pub fn new() -> Self {
+        Capture::default()
+    }
+
+    /**
+    Get a copy of the events captured so far, in the order they were emitted.
+    */
+    pub fn events(&self) -> Vec<Event<'static, OwnedProps>> {
+        self.events.lock().unwrap().clone()
+    }
+
+    /**
+    Remove all captured events.
+    */
+    pub fn clear(&self) {
+        self.events.lock().unwrap().clear();
+    }
+
+    /**
+    Get the spans captured so far with the given name.
+    */
+    pub fn spans(&self, name: &str) -> Vec<Event<'static, OwnedProps>> {
+        self.events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|evt| is_span(evt, name))
+            .cloned()
+            .collect()
+    }
+
+    /**
+    Assert that exactly one span with the given name was captured, returning it.
+
+    # Panics
+
+    This method panics if there are no spans with the given name, or if there's more than one.
+    */
+    #[track_caller]
+    pub fn assert_span(&self, name: &str) -> Event<'static, OwnedProps> {
+        let mut spans = self.spans(name);
+
+        if spans.len() != 1 {
+            panic!(
+                "expected exactly 1 span named `{name}`, but found {}\n\ncaptured events:\n{}",
+                spans.len(),
+                self.render()
+            );
+        }
+
+        spans.remove(0)
+    }
+
+    /**
+    Assert that exactly one span with the given name was captured, and that it's a child of exactly one span with the name `parent`, returning it.
+
+    # Panics
+
+    This method panics if there isn't exactly one span with each of the given names, or if they don't share a trace and aren't parent and child.
+    */
+    #[track_caller]
+    pub fn assert_span_with_parent(&self, name: &str, parent: &str) -> Event<'static, OwnedProps> {
+        let span = self.assert_span(name);
+        let parent_span = self.assert_span(parent);
+
+        let props = span.props();
+        let parent_props = parent_span.props();
+
+        let is_child = props.pull::<TraceId, _>(KEY_TRACE_ID).is_some()
+            && props.pull::<TraceId, _>(KEY_TRACE_ID)
+                == parent_props.pull::<TraceId, _>(KEY_TRACE_ID)
+            && props.pull::<SpanId, _>(KEY_SPAN_PARENT).is_some()
+            && props.pull::<SpanId, _>(KEY_SPAN_PARENT)
+                == parent_props.pull::<SpanId, _>(KEY_SPAN_ID);
+
+        if !is_child {
+            panic!(
+                "expected span `{name}` to be a child of span `{parent}`\n\ncaptured events:\n{}",
+                self.render()
+            );
+        }
+
+        span
+    }
+
+    /**
+    Assert that at least one event was captured with the given level and properties, returning the first.
+
+    Property values are compared by their formatted representation, so `[("id", 42)]` and `[("id", "42")]` are equivalent. The event may have other properties besides the ones given.
+
+    # Panics
+
+    This method panics if there are no matching events.
+    */
+    #[track_caller]
+    pub fn assert_event(&self, lvl: Level, props: impl Props) -> Event<'static, OwnedProps> {
+        let matched = self
+            .events
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|evt| {
+                evt.props().pull::<Level, _>(KEY_LVL) == Some(lvl)
+                    && props
+                        .for_each(|k, v| match evt.props().get(k) {
+                            Some(actual) if actual.to_string() == v.to_string() => {
+                                ControlFlow::Continue(())
+                            }
+                            _ => ControlFlow::Break(()),
+                        })
+                        .is_continue()
+            })
+            .cloned();
+
+        match matched {
+            Some(evt) => evt,
+            None => {
+                let mut expected = String::new();
+                let _ = props.for_each(|k, v| {
+                    let _ = write!(expected, ", {k}: {v}");
+                    ControlFlow::Continue(())
+                });
+
+                panic!(
+                    "expected an event with lvl: {lvl}{expected}\n\ncaptured events:\n{}",
+                    self.render()
+                );
+            }
+        }
+    }
+
+    /**
+    Render the captured events to text.
+
+    Each event is written on its own line, with its extent, module, and message, followed by its properties sorted by key on indented lines:
+
+    ```text
+    1970-01-01T00:00:00.000000000Z my_app: failed to connect to localhost
+      host: localhost
+      lvl: error
+    ```
+
+    The output is stable for a given set of events, so it's suitable for comparing against a snapshot. Use a [`FixedClock`] and [`FixedRng`], like [`Harness`] does, to make timestamps and ids stable too.
+    */
+    pub fn render(&self) -> String {
+        let mut rendered = String::new();
+
+        for evt in self.events.lock().unwrap().iter() {
+            let _ = render_event(&mut rendered, evt);
+        }
+
+        rendered
+    }
+}
+
+impl Emitter for Capture {
+    fn emit<E: ToEvent>(&self, evt: E) {
+        let evt = evt.to_event().to_owned();
+
+        self.events.lock().unwrap().push(evt);
+    }
+
+    fn blocking_flush(&self, _: core::time::Duration) -> bool {
+        true
+    }
+}
+
+fn is_span(evt: &Event<OwnedProps>, name: &str) -> bool {
+    evt.props().pull::<Kind, _>(KEY_EVT_KIND) == Some(Kind::Span)
+        && evt
+            .props()
+            .get(KEY_SPAN_NAME)
+            .is_some_and(|span_name| span_name.to_string() == name)
+}
+
+fn render_event(mut w: impl fmt::Write, evt: &Event<OwnedProps>) -> fmt::Result {
+    if let Some(extent) = evt.extent() {
+        write!(w, "{extent} ")?;
+    }
+
+    writeln!(w, "{}: {}", evt.mdl(), evt.msg())?;
+
+    let mut props = Vec::new();
+    let _ = evt.props().for_each(|k, v| {
+        props.push((k.to_string(), v.to_string()));
+        ControlFlow::Continue(())
+    });
+    props.sort();
+
+    for (k, v) in props {
+        writeln!(w, "  {k}: {v}")?;
+    }
+
+    Ok(())
+}
+
+/**
+A [`Clock`] that always returns the same timestamp.
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(Timestamp);
+
+impl FixedClock {
+    /**
+    Create a clock that always returns the given timestamp.
+    */
+    pub const fn new(ts: Timestamp) -> Self {
+        FixedClock(ts)
+    }
+}
+
+impl Default for FixedClock {
+    fn default() -> Self {
+        FixedClock::new(Timestamp::MIN)
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> Option<Timestamp> {
+        Some(self.0)
+    }
+}
+
+/**
+An [`Rng`] that produces a fixed sequence of numbers.
+
+Each call to [`Rng::gen_u64`] or [`Rng::gen_u128`] returns the next number in the sequence, starting from `1`. That means ids generated from it, like [`TraceId`]s and [`SpanId`]s, are small, unique, and the same each time a test runs.
+*/
+#[derive(Debug, Default)]
+pub struct FixedRng(AtomicU64);
+
+impl FixedRng {
+    /**
+    Create a source of randomness that starts its sequence from `1`.
+    */
+    pub const fn new() -> Self {
+        FixedRng(AtomicU64::new(0))
+    }
+
+    fn next(&self) -> u64 {
+        self.0.fetch_add(1, Ordering::Relaxed).wrapping_add(1)
+    }
+}
+
+impl Rng for FixedRng {
+    fn fill<A: AsMut<[u8]>>(&self, mut arr: A) -> Option<A> {
+        for chunk in arr.as_mut().chunks_mut(8) {
+            let next = self.next().to_be_bytes();
+
+            chunk.copy_from_slice(&next[8 - chunk.len()..]);
+        }
+
+        Some(arr)
+    }
+
+    fn gen_u64(&self) -> Option<u64> {
+        Some(self.next())
+    }
+
+    fn gen_u128(&self) -> Option<u128> {
+        Some(self.next() as u128)
+    }
+}
+
+/**
+A [`Clock`] that only moves when it's told to.
+
+A `ManualClock` starts frozen at a given timestamp. It can be moved with [`ManualClock::advance`] or [`ManualClock::set`], or made to follow real time with [`ManualClock::resume`] until it's frozen again with [`ManualClock::freeze`].
+
+Clones of a `ManualClock` share the same time, so one can be passed to [`crate::Setup::with_clock`] or [`crate::metric::Reporter::normalize_with_clock`] while another is kept to control it:
+
+```
+use std::time::Duration;
+
+let clock = emit::testing::ManualClock::default();
+
+let timer = emit::timer::Timer::start(clock.clone());
+
+clock.advance(Duration::from_secs(1));
+
+assert_eq!(Some(Duration::from_secs(1)), timer.elapsed());
+```
+*/
+#[derive(Debug, Clone)]
+pub struct ManualClock(Arc<Mutex<ManualClockState>>);
+
+#[derive(Debug)]
+struct ManualClockState {
+    now: Timestamp,
+    resumed: Option<Instant>,
+}
+
+impl ManualClockState {
+    fn now(&self) -> Timestamp {
+        match self.resumed {
+            Some(resumed) => self
+                .now
+                .checked_add(resumed.elapsed())
+                .unwrap_or(Timestamp::MAX),
+            None => self.now,
+        }
+    }
+}
+
+impl ManualClock {
+    /**
+    Create a clock that's frozen at the given timestamp.
+    */
+    pub fn new(ts: Timestamp) -> Self {
+        ManualClock(Arc::new(Mutex::new(ManualClockState {
+            now: ts,
+            resumed: None,
+        })))
+    }
+
+    /**
+    Set the clock to the given timestamp.
+
+    If the clock isn't frozen, it will continue to follow real time from `ts`.
+    */
+    pub fn set(&self, ts: Timestamp) {
+        let mut state = self.0.lock().unwrap();
+
+        state.now = ts;
+        if state.resumed.is_some() {
+            state.resumed = Some(Instant::now());
+        }
+    }
+
+    /**
+    Move the clock forwards by the given duration.
+
+    If the clock would move past [`Timestamp::MAX`] then it will stop there.
+    */
+    pub fn advance(&self, by: Duration) {
+        let mut state = self.0.lock().unwrap();
+
+        state.now = state.now.checked_add(by).unwrap_or(Timestamp::MAX);
+    }
+
+    /**
+    Stop the clock at its current time.
+
+    Calling this method on a frozen clock does nothing.
+    */
+    pub fn freeze(&self) {
+        let mut state = self.0.lock().unwrap();
+
+        state.now = state.now();
+        state.resumed = None;
+    }
+
+    /**
+    Make the clock follow real time from its current time.
+
+    Calling this method on a clock that isn't frozen does nothing.
+    */
+    pub fn resume(&self) {
+        let mut state = self.0.lock().unwrap();
+
+        if state.resumed.is_none() {
+            state.resumed = Some(Instant::now());
+        }
+    }
+
+    /**
+    Whether the clock is frozen.
+    */
+    pub fn is_frozen(&self) -> bool {
+        self.0.lock().unwrap().resumed.is_none()
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        ManualClock::new(Timestamp::MIN)
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Option<Timestamp> {
+        Some(self.0.lock().unwrap().now())
+    }
+}
+
+/**
+A [`Clock`] that follows `tokio`'s time.
+
+The clock starts at a given timestamp, and moves forwards with [`tokio::time::Instant`]. That means it stops when time is paused with [`tokio::time::pause`], and moves when it's advanced with [`tokio::time::advance`], or auto-advanced by a paused runtime. This requires `tokio`'s `test-util` feature.
+*/
+#[cfg(feature = "tokio")]
+#[derive(Debug, Clone, Copy)]
+pub struct TokioClock {
+    start: Timestamp,
+    origin: tokio::time::Instant,
+}
+
+#[cfg(feature = "tokio")]
+impl TokioClock {
+    /**
+    Create a clock that starts at the given timestamp.
+    */
+    pub fn new(start: Timestamp) -> Self {
+        TokioClock {
+            start,
+            origin: tokio::time::Instant::now(),
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Default for TokioClock {
+    fn default() -> Self {
+        TokioClock::new(Timestamp::MIN)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Clock for TokioClock {
+    fn now(&self) -> Option<Timestamp> {
+        self.start.checked_add(self.origin.elapsed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use emit_core::value::ToValue;
+
+    use crate::metric::sampler::Sampler as _;
+
+    #[test]
+    fn fixed_rng_sequence() {
+        let rng = FixedRng::new();
+
+        assert_eq!(Some(1), rng.gen_u64());
+        assert_eq!(Some(2), rng.gen_u128());
+        assert_eq!(Some([0, 3]), rng.fill([0u8; 2]));
+    }
+
+    #[test]
+    fn harness_is_deterministic() {
+        fn run() -> String {
+            let harness = Harness::new();
+
+            let rt = harness.rt();
+
+            let (mut span, frame) = crate::span::SpanGuard::new(
+                rt.filter(),
+                rt.ctxt(),
+                rt.clock(),
+                rt.rng(),
+                crate::span::completion::default_from_runtime(rt),
+                crate::Empty,
+                crate::mdl!(),
+                [(KEY_SPAN_NAME, "test")],
+            );
+
+            frame.call(move || {
+                span.start();
+
+                rt.emit(crate::Event::new(
+                    crate::mdl!(),
+                    crate::Template::literal("in span"),
+                    crate::Empty,
+                    [(KEY_LVL, Level::Info)],
+                ));
+            });
+
+            harness.render()
+        }
+
+        let first = run();
+        let second = run();
+
+        assert_eq!(first, second);
+        assert!(first.contains("  trace_id: 00000000000000000000000000000001\n"));
+        assert!(first.contains("  span_id: 0000000000000002\n"));
+    }
+
+    #[test]
+    fn harness_is_isolated() {
+        let a = Harness::new();
+        let b = Harness::new();
+
+        a.rt().emit(crate::Event::new(
+            crate::mdl!(),
+            crate::Template::literal("a"),
+            crate::Empty,
+            crate::Empty,
+        ));
+
+        assert_eq!(1, a.events().len());
+        assert_eq!(0, b.events().len());
+    }
+
+    #[test]
+    fn assert_span_with_parent() {
+        let capture = Capture::new();
+        let trace_id = TraceId::from_u128(1).unwrap();
+
+        let span = |name: &'static str, span_id: u64, span_parent: Option<u64>| {
+            capture.emit(crate::Event::new(
+                crate::mdl!(),
+                crate::Template::literal(name),
+                crate::Empty,
+                [
+                    (KEY_EVT_KIND, Kind::Span.to_value()),
+                    (KEY_SPAN_NAME, name.to_value()),
+                    (KEY_TRACE_ID, trace_id.to_value()),
+                ]
+                .and_props([(KEY_SPAN_ID, SpanId::from_u64(span_id).unwrap())])
+                .and_props([(KEY_SPAN_PARENT, span_parent.and_then(SpanId::from_u64))]),
+            ))
+        };
+
+        span("inner", 2, Some(1));
+        span("outer", 1, None);
+        span("other", 3, None);
+
+        assert_eq!(
+            "inner",
+            capture
+                .assert_span_with_parent("inner", "outer")
+                .msg()
+                .to_string()
+        );
+
+        let result = std::panic::catch_unwind(|| capture.assert_span_with_parent("other", "outer"));
+        assert!(result.is_err());
+
+        let result = std::panic::catch_unwind(|| capture.assert_span("missing"));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn assert_event() {
+        let capture = Capture::new();
+
+        capture.emit(crate::Event::new(
+            crate::mdl!(),
+            crate::Template::literal("test"),
+            crate::Empty,
+            [(KEY_LVL, Level::Error)]
+                .and_props([("id", 42)])
+                .and_props([("host", "localhost")]),
+        ));
+
+        capture.assert_event(Level::Error, [("id", 42)]);
+        capture.assert_event(Level::Error, [("id", "42")]);
+        capture.assert_event(Level::Error, [("host", "localhost")]);
+
+        let result = std::panic::catch_unwind(|| capture.assert_event(Level::Warn, [("id", 42)]));
+        assert!(result.is_err());
+
+        let result = std::panic::catch_unwind(|| capture.assert_event(Level::Error, [("id", 43)]));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn render() {
+        let capture = Capture::new();
+
+        capture.emit(crate::Event::new(
+            crate::Path::new_raw("my_app"),
+            crate::Template::literal("failed to connect"),
+            Timestamp::MIN,
+            [(KEY_LVL, Level::Error)].and_props([("host", "localhost")]),
+        ));
+
+        assert_eq!(
+            "1970-01-01T00:00:00.000000000Z my_app: failed to connect\n  host: localhost\n  lvl: error\n",
+            capture.render()
+        );
+    }
+
+    #[test]
+    fn manual_clock_advance() {
+        let clock = ManualClock::default();
+        let shared = clock.clone();
+
+        assert!(clock.is_frozen());
+        assert_eq!(Some(Timestamp::MIN), shared.now());
+
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(Timestamp::from_unix(Duration::from_secs(1)), shared.now());
+
+        clock.set(Timestamp::MAX);
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(Some(Timestamp::MAX), shared.now());
+    }
+
+    #[test]
+    fn manual_clock_freeze_resume() {
+        let clock = ManualClock::default();
+
+        clock.resume();
+        assert!(!clock.is_frozen());
+
+        std::thread::sleep(Duration::from_millis(1));
+        clock.freeze();
+        assert!(clock.is_frozen());
+
+        let frozen = clock.now().unwrap();
+        assert!(frozen > Timestamp::MIN);
+
+        std::thread::sleep(Duration::from_millis(1));
+        assert_eq!(Some(frozen), clock.now());
+    }
+
+    #[test]
+    fn manual_clock_timer() {
+        let clock = ManualClock::default();
+
+        let timer = crate::Timer::start(clock.clone());
+        clock.advance(Duration::from_secs(3));
+
+        assert_eq!(Some(Duration::from_secs(3)), timer.elapsed());
+        assert_eq!(
+            Timestamp::from_unix(Duration::from_secs(3)),
+            timer.extent().map(|extent| extent.as_range().unwrap().end)
+        );
+    }
+
+    #[test]
+    fn manual_clock_delta() {
+        let clock = ManualClock::default();
+
+        let mut delta = crate::metric::Delta::<usize>::new_default(clock.now());
+        *delta.current_value_mut() += 1;
+
+        clock.advance(Duration::from_secs(10));
+
+        let (extent, value) = delta.advance_default(clock.now());
+
+        assert_eq!(1, value);
+        assert_eq!(Some(Duration::from_secs(10)), extent.unwrap().len());
+    }
+
+    #[test]
+    fn manual_clock_reporter() {
+        let clock = ManualClock::default();
+
+        let mut reporter = crate::metric::Reporter::new();
+        reporter
+            .normalize_with_clock(clock.clone())
+            .add_source(crate::metric::source::from_fn(|sampler| {
+                sampler.metric(crate::Metric::new(
+                    crate::Path::new_raw("test"),
+                    crate::Empty,
+                    crate::Empty,
+                ));
+            }));
+
+        clock.advance(Duration::from_secs(5));
+
+        let harness = Harness::new();
+        reporter.emit_metrics(harness.rt().emitter());
+
+        assert_eq!(
+            Timestamp::from_unix(Duration::from_secs(5)),
+            harness.events()[0]
+                .extent()
+                .map(|extent| *extent.as_point())
+        );
+    }
+
+    #[cfg(all(
+        feature = "tokio",
+        not(all(
+            target_arch = "wasm32",
+            target_vendor = "unknown",
+            target_os = "unknown"
+        ))
+    ))]
+    #[tokio::test(start_paused = true)]
+    async fn tokio_clock_paused() {
+        let clock = TokioClock::default();
+
+        assert_eq!(Some(Timestamp::MIN), clock.now());
+
+        tokio::time::sleep(Duration::from_secs(60)).await;
+
+        assert_eq!(Timestamp::from_unix(Duration::from_secs(60)), clock.now());
+    }
+}
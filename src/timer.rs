@@ -1,7 +1,7 @@
 /*!
 The [`Timer`] type.
 
-Timers are a simple mechanism to track the start and end times of some operation. They're based on readings from a [`Clock`], which isn't guaranteed to be monotonic. That means timers can give an approximate timespan based on its readings, but are susceptible to clock drift.
+Timers are a simple mechanism to track the start and end times of some operation. They're based on readings from a [`Clock`], which isn't guaranteed to be monotonic. If the clock supports monotonic readings through [`Clock::now_monotonic`] then the timer uses them to measure the time elapsed since it started, and only uses [`Clock::now`] for its start timestamp. That means shifts in the clock, like those made by NTP, won't produce negative or inflated timespans. If the clock doesn't support monotonic readings then timers can give an approximate timespan based on its readings, but are susceptible to clock drift.
 
 Timers are used by [`crate::Span`]s to produce the [`Extent`] on their events.
 */
@@ -16,16 +16,20 @@ A timer that measures the point when an operation started and ended.
 #[derive(Clone, Copy)]
 pub struct Timer<C> {
     start: Option<Timestamp>,
+    start_monotonic: Option<Duration>,
     clock: C,
 }
 
 impl<C: Clock> Timer<C> {
     /**
     Start a timer using [`Clock::now`] as its initial reading.
+
+    If the clock supports [`Clock::now_monotonic`] then an initial monotonic reading is also taken.
     */
     pub fn start(clock: C) -> Self {
         Timer {
             start: clock.now(),
+            start_monotonic: clock.now_monotonic(),
             clock,
         }
     }
@@ -40,17 +44,27 @@ impl<C: Clock> Timer<C> {
     }
 
     /**
-    Get the value of the timer as an [`Extent`], using [`Clock::now`] as its final reading.
+    Get the value of the timer as an [`Extent`].
+
+    If the underlying [`Clock`] supports [`Clock::now_monotonic`] then the end of the extent is the initial reading plus the monotonic time elapsed since. Otherwise, [`Clock::now`] is used as its final reading.
 
     If the underlying [`Clock`] is unable to produce a reading then this method will return `None`.
     */
     pub fn extent(&self) -> Option<Extent> {
-        let end = self.clock.now();
+        let start = self.start?;
 
-        match (self.start, end) {
-            (Some(start), Some(end)) => Some(Extent::range(start..end)),
-            _ => None,
-        }
+        let end = match self.elapsed_monotonic() {
+            Some(elapsed) => start.checked_add(elapsed),
+            None => self.clock.now(),
+        }?;
+
+        Some(Extent::range(start..end))
+    }
+
+    fn elapsed_monotonic(&self) -> Option<Duration> {
+        self.clock
+            .now_monotonic()?
+            .checked_sub(self.start_monotonic?)
     }
 
     /**
@@ -58,8 +72,8 @@ impl<C: Clock> Timer<C> {
 
     If the underlying [`Clock`] is unable to produce a reading, or it shifts to before the initial reading, then this method will return `None`.
 
-    This method is not guaranteed to return the actual time elapsed since the timer was started.
-    It's based on the difference between two readings of the underlying [`Clock`], which is not guaranteed to be monotonic.
+    If the underlying [`Clock`] supports [`Clock::now_monotonic`] then this method returns the actual time elapsed since the timer was started.
+    Otherwise, it's not guaranteed to, because it's based on the difference between two readings of [`Clock::now`], which is not guaranteed to be monotonic.
     */
     pub fn elapsed(&self) -> Option<Duration> {
         self.extent().and_then(|extent| extent.len())
@@ -71,6 +85,7 @@ impl<C: Clock> Timer<C> {
     pub fn by_ref(&self) -> Timer<&C> {
         Timer {
             start: self.start,
+            start_monotonic: self.start_monotonic,
             clock: &self.clock,
         }
     }
@@ -150,6 +165,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn timer_monotonic() {
+        struct MonotonicClock {
+            now: Cell<Timestamp>,
+            now_monotonic: Cell<Duration>,
+        }
+
+        impl Clock for MonotonicClock {
+            fn now(&self) -> Option<Timestamp> {
+                Some(self.now.get())
+            }
+
+            fn now_monotonic(&self) -> Option<Duration> {
+                Some(self.now_monotonic.get())
+            }
+        }
+
+        let clock = MonotonicClock {
+            now: Cell::new(Timestamp::from_unix(Duration::from_secs(10)).unwrap()),
+            now_monotonic: Cell::new(Duration::from_secs(100)),
+        };
+
+        let timer = Timer::start(&clock);
+
+        // The wall-clock shifts backwards while the monotonic clock moves forwards
+        clock
+            .now
+            .set(Timestamp::from_unix(Duration::from_secs(0)).unwrap());
+        clock.now_monotonic.set(Duration::from_secs(102));
+
+        assert_eq!(Duration::from_secs(2), timer.elapsed().unwrap());
+
+        assert_eq!(
+            Timestamp::from_unix(Duration::from_secs(10)).unwrap()
+                ..Timestamp::from_unix(Duration::from_secs(12)).unwrap(),
+            timer.extent().unwrap().as_range().unwrap().clone()
+        );
+    }
+
     #[test]
     fn timer_empty() {
         let timer = Timer::start(crate::Empty);
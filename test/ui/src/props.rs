@@ -561,3 +561,37 @@ fn props_as_serde() {
         serde_json::to_string(&props.get("a").unwrap()).unwrap()
     );
 }
+
+#[test]
+#[cfg(feature = "std")]
+fn props_sensitive() {
+    use emit::Emitter as _;
+
+    let email = "user@example.com";
+    let name = ::std::string::String::from("Rustacean");
+
+    let props = emit::props! {
+        #[emit::sensitive] email,
+        #[emit::sensitive] name: &name,
+    };
+
+    assert_eq!("user@example.com", props.get("email").unwrap().to_string());
+    assert_eq!("Rustacean", props.get("name").unwrap().to_string());
+
+    let redact = emit::redact::Redact::new().sensitive(emit::redact::Action::mask());
+
+    let emitted = ::std::sync::Mutex::new(::std::string::String::new());
+    emit::emitter::wrap(
+        emit::emitter::from_fn(|evt| *emitted.lock().unwrap() = evt.msg().to_string()),
+        redact,
+    )
+    .emit(emit::evt!(
+        "{email} {name} {upper} {other}",
+        #[emit::sensitive] email,
+        #[emit::sensitive] name,
+        #[emit::sensitive] upper: name.to_uppercase(),
+        other: "visible",
+    ));
+
+    assert_eq!("*** *** *** visible", *emitted.lock().unwrap());
+}
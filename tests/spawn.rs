@@ -0,0 +1,31 @@
+/*!
+Tests for carrying ambient properties into spawned tasks.
+
+These tests initialize the shared runtime, so they live in their own test binary.
+*/
+
+#![cfg(all(feature = "implicit_rt", feature = "tokio"))]
+
+#[tokio::test]
+async fn spawn_in_current_ctxt() {
+    let capture = emit::testing::Capture::new();
+
+    let _rt = emit::setup().emit_to(capture.clone()).init();
+
+    outer().await;
+
+    capture.assert_span_with_parent("spawn", "outer");
+    capture.assert_span_with_parent("spawn_blocking", "outer");
+}
+
+#[emit::span("outer")]
+async fn outer() {
+    emit::spawn(spawned()).await.unwrap();
+    emit::spawn_blocking(spawned_blocking).await.unwrap();
+}
+
+#[emit::span("spawn")]
+async fn spawned() {}
+
+#[emit::span("spawn_blocking")]
+fn spawned_blocking() {}